@@ -3,21 +3,26 @@ use knukki::*;
 pub const EXAMPLE_NAME: &'static str = "hover-color-circle-grid";
 
 pub fn create_app() -> Application {
-    let mut menu = SimpleFlatMenu::new(Some(Color::rgb(150, 150, 250)));
+    // The outer columns and rows are only used as margins around the 50x50 circles
+    let mut columns = vec![GridTrackSize::Weight(10.0)];
+    columns.extend((0..50).map(|_| GridTrackSize::Weight(1.5)));
+    columns.push(GridTrackSize::Weight(15.0));
+    let mut rows = vec![GridTrackSize::Weight(15.0)];
+    rows.extend((0..50).map(|_| GridTrackSize::Weight(1.5)));
+    rows.push(GridTrackSize::Weight(10.0));
+
+    let mut menu = GridMenu::new(Some(Color::rgb(150, 150, 250)), columns, rows);
     for x in 0..50 {
         for y in 0..50 {
-            let min_x = 0.1 + 0.015 * x as f32;
-            let min_y = 0.1 + 0.015 * y as f32;
-            let max_x = 0.1 + 0.015 * (x + 1) as f32;
-            let max_y = 0.1 + 0.015 * (y + 1) as f32;
             let base_color = Color::rgb(100, 3 * x, 3 * y);
             let hover_color = Color::rgb(200, 5 * x, 5 * y);
+            // The rows are numbered from top to bottom
             menu.add_component(
                 Box::new(HoverColorCircleComponent::new(base_color, hover_color)),
-                ComponentDomain::between(min_x, min_y, max_x, max_y),
+                GridCell::single(1 + x as usize, 50 - y as usize),
             );
         }
     }
 
     Application::new(Box::new(menu))
-}
\ No newline at end of file
+}
@@ -0,0 +1,170 @@
+use crate::*;
+
+use std::time::Duration;
+
+/// A value of type `T` that can be animated. Components can store an `Animated` value instead of
+/// a plain value, and `start` an `Animation` on it (or use `animate_to` to start a `Tween` from
+/// the current value).
+///
+/// The animation is timed with the `get_current_time` method of the `ComponentBuddy`. While an
+/// animation is running, each call to `get` will also call `request_animation_frame`, so the
+/// component will keep being re-rendered until the animation is finished. Thus a component should
+/// simply call `get` during its `render` method.
+///
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// struct FadingComponent {
+///     color: Animated<Color>,
+/// }
+///
+/// impl Component for FadingComponent {
+///     fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+///         buddy.subscribe_mouse_enter();
+///     }
+///
+///     fn render(&mut self, renderer: &Renderer, buddy: &mut dyn ComponentBuddy, _force: bool)
+///     -> RenderResult {
+///         renderer.clear(self.color.get(buddy));
+///         entire_render_result()
+///     }
+///
+///     fn on_mouse_enter(&mut self, _event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+///         let target = Color::rgb(200, 0, 0);
+///         self.color.animate_to(target, Duration::from_millis(300), Easing::QuadOut, buddy);
+///     }
+/// }
+/// ```
+pub struct Animated<T: Interpolate> {
+    value: T,
+    running: Option<RunningAnimation<T>>,
+}
+
+struct RunningAnimation<T> {
+    animation: Box<dyn Animation<T>>,
+    start_time: Duration,
+}
+
+impl<T: Interpolate + 'static> Animated<T> {
+    /// Constructs a new `Animated` with the given initial `value` that is not being animated
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            running: None,
+        }
+    }
+
+    /// Starts playing the given `animation`, beginning at the current time of the `buddy`. If
+    /// another animation was running, it will be stopped.
+    pub fn start(&mut self, animation: Box<dyn Animation<T>>, buddy: &mut dyn ComponentBuddy) {
+        self.running = Some(RunningAnimation {
+            animation,
+            start_time: buddy.get_current_time(),
+        });
+        buddy.request_animation_frame();
+    }
+
+    /// Starts a `Tween` from the current value to the given `target`. This is convenient to
+    /// smoothly respond to state changes (like a mouse that starts hovering over the component):
+    /// if another animation was still running, the new animation will start at the value it had
+    /// reached.
+    pub fn animate_to(
+        &mut self,
+        target: T,
+        duration: Duration,
+        easing: Easing,
+        buddy: &mut dyn ComponentBuddy,
+    ) {
+        let current_value = self.get(buddy);
+        self.start(
+            Box::new(Tween::new(current_value, target, duration, easing)),
+            buddy,
+        );
+    }
+
+    /// Stops the running animation (if any), and immediately changes the value to `value`
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+        self.running = None;
+    }
+
+    /// Gets the value at the current time of the `buddy`. If an animation is running and not yet
+    /// finished, this method will call `request_animation_frame` on the `buddy`.
+    pub fn get(&mut self, buddy: &mut dyn ComponentBuddy) -> T {
+        if let Some(running) = &self.running {
+            let elapsed = buddy
+                .get_current_time()
+                .checked_sub(running.start_time)
+                .unwrap_or(Duration::from_secs(0));
+            self.value = running.animation.value_at(elapsed);
+            if elapsed >= running.animation.get_duration() {
+                self.running = None;
+            } else {
+                buddy.request_animation_frame();
+            }
+        }
+        self.value
+    }
+
+    /// Checks whether an animation is running. Note that an animation will only be marked as
+    /// finished during a call to `get`.
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_animated() {
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_current_time(Duration::from_millis(1000));
+        buddy.clear_render_request();
+
+        let mut value = Animated::new(2.0);
+        assert_eq!(2.0, value.get(&mut buddy));
+        assert!(!value.is_running());
+        assert!(!buddy.did_request_render());
+
+        value.animate_to(4.0, Duration::from_millis(100), Easing::Linear, &mut buddy);
+        assert!(value.is_running());
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+
+        buddy.set_current_time(Duration::from_millis(1050));
+        assert_eq!(3.0, value.get(&mut buddy));
+        assert!(buddy.did_request_render());
+        buddy.clear_render_request();
+
+        // Changing the target halfway should continue from the current value
+        value.animate_to(1.0, Duration::from_millis(200), Easing::Linear, &mut buddy);
+        buddy.clear_render_request();
+        buddy.set_current_time(Duration::from_millis(1150));
+        assert_eq!(2.0, value.get(&mut buddy));
+        buddy.clear_render_request();
+
+        // When the animation is finished, no more renders should be requested
+        buddy.set_current_time(Duration::from_millis(1300));
+        assert_eq!(1.0, value.get(&mut buddy));
+        assert!(!value.is_running());
+        assert!(!buddy.did_request_render());
+
+        value.start(
+            Box::new(Tween::new(
+                5.0,
+                6.0,
+                Duration::from_millis(100),
+                Easing::Linear,
+            )),
+            &mut buddy,
+        );
+        value.set(8.0);
+        assert!(!value.is_running());
+        assert_eq!(8.0, value.get(&mut buddy));
+    }
+}
@@ -0,0 +1,99 @@
+use std::f32::consts::PI;
+
+/// Determines how the progress of an animation is distributed over its duration. `Linear` will
+/// change the value at a constant speed, while the other easing curves start and/or end slower.
+///
+/// The *In* variants start slow and end fast, the *Out* variants start fast and end slow, and the
+/// *InOut* variants start and end slow.
+#[derive(Copy, Clone, Debug)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut,
+    /// Moves a bit beyond the target value before settling at it
+    BackOut,
+    /// A custom easing curve. The function will be given the linear progress (between 0.0 and
+    /// 1.0) and should return the eased progress. It should map 0.0 to 0.0 and 1.0 to 1.0.
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Applies this easing curve to the given linear `progress`, which will be clamped between
+    /// 0.0 and 1.0. The result is 0.0 at the start and 1.0 at the end, but can go a bit outside
+    /// that range in between (for instance for `BackOut`).
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => match t < 0.5 {
+                true => 2.0 * t * t,
+                false => 1.0 - 2.0 * (1.0 - t) * (1.0 - t),
+            },
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t) * (1.0 - t) * (1.0 - t),
+            Easing::CubicInOut => match t < 0.5 {
+                true => 4.0 * t * t * t,
+                false => 1.0 - 4.0 * (1.0 - t) * (1.0 - t) * (1.0 - t),
+            },
+            Easing::SineInOut => 0.5 - 0.5 * (PI * t).cos(),
+            Easing::BackOut => {
+                let overshoot = 1.70158;
+                let u = t - 1.0;
+                1.0 + u * u * ((overshoot + 1.0) * u + overshoot)
+            }
+            Easing::Custom(function) => function(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_end_points() {
+        let easings = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SineInOut,
+            Easing::BackOut,
+            Easing::Custom(|t| t * t * t * t),
+        ];
+        for easing in &easings {
+            assert!(easing.apply(0.0).abs() < 0.001);
+            assert!((easing.apply(1.0) - 1.0).abs() < 0.001);
+
+            // The progress should be clamped
+            assert!(easing.apply(-1.0).abs() < 0.001);
+            assert!((easing.apply(2.0) - 1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_shapes() {
+        assert_eq!(0.5, Easing::Linear.apply(0.5));
+        assert_eq!(0.25, Easing::QuadIn.apply(0.5));
+        assert_eq!(0.75, Easing::QuadOut.apply(0.5));
+        assert_eq!(0.5, Easing::QuadInOut.apply(0.5));
+        assert_eq!(0.125, Easing::CubicIn.apply(0.5));
+        assert_eq!(0.875, Easing::CubicOut.apply(0.5));
+        assert_eq!(0.5, Easing::CubicInOut.apply(0.5));
+        assert!((Easing::SineInOut.apply(0.5) - 0.5).abs() < 0.001);
+
+        // BackOut should overshoot the target a bit
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::*;
+
+/// Types that can be animated: it must be possible to compute the values *between* 2 values of
+/// the type.
+pub trait Interpolate: Copy {
+    /// Computes the value between `self` and `target`: a `progress` of 0.0 should return `self`
+    /// and a `progress` of 1.0 should return `target`. The `progress` can be a bit smaller than
+    /// 0.0 or larger than 1.0 when an easing curve overshoots.
+    fn interpolate(self, target: Self, progress: f32) -> Self;
+}
+
+fn lerp(from: f32, to: f32, progress: f32) -> f32 {
+    from + (to - from) * progress
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        lerp(self, target, progress)
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        self + (target - self) * progress
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        // Overshooting easing curves could move the channels out of range, so they are clamped
+        let channel = |from: u8, to: u8| -> u8 {
+            lerp(from as f32, to as f32, progress)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Color::rgba(
+            channel(self.get_red_int(), target.get_red_int()),
+            channel(self.get_green_int(), target.get_green_int()),
+            channel(self.get_blue_int(), target.get_blue_int()),
+            channel(self.get_alpha_int(), target.get_alpha_int()),
+        )
+    }
+}
+
+impl Interpolate for ComponentDomain {
+    fn interpolate(self, target: Self, progress: f32) -> Self {
+        ComponentDomain::between(
+            lerp(self.get_min_x(), target.get_min_x(), progress),
+            lerp(self.get_min_y(), target.get_min_y(), progress),
+            lerp(self.get_max_x(), target.get_max_x(), progress),
+            lerp(self.get_max_y(), target.get_max_y(), progress),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_f32() {
+        assert_eq!(3.0, 2.0.interpolate(6.0, 0.25));
+        assert_eq!(7.0, 2.0.interpolate(6.0, 1.25));
+    }
+
+    #[test]
+    fn test_point() {
+        assert!(Point::new(1.0, 2.0)
+            .interpolate(Point::new(3.0, -2.0), 0.5)
+            .nearly_equal(Point::new(2.0, 0.0)));
+    }
+
+    #[test]
+    fn test_color() {
+        let from = Color::rgba(0, 100, 200, 255);
+        let to = Color::rgba(100, 100, 0, 55);
+        assert_eq!(from, from.interpolate(to, 0.0));
+        assert_eq!(Color::rgba(50, 100, 100, 155), from.interpolate(to, 0.5));
+        assert_eq!(to, from.interpolate(to, 1.0));
+
+        // The channels should stay in range when the progress overshoots
+        assert_eq!(Color::rgba(0, 100, 255, 255), from.interpolate(to, -0.5));
+        assert_eq!(Color::rgba(150, 100, 0, 0), from.interpolate(to, 1.5));
+    }
+
+    #[test]
+    fn test_domain() {
+        let from = ComponentDomain::between(0.0, 0.0, 0.5, 0.5);
+        let to = ComponentDomain::between(0.5, 0.25, 1.0, 1.0);
+        assert_eq!(
+            ComponentDomain::between(0.25, 0.125, 0.75, 0.75),
+            from.interpolate(to, 0.5)
+        );
+    }
+}
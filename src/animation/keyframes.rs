@@ -0,0 +1,134 @@
+use super::*;
+
+use std::time::Duration;
+
+/// An `Animation` that passes through a number of values (the *keyframes*) at given moments in
+/// time. Between 2 keyframes, the value is interpolated using the easing curve of the latter
+/// keyframe.
+///
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// // Grow from 0.0 to 1.0 during the first 100ms, stay there for 200ms, and shrink back to 0.5
+/// let mut keyframes = Keyframes::new(0.0);
+/// keyframes.add_keyframe(Duration::from_millis(100), 1.0, Easing::QuadOut);
+/// keyframes.add_keyframe(Duration::from_millis(300), 1.0, Easing::Linear);
+/// keyframes.add_keyframe(Duration::from_millis(400), 0.5, Easing::Linear);
+///
+/// assert_eq!(Duration::from_millis(400), keyframes.get_duration());
+/// assert_eq!(1.0, keyframes.value_at(Duration::from_millis(200)));
+/// assert_eq!(0.75, keyframes.value_at(Duration::from_millis(350)));
+/// ```
+pub struct Keyframes<T: Interpolate> {
+    start_value: T,
+    keyframes: Vec<Keyframe<T>>,
+}
+
+struct Keyframe<T> {
+    time: Duration,
+    value: T,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// Constructs a new `Keyframes` animation that starts with the given `start_value`. Until
+    /// keyframes are added, its duration will be 0.
+    pub fn new(start_value: T) -> Self {
+        Self {
+            start_value,
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// Adds a keyframe: the value of the animation will be `value` at the given `time` (relative
+    /// to the start of the animation). The `easing` will be used to move from the previous
+    /// keyframe to this keyframe.
+    ///
+    /// ### Panics
+    /// This method will panic if `time` is earlier than the time of the previous keyframe.
+    pub fn add_keyframe(&mut self, time: Duration, value: T, easing: Easing) {
+        assert!(time >= self.get_duration());
+        self.keyframes.push(Keyframe {
+            time,
+            value,
+            easing,
+        });
+    }
+}
+
+impl<T: Interpolate> Animation<T> for Keyframes<T> {
+    fn get_duration(&self) -> Duration {
+        match self.keyframes.last() {
+            Some(keyframe) => keyframe.time,
+            None => Duration::from_secs(0),
+        }
+    }
+
+    fn value_at(&self, elapsed: Duration) -> T {
+        let mut previous_time = Duration::from_secs(0);
+        let mut previous_value = self.start_value;
+        for keyframe in &self.keyframes {
+            if elapsed < keyframe.time {
+                let progress = progress_of(elapsed - previous_time, keyframe.time - previous_time);
+                return previous_value.interpolate(keyframe.value, keyframe.easing.apply(progress));
+            }
+            previous_time = keyframe.time;
+            previous_value = keyframe.value;
+        }
+        previous_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_keyframes() {
+        let mut keyframes = Keyframes::new(Point::new(0.0, 0.0));
+        assert_eq!(Duration::from_secs(0), keyframes.get_duration());
+        assert_eq!(
+            Point::new(0.0, 0.0),
+            keyframes.value_at(Duration::from_millis(10))
+        );
+
+        keyframes.add_keyframe(
+            Duration::from_millis(100),
+            Point::new(1.0, 0.0),
+            Easing::Linear,
+        );
+        keyframes.add_keyframe(
+            Duration::from_millis(300),
+            Point::new(1.0, 1.0),
+            Easing::QuadIn,
+        );
+        assert_eq!(Duration::from_millis(300), keyframes.get_duration());
+
+        assert!(keyframes
+            .value_at(Duration::from_millis(0))
+            .nearly_equal(Point::new(0.0, 0.0)));
+        assert!(keyframes
+            .value_at(Duration::from_millis(50))
+            .nearly_equal(Point::new(0.5, 0.0)));
+        assert!(keyframes
+            .value_at(Duration::from_millis(100))
+            .nearly_equal(Point::new(1.0, 0.0)));
+        assert!(keyframes
+            .value_at(Duration::from_millis(200))
+            .nearly_equal(Point::new(1.0, 0.25)));
+        assert!(keyframes
+            .value_at(Duration::from_millis(500))
+            .nearly_equal(Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_keyframes_out_of_order() {
+        let mut keyframes = Keyframes::new(0.0);
+        keyframes.add_keyframe(Duration::from_millis(100), 1.0, Easing::Linear);
+        keyframes.add_keyframe(Duration::from_millis(50), 2.0, Easing::Linear);
+    }
+}
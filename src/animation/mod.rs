@@ -0,0 +1,40 @@
+mod animated;
+mod easing;
+mod interpolate;
+mod keyframes;
+mod sequence;
+mod tween;
+
+pub use animated::*;
+pub use easing::*;
+pub use interpolate::*;
+pub use keyframes::*;
+pub use sequence::*;
+pub use tween::*;
+
+use std::time::Duration;
+
+/// Something that determines a value of type `T` for every moment during a fixed period of time.
+/// The simplest implementation is `Tween`, but `Keyframes` and `Sequence` can be used to build
+/// more complicated animations.
+///
+/// Animations only describe how the value changes over time: to play an animation in a
+/// `Component`, it should be given to an `Animated` value.
+pub trait Animation<T> {
+    /// Gets the total duration of this animation
+    fn get_duration(&self) -> Duration;
+
+    /// Computes the value of this animation after `elapsed` time has passed since the start of
+    /// the animation. If `elapsed` is larger than the duration of this animation, the final value
+    /// of the animation should be returned.
+    fn value_at(&self, elapsed: Duration) -> T;
+}
+
+/// Computes how far `elapsed` is in a period of `duration`, as a number between 0.0 and 1.0
+fn progress_of(elapsed: Duration, duration: Duration) -> f32 {
+    if elapsed >= duration {
+        1.0
+    } else {
+        elapsed.as_secs_f32() / duration.as_secs_f32()
+    }
+}
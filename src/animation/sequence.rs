@@ -0,0 +1,95 @@
+use super::*;
+
+use std::time::Duration;
+
+/// An `Animation` that plays other animations one after another. This can for instance be used
+/// to chain `Tween`s with different easing curves, or to wait a while between 2 animations.
+///
+/// The sequence doesn't force the animations to connect: if an animation ends with another
+/// value than the next animation starts with, the value will jump.
+pub struct Sequence<T: Interpolate> {
+    animations: Vec<Box<dyn Animation<T>>>,
+}
+
+impl<T: Interpolate + 'static> Sequence<T> {
+    /// Constructs a new `Sequence` that starts with the given `first` animation
+    pub fn new(first: Box<dyn Animation<T>>) -> Self {
+        Self {
+            animations: vec![first],
+        }
+    }
+
+    /// Appends the given `animation` to this sequence: it will start when the previous animation
+    /// ends.
+    pub fn add(&mut self, animation: Box<dyn Animation<T>>) {
+        self.animations.push(animation);
+    }
+
+    /// Appends a pause to this sequence: the final value of the previous animation will be kept
+    /// during the given `duration`.
+    pub fn add_pause(&mut self, duration: Duration) {
+        let last_value = self.value_at(self.get_duration());
+        self.add(Box::new(Tween::new(
+            last_value,
+            last_value,
+            duration,
+            Easing::Linear,
+        )));
+    }
+}
+
+impl<T: Interpolate> Animation<T> for Sequence<T> {
+    fn get_duration(&self) -> Duration {
+        self.animations
+            .iter()
+            .map(|animation| animation.get_duration())
+            .sum()
+    }
+
+    fn value_at(&self, elapsed: Duration) -> T {
+        let mut start_time = Duration::from_secs(0);
+        for animation in &self.animations {
+            let end_time = start_time + animation.get_duration();
+            if elapsed < end_time {
+                return animation.value_at(elapsed - start_time);
+            }
+            start_time = end_time;
+        }
+
+        let last = self.animations.last().unwrap();
+        last.value_at(last.get_duration())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sequence() {
+        let mut sequence = Sequence::new(Box::new(Tween::new(
+            0.0,
+            1.0,
+            Duration::from_millis(100),
+            Easing::Linear,
+        )));
+        sequence.add_pause(Duration::from_millis(50));
+        sequence.add(Box::new(Tween::new(
+            1.0,
+            3.0,
+            Duration::from_millis(200),
+            Easing::Linear,
+        )));
+
+        assert_eq!(Duration::from_millis(350), sequence.get_duration());
+        assert_eq!(0.0, sequence.value_at(Duration::from_millis(0)));
+        assert_eq!(0.5, sequence.value_at(Duration::from_millis(50)));
+        assert_eq!(1.0, sequence.value_at(Duration::from_millis(120)));
+        assert_eq!(1.0, sequence.value_at(Duration::from_millis(150)));
+        assert_eq!(2.0, sequence.value_at(Duration::from_millis(250)));
+        assert_eq!(3.0, sequence.value_at(Duration::from_millis(350)));
+        assert_eq!(3.0, sequence.value_at(Duration::from_millis(1000)));
+    }
+}
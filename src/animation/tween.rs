@@ -0,0 +1,57 @@
+use super::*;
+
+use std::time::Duration;
+
+/// The simplest `Animation`: it moves from one value to another value during a given duration,
+/// using an easing curve.
+#[derive(Copy, Clone, Debug)]
+pub struct Tween<T: Interpolate> {
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Tween<T> {
+    pub fn new(from: T, to: T, duration: Duration, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing,
+        }
+    }
+}
+
+impl<T: Interpolate> Animation<T> for Tween<T> {
+    fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn value_at(&self, elapsed: Duration) -> T {
+        let progress = self.easing.apply(progress_of(elapsed, self.duration));
+        self.from.interpolate(self.to, progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_tween() {
+        let tween = Tween::new(10.0, 20.0, Duration::from_millis(200), Easing::QuadIn);
+        assert_eq!(Duration::from_millis(200), tween.get_duration());
+        assert_eq!(10.0, tween.value_at(Duration::from_millis(0)));
+        assert_eq!(12.5, tween.value_at(Duration::from_millis(100)));
+        assert_eq!(20.0, tween.value_at(Duration::from_millis(200)));
+        assert_eq!(20.0, tween.value_at(Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn test_zero_duration() {
+        let tween = Tween::new(10.0, 20.0, Duration::from_millis(0), Easing::Linear);
+        assert_eq!(20.0, tween.value_at(Duration::from_millis(0)));
+    }
+}
@@ -1,7 +1,18 @@
 use crate::*;
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Duration;
+
+/// Answers the `request_text_input` calls of components on behalf of the user. The *wrapper* can
+/// give the `Application` a provider that uses a platform-specific prompt, and unit tests can use
+/// a provider that gives scripted answers.
+pub trait TextInputProvider {
+    /// Asks the user to modify the `start_text`, and returns the result, or `None` if the user
+    /// cancelled.
+    fn provide_text_input(&mut self, start_text: String) -> Option<String>;
+}
 
 /// The `Application` is the 'highest' object that is cross-platform. It
 /// encapsulates all the components and their buddies.
@@ -32,14 +43,37 @@ pub struct Application {
     root_buddy: RootComponentBuddy,
 
     mouse_store: Rc<RefCell<MouseStore>>,
+    keyboard_store: Rc<RefCell<KeyboardStore>>,
+    gesture_recognizer: GestureRecognizer,
+    drag_and_drop: Option<DragAndDropState>,
+    captured_mouses: Vec<Mouse>,
+    last_resize: Option<ResizeEvent>,
+
+    text_input_provider: Option<Box<dyn TextInputProvider>>,
+    text_input_request: Option<String>,
+    awaiting_text_input: bool,
+
+    // The (detached) menus that can be restored with go_back and go_forward
+    back_stack: Vec<Box<dyn Component>>,
+    forward_stack: Vec<Box<dyn Component>>,
+    // True if the root component changed since the last render
+    force_next_render: bool,
+
+    current_time: Duration,
+    // The timers of the root component, as pairs (expiration time, id)
+    timers: Vec<(Duration, u32)>,
+    // The ids of the timers that expired during the current tick, but haven't been fired yet
+    expired_timers: Vec<u32>,
 }
 
 impl Application {
     pub fn new(mut initial_root_component: Box<dyn Component>) -> Self {
         let mouse_store = Rc::new(RefCell::new(MouseStore::new()));
+        let keyboard_store = Rc::new(RefCell::new(KeyboardStore::new()));
 
         let mut root_buddy = RootComponentBuddy::new();
         root_buddy.set_mouse_store(Rc::clone(&mouse_store));
+        root_buddy.set_keyboard_store(Rc::clone(&keyboard_store));
 
         initial_root_component.on_attach(&mut root_buddy);
         // No need to call request_render, because the did_request_render field
@@ -49,28 +83,436 @@ impl Application {
             root_buddy,
 
             mouse_store,
+            keyboard_store,
+            gesture_recognizer: GestureRecognizer::new(),
+            drag_and_drop: None,
+            captured_mouses: Vec::new(),
+            last_resize: None,
+
+            text_input_provider: None,
+            text_input_request: None,
+            awaiting_text_input: false,
+
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            force_next_render: false,
+
+            current_time: Duration::from_secs(0),
+            timers: Vec::new(),
+            expired_timers: Vec::new(),
         };
         result.work_after_events();
         result
     }
 
     fn work_after_events(&mut self) {
-        if self.root_buddy.has_next_menu() {
-            self.root_component.on_detach();
+        if let Some(wants_focus) = self.root_buddy.take_focus_request() {
+            // The root component is the only component that can ask for focus at this level, so
+            // the request can simply be granted
+            if wants_focus != self.root_buddy.has_focus() {
+                self.root_buddy.set_focus(wants_focus);
+                if wants_focus {
+                    self.root_component.on_focus_gained(&mut self.root_buddy);
+                } else {
+                    self.root_component.on_focus_lost(&mut self.root_buddy);
+                }
+
+                // The focus event handler might have requested more work
+                self.work_after_events();
+            }
+        }
+
+        if let Some(start_text) = self.root_buddy.take_text_input_request() {
+            self.text_input_request = Some(start_text);
+            self.awaiting_text_input = true;
+            self.answer_text_input_request();
+        }
+
+        if let Some((mouse, payload)) = self.root_buddy.take_drag_and_drop_request() {
+            self.start_drag_and_drop(mouse, payload);
+        }
+
+        for (delay, id) in self.root_buddy.take_timer_requests() {
+            self.timers.push((self.current_time + delay, id));
+        }
+
+        for (mouse, capture) in self.root_buddy.take_mouse_capture_requests() {
+            if capture {
+                self.capture_mouse(mouse);
+            } else {
+                self.release_mouse_capture(mouse);
+            }
+        }
+
+        if let Some(create_next_menu) = self.root_buddy.take_next_menu() {
+            let current_menu = self.detach_root_component();
+            self.attach_root_component(create_next_menu(current_menu));
+        } else if let Some(request) = self.root_buddy.take_navigation_request() {
+            match request {
+                NavigationRequest::Push(new_menu) => {
+                    let current_menu = self.detach_root_component();
+                    self.back_stack.push(current_menu);
+                    self.forward_stack.clear();
+                    self.attach_root_component(new_menu);
+                }
+                NavigationRequest::Pop => {
+                    self.go_back();
+                }
+                NavigationRequest::Replace(new_menu) => {
+                    // The current root component can't be restored, so it can simply be dropped
+                    self.detach_root_component();
+                    self.attach_root_component(new_menu);
+                }
+            };
+        }
+    }
+
+    /// Detaches the current root component and takes it out of the application. The root
+    /// component will be replaced by a `DummyComponent`, so `attach_root_component` must be called
+    /// right after this method.
+    fn detach_root_component(&mut self) -> Box<dyn Component> {
+        if self.root_buddy.has_focus() {
+            self.root_buddy.set_focus(false);
+            self.root_component.on_focus_lost(&mut self.root_buddy);
+        }
+
+        // The next root component shouldn't receive the text input of the current one
+        self.text_input_request = None;
+        self.awaiting_text_input = false;
+
+        // The next root component shouldn't be involved in the drag-and-drop operation of
+        // the current one
+        self.drag_and_drop = None;
+        self.captured_mouses.clear();
+
+        // The timers belong to the current root component
+        self.timers.clear();
+        self.expired_timers.clear();
+
+        // Work around because self.root_component must have some value at all times
+        let mut component =
+            std::mem::replace(&mut self.root_component, Box::new(DummyComponent {}));
+        component.on_detach();
+        component
+    }
+
+    /// Attaches `new_root` as the new root component, with a fresh buddy. This should only be
+    /// called right after `detach_root_component`.
+    fn attach_root_component(&mut self, new_root: Box<dyn Component>) {
+        self.root_component = new_root;
+        self.root_buddy = RootComponentBuddy::new();
+        self.root_buddy
+            .set_mouse_store(Rc::clone(&self.mouse_store));
+        self.root_buddy
+            .set_keyboard_store(Rc::clone(&self.keyboard_store));
+        self.root_buddy.set_current_time(self.current_time);
+        self.root_component.on_attach(&mut self.root_buddy);
+
+        // The new root component needs to know its size as well
+        if let Some(resize_event) = self.last_resize {
+            self.root_component
+                .on_resize(resize_event, &mut self.root_buddy);
+        }
+
+        // The new root component needs to draw over everything of the previous one
+        self.force_next_render = true;
+        self.root_buddy.request_render();
+        self.work_after_events();
+    }
+
+    /// Restores the root menu that was replaced by the last `push_menu` request of the root
+    /// component (the current root menu can be restored with `go_forward`). This method is
+    /// normally called by the *wrapper* (for instance when the user presses the *back* button of
+    /// the browser) and by the `pop_menu` method of `ComponentBuddy`.
+    ///
+    /// The current root menu will be detached, and the restored menu will be attached again.
+    ///
+    /// Returns false (and does nothing) if there is no menu to go back to.
+    pub fn go_back(&mut self) -> bool {
+        if let Some(previous_menu) = self.back_stack.pop() {
+            let current_menu = self.detach_root_component();
+            self.forward_stack.push(current_menu);
+            self.attach_root_component(previous_menu);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Restores the root menu that was removed by the last `go_back` (or `pop_menu` request),
+    /// unless another menu has been pushed since then.
+    ///
+    /// Returns false (and does nothing) if there is no menu to go forward to.
+    pub fn go_forward(&mut self) -> bool {
+        if let Some(next_menu) = self.forward_stack.pop() {
+            let current_menu = self.detach_root_component();
+            self.back_stack.push(current_menu);
+            self.attach_root_component(next_menu);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gets the number of menus that can be restored with `go_back`. The *wrapper* can use this
+    /// to keep the navigation stack in sync with a platform history (like that of the browser).
+    pub fn get_navigation_depth(&self) -> usize {
+        self.back_stack.len()
+    }
+
+    /// Sets the `TextInputProvider` that will be used to answer the `request_text_input` calls of
+    /// the components. If there is a pending text input request, it will be answered immediately.
+    ///
+    /// If no provider is set, the *wrapper* should use the `take_text_input_request` and
+    /// `fire_text_input_event` methods instead.
+    pub fn set_text_input_provider(&mut self, provider: Box<dyn TextInputProvider>) {
+        self.text_input_provider = Some(provider);
+        self.answer_text_input_request();
+    }
+
+    fn answer_text_input_request(&mut self) {
+        if let Some(provider) = &mut self.text_input_provider {
+            if let Some(start_text) = self.text_input_request.take() {
+                let text = provider.provide_text_input(start_text);
+                self.fire_text_input_event(text);
+            }
+        }
+    }
+
+    /// Takes the start text of the pending text input request of the components, if there is
+    /// one. This will return `None` if there is no such request, or if it was already taken.
+    ///
+    /// ### Wrapper
+    /// If the *wrapper* didn't give the application a `TextInputProvider`, it should call this
+    /// method regularly. When this returns `Some`, the *wrapper* should ask the user to modify the
+    /// start text, and call `fire_text_input_event` once the user is finished.
+    pub fn take_text_input_request(&mut self) -> Option<String> {
+        self.text_input_request.take()
+    }
+
+    /// Checks whether a component is waiting for the answer to its text input request. While this
+    /// is the case, the application will ignore all mouse and keyboard input (apart from keeping
+    /// track of the pressed keys and mouse buttons).
+    pub fn is_awaiting_text_input(&self) -> bool {
+        self.awaiting_text_input
+    }
 
-            // Work around because self.root_component must have some value at all times
-            let mut replacement_helper: Box<dyn Component> = Box::new(DummyComponent {});
-            std::mem::swap(&mut replacement_helper, &mut self.root_component);
-            self.root_component = self.root_buddy.create_next_menu(replacement_helper);
+    /// Gives the answer of the user to the latest text input request, which should be `None` if
+    /// the user cancelled. This method has no effect if no component is waiting for text input.
+    pub fn fire_text_input_event(&mut self, text: Option<String>) {
+        if self.awaiting_text_input {
+            self.awaiting_text_input = false;
+            self.root_component
+                .on_text_input(&TextInputEvent::new(text), &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    fn start_drag_and_drop(&mut self, mouse: Mouse, payload: Rc<dyn Any>) {
+        // Only 1 drag-and-drop operation can be active at a time
+        self.finish_drag_and_drop(None);
+
+        // It's impossible to drag with a mouse that is not in the application
+        let maybe_position = self
+            .mouse_store
+            .borrow()
+            .get_mouse_state(mouse)
+            .map(|state| state.position);
+        if let Some(position) = maybe_position {
+            self.drag_and_drop = Some(DragAndDropState {
+                mouse,
+                payload,
+                point: position,
+                root_entered: false,
+            });
+            self.update_drag_and_drop(position);
+        }
+    }
+
+    fn is_dragging_and_dropping(&self, mouse: Mouse) -> bool {
+        match &self.drag_and_drop {
+            Some(state) => state.mouse == mouse,
+            None => false,
+        }
+    }
+
+    fn accepts_drag_and_drop(&self, point: Point) -> bool {
+        if !self.root_buddy.get_subscriptions().drag_and_drop {
+            return false;
+        }
+        match self.root_buddy.get_last_render_result() {
+            Some(render_result) => {
+                !render_result.filter_mouse_actions || render_result.drawn_region.is_inside(point)
+            }
+            None => false,
+        }
+    }
+
+    fn update_drag_and_drop(&mut self, point: Point) {
+        let accepts = self.accepts_drag_and_drop(point);
+        if let Some(state) = &mut self.drag_and_drop {
+            let mouse = state.mouse;
+            let payload = Rc::clone(&state.payload);
+            let was_entered = state.root_entered;
+            state.point = point;
+            state.root_entered = accepts;
+
+            if accepts {
+                if !was_entered {
+                    let enter_event = DragEnterEvent::new(mouse, point, Rc::clone(&payload));
+                    self.root_component
+                        .on_drag_enter(&enter_event, &mut self.root_buddy);
+                }
+                let over_event = DragOverEvent::new(mouse, point, payload);
+                self.root_component
+                    .on_drag_over(&over_event, &mut self.root_buddy);
+            } else if was_entered {
+                let leave_event = DragLeaveEvent::new(mouse, point, payload);
+                self.root_component
+                    .on_drag_leave(&leave_event, &mut self.root_buddy);
+            }
+            self.work_after_events();
+        }
+    }
+
+    /// Ends the current drag-and-drop operation (if any) by dropping its payload at `drop_point`,
+    /// or cancels it if `drop_point` is `None`
+    fn finish_drag_and_drop(&mut self, drop_point: Option<Point>) {
+        if let Some(state) = self.drag_and_drop.take() {
+            let accepts_drop = match drop_point {
+                Some(point) => self.accepts_drag_and_drop(point),
+                None => false,
+            };
+
+            if accepts_drop {
+                let drop_event = DropEvent::new(state.mouse, drop_point.unwrap(), state.payload);
+                self.root_component
+                    .on_drop(&drop_event, &mut self.root_buddy);
+            } else if state.root_entered {
+                let leave_event = DragLeaveEvent::new(state.mouse, state.point, state.payload);
+                self.root_component
+                    .on_drag_leave(&leave_event, &mut self.root_buddy);
+            }
+            self.work_after_events();
+        }
+    }
+
+    fn capture_mouse(&mut self, mouse: Mouse) {
+        // It's impossible to capture a mouse that is not in the application
+        let has_mouse = self.mouse_store.borrow().get_mouse_state(mouse).is_some();
+        if has_mouse && !self.is_mouse_captured(mouse) {
+            self.captured_mouses.push(mouse);
+        }
+    }
+
+    fn release_mouse_capture(&mut self, mouse: Mouse) {
+        if self.is_mouse_captured(mouse) {
+            self.captured_mouses
+                .retain(|captured_mouse| *captured_mouse != mouse);
+
+            // The root component didn't receive a MouseLeaveEvent while the mouse was captured
+            let maybe_position = self
+                .mouse_store
+                .borrow()
+                .get_mouse_state(mouse)
+                .map(|state| state.position);
+            if let Some(position) = maybe_position {
+                if self.root_buddy.get_subscriptions().mouse_leave {
+                    if let Some(render_result) = self.root_buddy.get_last_render_result() {
+                        if render_result.filter_mouse_actions
+                            && !render_result.drawn_region.is_inside(position)
+                        {
+                            self.root_component.on_mouse_leave(
+                                MouseLeaveEvent::new(mouse, position),
+                                &mut self.root_buddy,
+                            );
+                            self.work_after_events();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_mouse_captured(&self, mouse: Mouse) -> bool {
+        self.captured_mouses.contains(&mouse)
+    }
+
+    /// Informs the `Application` about the current time. The `now` can be measured from any
+    /// starting point, as long as the *wrapper* always uses the same starting point.
+    ///
+    /// The `now` will be returned by the `get_current_time` method of the buddies until the next
+    /// call to this method, and the timers (see `schedule_timer`) that expired before `now` will
+    /// be fired.
+    ///
+    /// ### Wrapper
+    /// The *wrapper* should call this method frequently (typically right before every call to
+    /// `render`). The time is needed to recognize double-clicks and long presses, and to fire
+    /// the timers. Unit tests can call this method to control the time deterministically.
+    pub fn tick(&mut self, now: Duration) {
+        self.current_time = now;
+        self.root_buddy.set_current_time(now);
+
+        let gestures = self.gesture_recognizer.tick(now);
+        self.fire_gesture_events(gestures);
+        self.fire_timers();
+    }
+
+    fn fire_timers(&mut self) {
+        // Timers that are scheduled while handling these timers will have to wait until the next
+        // tick, even if they expire immediately. This prevents endless loops.
+        let now = self.current_time;
+        let mut expired_timers: Vec<_> = self
+            .timers
+            .iter()
+            .filter(|(expiration_time, _)| *expiration_time <= now)
+            .cloned()
+            .collect();
+        self.timers
+            .retain(|(expiration_time, _)| *expiration_time > now);
+
+        // The timers that expired first should be fired first
+        expired_timers.sort_by_key(|(expiration_time, _)| *expiration_time);
+        self.expired_timers = expired_timers.into_iter().map(|(_, id)| id).collect();
+
+        // If a timer causes the root component to change, the remaining timers will be cancelled
+        while !self.expired_timers.is_empty() {
+            let id = self.expired_timers.remove(0);
+            self.root_component
+                .on_timer(TimerEvent::new(id), &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
 
-            // A fresh main component requires a fresh buddy
-            self.root_buddy = RootComponentBuddy::new();
-            self.root_buddy
-                .set_mouse_store(Rc::clone(&self.mouse_store));
+    fn fire_gesture_events(&mut self, gestures: Vec<GestureEvent>) {
+        for gesture in gestures {
+            if gesture.is_subscribed(self.root_buddy.get_subscriptions()) {
+                if let Some(render_result) = self.root_buddy.get_last_render_result() {
+                    if !render_result.filter_mouse_actions
+                        || render_result
+                            .drawn_region
+                            .is_inside(gesture.get_routing_point())
+                    {
+                        gesture.fire(self.root_component.as_mut(), &mut self.root_buddy);
+                        self.work_after_events();
+                    }
+                }
+            }
+        }
+    }
 
-            self.root_component.on_attach(&mut self.root_buddy);
+    /// Informs the `Application` about the size (in pixels) of the window (or canvas or screen)
+    /// it is drawn on. The root component will receive the event if the size is different from the
+    /// previous size (or if this is the first time this method is called).
+    ///
+    /// ### Wrapper
+    /// The *wrapper* should call this method before the application is rendered for the first
+    /// time, and whenever the window is resized.
+    pub fn fire_resize_event(&mut self, event: ResizeEvent) {
+        if self.last_resize != Some(event) {
+            self.last_resize = Some(event);
+            self.root_component.on_resize(event, &mut self.root_buddy);
             self.work_after_events();
-            self.root_buddy.request_render();
         }
     }
 
@@ -100,11 +542,25 @@ impl Application {
     /// nothing changed, the window will keep showing the results of the previous
     /// time the application *did* render.
     ///
+    /// ### Animation frames
+    /// If the root component requested an animation frame, its `on_animation_frame` method will be
+    /// called before it is rendered. The timestamp of the frame is the `now` of the latest `tick`.
+    ///
     /// ### Return value
     /// This method returns true if the application chose to render (or it was
     /// forced to do so) and false if the application chose not to render.
     pub fn render(&mut self, renderer: &Renderer, force: bool) -> bool {
+        // Let the animations compute their state before they are rendered
+        if self.root_buddy.take_animation_frame_request() {
+            let event = AnimationFrameEvent::new(self.current_time);
+            self.root_component
+                .on_animation_frame(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+
+        let force = force || self.force_next_render;
         if force || self.root_buddy.did_request_render() {
+            self.force_next_render = false;
             self.root_buddy.clear_render_request();
 
             // Make sure we draw onto the right area
@@ -131,6 +587,11 @@ impl Application {
     }
 
     pub fn fire_mouse_click_event(&mut self, event: MouseClickEvent) {
+        if self.is_awaiting_text_input() {
+            return;
+        }
+        let gestures = self.gesture_recognizer.mouse_click(event);
+
         let sub_mouse_click = self.root_buddy.get_subscriptions().mouse_click;
         let sub_mouse_click_out = self.root_buddy.get_subscriptions().mouse_click_out;
 
@@ -153,21 +614,25 @@ impl Application {
                 fire_out = !fire;
             }
 
-            if fire {
+            if fire && sub_mouse_click {
                 self.root_component
                     .on_mouse_click(event, &mut self.root_buddy);
                 self.work_after_events();
             }
-            if fire_out {
+            if fire_out && sub_mouse_click_out {
                 let out_event = MouseClickOutEvent::new(event.get_mouse(), event.get_button());
                 self.root_component
                     .on_mouse_click_out(out_event, &mut self.root_buddy);
                 self.work_after_events();
             }
         }
+
+        self.fire_gesture_events(gestures);
     }
 
     pub fn fire_mouse_press_event(&mut self, event: MousePressEvent) {
+        let gestures = self.gesture_recognizer.mouse_press(event);
+
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
             Some(state) => state.buttons.press(event.get_button()),
@@ -175,6 +640,10 @@ impl Application {
         };
         drop(mouse_store);
 
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
         if self.root_buddy.get_subscriptions().mouse_press {
             if let Some(render_result) = self.root_buddy.get_last_render_result() {
                 if !render_result.filter_mouse_actions
@@ -186,9 +655,13 @@ impl Application {
                 }
             }
         }
+
+        self.fire_gesture_events(gestures);
     }
 
     pub fn fire_mouse_release_event(&mut self, event: MouseReleaseEvent) {
+        let gestures = self.gesture_recognizer.mouse_release(event);
+
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
             Some(state) => state.buttons.release(event.get_button()),
@@ -196,9 +669,16 @@ impl Application {
         };
         drop(mouse_store);
 
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
+        // A captured mouse should be released even if it is outside the drawn region
+        let is_captured = self.is_mouse_captured(event.get_mouse());
         if self.root_buddy.get_subscriptions().mouse_release {
             if let Some(render_result) = self.root_buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
+                if is_captured
+                    || !render_result.filter_mouse_actions
                     || render_result.drawn_region.is_inside(event.get_point())
                 {
                     self.root_component
@@ -207,6 +687,12 @@ impl Application {
                 }
             }
         }
+
+        if self.is_dragging_and_dropping(event.get_mouse()) {
+            self.finish_drag_and_drop(Some(event.get_point()));
+        }
+
+        self.fire_gesture_events(gestures);
     }
 
     fn sub_mouse_enter(&self) -> bool {
@@ -222,6 +708,8 @@ impl Application {
     }
 
     pub fn fire_mouse_move_event(&mut self, event: MouseMoveEvent) {
+        let gestures = self.gesture_recognizer.mouse_move(event);
+
         // Keep the MouseStore up-to-date
         let mut mouse_store = self.mouse_store.borrow_mut();
         match mouse_store.update_mouse_state(event.get_mouse()) {
@@ -242,8 +730,20 @@ impl Application {
         };
         drop(mouse_store);
 
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
         // Fire the necessary events
-        if let Some(render_result) = self.root_buddy.get_last_render_result() {
+        if self.is_mouse_captured(event.get_mouse()) {
+            // The root component should receive all movements of a captured mouse, and no enter
+            // or leave events
+            if self.sub_mouse_move() {
+                self.root_component
+                    .on_mouse_move(event, &mut self.root_buddy);
+                self.work_after_events();
+            }
+        } else if let Some(render_result) = self.root_buddy.get_last_render_result() {
             // Don't bother doing computations if the root component isn't interested in either event
             if self.sub_mouse_enter() || self.sub_mouse_move() || self.sub_mouse_leave() {
                 let filter_mouse = render_result.filter_mouse_actions;
@@ -324,6 +824,12 @@ impl Application {
                 self.work_after_events();
             }
         }
+
+        if self.is_dragging_and_dropping(event.get_mouse()) {
+            self.update_drag_and_drop(event.get_to());
+        }
+
+        self.fire_gesture_events(gestures);
     }
 
     pub fn fire_mouse_enter_event(&mut self, event: MouseEnterEvent) {
@@ -338,6 +844,10 @@ impl Application {
         );
         drop(mouse_store);
 
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
         // Propagate the MouseEnterEvent
         if let Some(render_result) = self.root_buddy.get_last_render_result() {
             if self.root_buddy.get_subscriptions().mouse_enter {
@@ -357,16 +867,29 @@ impl Application {
     }
 
     pub fn fire_mouse_leave_event(&mut self, event: MouseLeaveEvent) {
+        let gestures = self.gesture_recognizer.mouse_leave(event);
+
         // Keep the MouseStore up-to-date
         let mut mouse_store = self.mouse_store.borrow_mut();
         mouse_store.remove_mouse(event.get_mouse());
         drop(mouse_store);
 
+        // The capture of a mouse ends when it leaves the application
+        let was_captured = self.is_mouse_captured(event.get_mouse());
+        self.captured_mouses
+            .retain(|captured_mouse| *captured_mouse != event.get_mouse());
+
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
         // Propagate the MouseLeaveEvent
         if let Some(render_result) = self.root_buddy.get_last_render_result() {
             if self.root_buddy.get_subscriptions().mouse_leave {
                 let should_propagate = match render_result.filter_mouse_actions {
-                    true => render_result.drawn_region.is_inside(event.get_exit_point()),
+                    true => {
+                        was_captured || render_result.drawn_region.is_inside(event.get_exit_point())
+                    }
                     false => true,
                 };
                 if should_propagate {
@@ -376,9 +899,82 @@ impl Application {
                 }
             }
         }
+
+        if self.is_dragging_and_dropping(event.get_mouse()) {
+            self.finish_drag_and_drop(None);
+        }
+
+        self.fire_gesture_events(gestures);
+    }
+
+    pub fn fire_mouse_scroll_event(&mut self, event: MouseScrollEvent) {
+        if self.is_awaiting_text_input() {
+            return;
+        }
+        if self.root_buddy.get_subscriptions().mouse_scroll {
+            if let Some(render_result) = self.root_buddy.get_last_render_result() {
+                if !render_result.filter_mouse_actions
+                    || render_result.drawn_region.is_inside(event.get_point())
+                {
+                    self.root_component
+                        .on_mouse_scroll(event, &mut self.root_buddy);
+                    self.work_after_events();
+                }
+            }
+        }
+    }
+
+    pub fn fire_key_press_event(&mut self, event: KeyPressEvent) {
+        // Keep the KeyboardStore up-to-date
+        self.keyboard_store.borrow_mut().press(event.get_key());
+
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
+        // Unlike mouse events, key events don't have a position, so they don't need to be filtered
+        if self.root_buddy.get_subscriptions().key_press {
+            self.root_component
+                .on_key_press(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_key_release_event(&mut self, event: KeyReleaseEvent) {
+        // Keep the KeyboardStore up-to-date
+        self.keyboard_store.borrow_mut().release(event.get_key());
+
+        if self.is_awaiting_text_input() {
+            return;
+        }
+
+        if self.root_buddy.get_subscriptions().key_release {
+            self.root_component
+                .on_key_release(event, &mut self.root_buddy);
+            self.work_after_events();
+        }
+    }
+
+    pub fn fire_char_type_event(&mut self, event: CharTypeEvent) {
+        if self.is_awaiting_text_input() {
+            return;
+        }
+        if self.root_buddy.get_subscriptions().char_type {
+            self.root_component
+                .on_char_type(&event, &mut self.root_buddy);
+            self.work_after_events();
+        }
     }
 }
 
+struct DragAndDropState {
+    mouse: Mouse,
+    payload: Rc<dyn Any>,
+    point: Point,
+    // Whether the root component received a DragEnterEvent, but no DragLeaveEvent yet
+    root_entered: bool,
+}
+
 impl Drop for Application {
     fn drop(&mut self) {
         self.root_component.on_detach();
@@ -392,6 +988,7 @@ mod tests {
 
     use std::cell::{Cell, RefCell};
     use std::rc::Rc;
+    use std::time::Duration;
 
     struct CountingComponent {
         counter: Rc<Cell<u32>>,
@@ -634,6 +1231,71 @@ mod tests {
         check_counters(1, 1, 1, 1);
     }
 
+    #[test]
+    fn test_click_subscriptions() {
+        struct HalfSubscribedComponent {
+            subscribe_click_out: bool,
+            counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for HalfSubscribedComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                if self.subscribe_click_out {
+                    buddy.subscribe_mouse_click_out();
+                } else {
+                    buddy.subscribe_mouse_click();
+                }
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.4, 0.4, 0.6, 0.6)),
+                    filter_mouse_actions: true,
+                })
+            }
+
+            // The default implementations of the other click handler would panic
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+                assert!(!self.subscribe_click_out);
+                self.counter.set(self.counter.get() + 1);
+            }
+
+            fn on_mouse_click_out(
+                &mut self,
+                _event: MouseClickOutEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                assert!(self.subscribe_click_out);
+                self.counter.set(self.counter.get() + 1);
+            }
+        }
+
+        let miss_click =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.3, 0.3), MouseButton::primary());
+        let hit_click =
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.5, 0.5), MouseButton::primary());
+
+        for subscribe_click_out in &[false, true] {
+            let counter = Rc::new(Cell::new(0));
+            let mut application = Application::new(Box::new(HalfSubscribedComponent {
+                subscribe_click_out: *subscribe_click_out,
+                counter: Rc::clone(&counter),
+            }));
+            application.render(&test_renderer(RenderRegion::between(0, 0, 1, 1)), false);
+
+            // Only the event that the component subscribed to should be fired
+            application.fire_mouse_click_event(miss_click);
+            application.fire_mouse_click_event(hit_click);
+            assert_eq!(1, counter.get());
+        }
+    }
+
     struct ConditionalMouseFilterComponent {
         should_filter_mouse_actions: Rc<Cell<bool>>,
         mouse_enter_log: Rc<RefCell<Vec<MouseEnterEvent>>>,
@@ -1388,15 +2050,14 @@ mod tests {
     }
 
     #[test]
-    fn test_change_menu() {
-        struct ChangingComponent {
-            click_counter: Rc<Cell<u8>>,
-            changed_counter: Rc<Cell<u8>>,
+    fn test_mouse_scroll() {
+        struct ScrollComponent {
+            scroll_log: Rc<RefCell<Vec<MouseScrollEvent>>>,
         }
 
-        impl Component for ChangingComponent {
+        impl Component for ScrollComponent {
             fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
-                buddy.subscribe_mouse_click();
+                buddy.subscribe_mouse_scroll();
             }
 
             fn render(
@@ -1405,12 +2066,694 @@ mod tests {
                 _buddy: &mut dyn ComponentBuddy,
                 _force: bool,
             ) -> RenderResult {
-                entire_render_result()
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.4, 0.4, 0.6, 0.6)),
+                    filter_mouse_actions: true,
+                })
             }
 
-            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
-                self.click_counter.set(self.click_counter.get() + 1);
-                let changed_counter = Rc::clone(&self.changed_counter);
+            fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+                self.scroll_log.borrow_mut().push(event);
+
+                // Stop listening after scrolling down
+                if event.get_delta_y() < 0.0 {
+                    buddy.unsubscribe_mouse_scroll();
+                }
+            }
+        }
+
+        let scroll_log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(ScrollComponent {
+            scroll_log: Rc::clone(&scroll_log),
+        }));
+
+        let mouse = Mouse::new(0);
+        let hit_up = MouseScrollEvent::new(mouse, Point::new(0.5, 0.5), 0.0, 2.0);
+        let hit_down = MouseScrollEvent::new(mouse, Point::new(0.5, 0.5), 0.5, -1.0);
+        let miss = MouseScrollEvent::new(mouse, Point::new(0.3, 0.5), 0.0, 1.0);
+
+        // Scroll events don't have effect until the component has been drawn
+        application.fire_mouse_scroll_event(hit_up);
+        assert!(scroll_log.borrow().is_empty());
+
+        application.render(&test_renderer(RenderRegion::between(0, 0, 1, 1)), false);
+
+        // Scrolling outside the drawn region should be filtered
+        application.fire_mouse_scroll_event(miss);
+        assert!(scroll_log.borrow().is_empty());
+
+        application.fire_mouse_scroll_event(hit_up);
+        application.fire_mouse_scroll_event(hit_down);
+        assert_eq!(vec![hit_up, hit_down], *scroll_log.borrow());
+
+        // The component should have unsubscribed after scrolling down
+        application.fire_mouse_scroll_event(hit_up);
+        assert_eq!(2, scroll_log.borrow().len());
+    }
+
+    #[test]
+    fn test_mouse_capture() {
+        struct CaptureComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for CaptureComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_enter();
+                buddy.subscribe_mouse_leave();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.4, 0.4, 0.6, 0.6)),
+                    filter_mouse_actions: true,
+                })
+            }
+
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("press".to_string());
+                buddy.capture_mouse(event.get_mouse());
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                let point = event.get_point();
+                self.log
+                    .borrow_mut()
+                    .push(format!("release {} {}", point.get_x(), point.get_y()));
+                buddy.release_mouse_capture(event.get_mouse());
+            }
+
+            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                let point = event.get_to();
+                self.log
+                    .borrow_mut()
+                    .push(format!("move {} {}", point.get_x(), point.get_y()));
+            }
+
+            fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("enter".to_string());
+            }
+
+            fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("leave".to_string());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(CaptureComponent {
+            log: Rc::clone(&log),
+        }));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 1, 1)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let inside = Point::new(0.5, 0.5);
+        let outside = Point::new(0.9, 0.9);
+        let take_log = || log.replace(Vec::new());
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, inside));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, inside, button));
+        assert_eq!(vec!["enter", "press"], take_log());
+
+        // The component should keep receiving the movements after the mouse leaves its region
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, outside));
+        assert_eq!(vec!["move 0.9 0.9"], take_log());
+
+        // The release should also be received, and releasing the capture should cause a leave
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, outside, button));
+        assert_eq!(vec!["release 0.9 0.9", "leave"], take_log());
+
+        // Now that the capture is released, movements outside the region should be filtered
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            outside,
+            Point::new(0.8, 0.9),
+        ));
+        assert!(take_log().is_empty());
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, Point::new(0.8, 0.9), inside));
+        assert_eq!(vec!["enter", "move 0.5 0.5"], take_log());
+
+        // The capture should end when the mouse leaves the application
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, inside, button));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, outside));
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, outside));
+        assert_eq!(vec!["press", "move 0.9 0.9", "leave"], take_log());
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, inside));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, outside));
+        assert_eq!(vec!["enter", "move 0.6 0.6", "leave"], take_log());
+    }
+
+    struct GestureComponent {
+        log: Rc<RefCell<Vec<GestureEvent>>>,
+    }
+
+    impl Component for GestureComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_double_click();
+            buddy.subscribe_long_press();
+            buddy.subscribe_drag();
+            buddy.subscribe_pinch();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            Ok(RenderResultStruct {
+                drawn_region: Box::new(RectangularDrawnRegion::new(0.4, 0.4, 0.6, 0.6)),
+                filter_mouse_actions: true,
+            })
+        }
+
+        fn on_double_click(&mut self, event: DoubleClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::DoubleClick(event));
+        }
+
+        fn on_long_press(&mut self, event: LongPressEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::LongPress(event));
+        }
+
+        fn on_drag_start(&mut self, event: DragStartEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::DragStart(event));
+        }
+
+        fn on_drag_update(&mut self, event: DragUpdateEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::DragUpdate(event));
+        }
+
+        fn on_drag_end(&mut self, event: DragEndEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::DragEnd(event));
+        }
+
+        fn on_pinch(&mut self, event: PinchEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(GestureEvent::Pinch(event));
+        }
+    }
+
+    #[test]
+    fn test_gestures() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(GestureComponent {
+            log: Rc::clone(&log),
+        }));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 1, 1)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let take_log = || log.replace(Vec::new());
+        let click = |application: &mut Application, x: f32, y: f32| {
+            let point = Point::new(x, y);
+            application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+            application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+            application.fire_mouse_click_event(MouseClickEvent::new(mouse, point, button));
+        };
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.5, 0.5)));
+        application.tick(Duration::from_millis(1000));
+
+        // Double clicks
+        click(&mut application, 0.5, 0.5);
+        application.tick(Duration::from_millis(1100));
+        click(&mut application, 0.5, 0.5);
+        assert_eq!(
+            vec![GestureEvent::DoubleClick(DoubleClickEvent::new(
+                mouse,
+                Point::new(0.5, 0.5),
+                button
+            ))],
+            take_log()
+        );
+
+        // Double clicks outside the drawn region should be filtered
+        click(&mut application, 0.2, 0.2);
+        click(&mut application, 0.2, 0.2);
+        assert!(take_log().is_empty());
+
+        // Long presses
+        let point = Point::new(0.5, 0.5);
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+        application.tick(Duration::from_millis(2000));
+        assert_eq!(
+            vec![GestureEvent::LongPress(LongPressEvent::new(
+                mouse, point, button
+            ))],
+            take_log()
+        );
+
+        // Drags should keep being fired after leaving the drawn region
+        let far_point = Point::new(0.9, 0.5);
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, point, far_point));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, far_point, button));
+        assert_eq!(
+            vec![
+                GestureEvent::DragStart(DragStartEvent::new(mouse, button, point)),
+                GestureEvent::DragUpdate(DragUpdateEvent::new(
+                    mouse, button, point, point, far_point
+                )),
+                GestureEvent::DragEnd(DragEndEvent::new(mouse, button, point, far_point))
+            ],
+            take_log()
+        );
+
+        // But drags that start outside the drawn region should be filtered
+        let outside_point = Point::new(0.3, 0.5);
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, far_point, outside_point));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, outside_point, button));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, outside_point, point));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+        assert!(take_log().is_empty());
+
+        // Pinches
+        let mouse2 = Mouse::new(1);
+        let point2 = Point::new(0.55, 0.5);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse2, point2));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse2, point2, button));
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse2,
+            point2,
+            Point::new(0.6, 0.5),
+        ));
+        let log = take_log();
+        assert_eq!(1, log.len());
+        if let GestureEvent::Pinch(pinch) = log[0] {
+            assert_eq!((mouse, mouse2), pinch.get_mouses());
+            assert!((pinch.get_scale_factor() - 2.0).abs() < 0.001);
+        } else {
+            panic!("Expected a pinch, but got {:?}", log[0]);
+        }
+    }
+
+    #[test]
+    fn test_key_press_and_release() {
+        struct KeyComponent {
+            pressed: Rc<RefCell<Vec<KeyPressEvent>>>,
+            released: Rc<RefCell<Vec<KeyReleaseEvent>>>,
+            key_down_checks: Rc<Cell<u8>>,
+        }
+
+        impl Component for KeyComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_key_press();
+                buddy.subscribe_key_release();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+                // The pressed key should already be marked as down
+                assert!(buddy.is_key_down(event.get_key()));
+                self.key_down_checks.set(self.key_down_checks.get() + 1);
+                self.pressed.borrow_mut().push(event);
+
+                // Stop listening to key presses once escape is pressed
+                if event.get_key() == Key::Escape {
+                    buddy.unsubscribe_key_press();
+                }
+            }
+
+            fn on_key_release(&mut self, event: KeyReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+                assert!(!buddy.is_key_down(event.get_key()));
+                self.key_down_checks.set(self.key_down_checks.get() + 1);
+                self.released.borrow_mut().push(event);
+            }
+        }
+
+        let pressed = Rc::new(RefCell::new(Vec::new()));
+        let released = Rc::new(RefCell::new(Vec::new()));
+        let key_down_checks = Rc::new(Cell::new(0));
+        let mut application = Application::new(Box::new(KeyComponent {
+            pressed: Rc::clone(&pressed),
+            released: Rc::clone(&released),
+            key_down_checks: Rc::clone(&key_down_checks),
+        }));
+
+        // Key events don't need a prior render
+        let shift = KeyModifiers::new(true, false, false, false);
+        application.fire_key_press_event(KeyPressEvent::new(Key::Shift, KeyModifiers::none()));
+        application.fire_key_press_event(KeyPressEvent::new(Key::Letter('K'), shift));
+        assert_eq!(
+            vec![
+                KeyPressEvent::new(Key::Shift, KeyModifiers::none()),
+                KeyPressEvent::new(Key::Letter('K'), shift)
+            ],
+            *pressed.borrow()
+        );
+        assert!(released.borrow().is_empty());
+
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Letter('K'), shift));
+        assert_eq!(
+            vec![KeyReleaseEvent::new(Key::Letter('K'), shift)],
+            *released.borrow()
+        );
+
+        // After pressing escape, the component should no longer receive key presses...
+        application.fire_key_press_event(KeyPressEvent::new(Key::Escape, shift));
+        application.fire_key_press_event(KeyPressEvent::new(Key::Enter, shift));
+        assert_eq!(3, pressed.borrow().len());
+
+        // But it should still receive key releases
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Shift, KeyModifiers::none()));
+        assert_eq!(2, released.borrow().len());
+        assert_eq!(5, key_down_checks.get());
+    }
+
+    #[test]
+    fn test_buddy_is_key_down() {
+        struct KeyCheckComponent {
+            expected_keys: Rc<RefCell<Vec<Key>>>,
+            render_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for KeyCheckComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let expected_keys = self.expected_keys.borrow();
+                assert_eq!(*expected_keys, buddy.get_pressed_keys());
+                for key in &[Key::Left, Key::Right, Key::Space] {
+                    assert_eq!(expected_keys.contains(key), buddy.is_key_down(*key));
+                }
+                self.render_counter.set(self.render_counter.get() + 1);
+                entire_render_result()
+            }
+        }
+
+        let expected_keys = Rc::new(RefCell::new(Vec::new()));
+        let render_counter = Rc::new(Cell::new(0));
+        let mut application = Application::new(Box::new(KeyCheckComponent {
+            expected_keys: Rc::clone(&expected_keys),
+            render_counter: Rc::clone(&render_counter),
+        }));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 10, 10));
+
+        application.render(&renderer, true);
+
+        // The component didn't subscribe to key events, but should still know which keys are down
+        let modifiers = KeyModifiers::none();
+        application.fire_key_press_event(KeyPressEvent::new(Key::Left, modifiers));
+        *expected_keys.borrow_mut() = vec![Key::Left];
+        application.render(&renderer, true);
+
+        application.fire_key_press_event(KeyPressEvent::new(Key::Space, modifiers));
+        *expected_keys.borrow_mut() = vec![Key::Left, Key::Space];
+        application.render(&renderer, true);
+
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Left, modifiers));
+        *expected_keys.borrow_mut() = vec![Key::Space];
+        application.render(&renderer, true);
+
+        assert_eq!(4, render_counter.get());
+    }
+
+    #[test]
+    fn test_char_type() {
+        struct TypingComponent {
+            typed_text: Rc<RefCell<String>>,
+        }
+
+        impl Component for TypingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                assert!(buddy.subscribe_char_type().is_ok());
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+                self.typed_text.borrow_mut().push_str(event.get_text());
+                if event.get_text() == "." {
+                    buddy.unsubscribe_char_type();
+                }
+            }
+        }
+
+        let typed_text = Rc::new(RefCell::new(String::new()));
+        let mut application = Application::new(Box::new(TypingComponent {
+            typed_text: Rc::clone(&typed_text),
+        }));
+
+        // Unlike mouse events, char type events don't need a prior render
+        application.fire_char_type_event(CharTypeEvent::new("h".to_string()));
+        application.fire_char_type_event(CharTypeEvent::new("i".to_string()));
+        assert_eq!("hi", typed_text.borrow().as_str());
+
+        // Grapheme clusters consisting of multiple chars should be passed as a whole
+        application.fire_char_type_event(CharTypeEvent::new("e\u{301}".to_string()));
+        assert_eq!("hie\u{301}", typed_text.borrow().as_str());
+
+        // The component should no longer receive events after it unsubscribed
+        application.fire_char_type_event(CharTypeEvent::new(".".to_string()));
+        application.fire_char_type_event(CharTypeEvent::new("x".to_string()));
+        assert_eq!("hie\u{301}.", typed_text.borrow().as_str());
+    }
+
+    #[test]
+    fn test_root_focus() {
+        struct FocusComponent {
+            focus_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for FocusComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                assert!(!buddy.has_focus());
+                buddy.subscribe_key_press();
+                buddy.request_focus();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+                match event.get_key() {
+                    Key::Escape => buddy.release_focus(),
+                    _ => buddy.request_focus(),
+                }
+            }
+
+            fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+                assert!(buddy.has_focus());
+                self.focus_counter.set(self.focus_counter.get() + 1);
+            }
+
+            fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+                assert!(!buddy.has_focus());
+                self.focus_counter.set(self.focus_counter.get() + 10);
+            }
+        }
+
+        let focus_counter = Rc::new(Cell::new(0));
+        let mut application = Application::new(Box::new(FocusComponent {
+            focus_counter: Rc::clone(&focus_counter),
+        }));
+
+        // The focus request of the root component should be granted immediately
+        assert_eq!(1, focus_counter.get());
+
+        // Requesting the focus again shouldn't have any effect
+        let modifiers = KeyModifiers::none();
+        application.fire_key_press_event(KeyPressEvent::new(Key::Enter, modifiers));
+        assert_eq!(1, focus_counter.get());
+
+        application.fire_key_press_event(KeyPressEvent::new(Key::Escape, modifiers));
+        assert_eq!(11, focus_counter.get());
+
+        // Releasing the focus twice shouldn't have any effect either
+        application.fire_key_press_event(KeyPressEvent::new(Key::Escape, modifiers));
+        assert_eq!(11, focus_counter.get());
+
+        application.fire_key_press_event(KeyPressEvent::new(Key::Enter, modifiers));
+        assert_eq!(12, focus_counter.get());
+    }
+
+    struct TextInputComponent {
+        start_text: &'static str,
+        answers: Rc<RefCell<Vec<Option<String>>>>,
+    }
+
+    impl Component for TextInputComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_key_press();
+            buddy.request_text_input(self.start_text.to_string());
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_key_press(&mut self, _event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+            buddy.request_text_input(self.start_text.to_string());
+        }
+
+        fn on_text_input(&mut self, event: &TextInputEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.answers
+                .borrow_mut()
+                .push(event.get_text().map(|text| text.to_string()));
+        }
+    }
+
+    struct ScriptedTextInputProvider {
+        start_texts: Rc<RefCell<Vec<String>>>,
+        answers: Vec<Option<String>>,
+    }
+
+    impl TextInputProvider for ScriptedTextInputProvider {
+        fn provide_text_input(&mut self, start_text: String) -> Option<String> {
+            self.start_texts.borrow_mut().push(start_text);
+            self.answers.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_text_input_provider() {
+        let answers = Rc::new(RefCell::new(Vec::new()));
+        let start_texts = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TextInputComponent {
+            start_text: "start",
+            answers: Rc::clone(&answers),
+        }));
+
+        // The request of on_attach should be answered as soon as there is a provider
+        assert!(answers.borrow().is_empty());
+        application.set_text_input_provider(Box::new(ScriptedTextInputProvider {
+            start_texts: Rc::clone(&start_texts),
+            answers: vec![Some("first".to_string()), None],
+        }));
+        assert_eq!(vec![Some("first".to_string())], *answers.borrow());
+        assert_eq!(vec!["start".to_string()], *start_texts.borrow());
+
+        // Next requests should be answered right after the event handler
+        application.fire_key_press_event(KeyPressEvent::new(Key::Enter, KeyModifiers::none()));
+        assert_eq!(vec![Some("first".to_string()), None], *answers.borrow());
+        assert_eq!(2, start_texts.borrow().len());
+
+        // Since the request is answered by the provider, the wrapper shouldn't see it
+        assert_eq!(None, application.take_text_input_request());
+    }
+
+    #[test]
+    fn test_input_blocked_during_text_input() {
+        let answers = Rc::new(RefCell::new(Vec::new()));
+        let start_texts = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TextInputComponent {
+            start_text: "start",
+            answers: Rc::clone(&answers),
+        }));
+        assert!(application.is_awaiting_text_input());
+
+        // The key press would request text input again if it reached the component
+        let enter = KeyPressEvent::new(Key::Enter, KeyModifiers::none());
+        application.fire_key_press_event(enter);
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Enter, KeyModifiers::none()));
+        assert!(answers.borrow().is_empty());
+
+        application.set_text_input_provider(Box::new(ScriptedTextInputProvider {
+            start_texts: Rc::clone(&start_texts),
+            answers: vec![Some("first".to_string()), Some("second".to_string())],
+        }));
+        assert!(!application.is_awaiting_text_input());
+        assert_eq!(vec!["start".to_string()], *start_texts.borrow());
+        assert_eq!(vec![Some("first".to_string())], *answers.borrow());
+
+        // Now that the request is answered, the key presses should reach the component again
+        application.fire_key_press_event(enter);
+        assert_eq!(
+            vec![Some("first".to_string()), Some("second".to_string())],
+            *answers.borrow()
+        );
+    }
+
+    #[test]
+    fn test_text_input_without_provider() {
+        let answers = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TextInputComponent {
+            start_text: "hello",
+            answers: Rc::clone(&answers),
+        }));
+
+        // The wrapper should be able to take the request once
+        assert_eq!(
+            Some("hello".to_string()),
+            application.take_text_input_request()
+        );
+        assert_eq!(None, application.take_text_input_request());
+
+        application.fire_text_input_event(Some("hello world".to_string()));
+        assert_eq!(vec![Some("hello world".to_string())], *answers.borrow());
+
+        // Answering when nobody is waiting shouldn't do anything
+        application.fire_text_input_event(Some("ignored".to_string()));
+        assert_eq!(1, answers.borrow().len());
+    }
+
+    #[test]
+    fn test_change_menu() {
+        struct ChangingComponent {
+            click_counter: Rc<Cell<u8>>,
+            changed_counter: Rc<Cell<u8>>,
+        }
+
+        impl Component for ChangingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                self.click_counter.set(self.click_counter.get() + 1);
+                let changed_counter = Rc::clone(&self.changed_counter);
                 buddy.change_menu(Box::new(move |_old_menu: Box<dyn Component>| {
                     Box::new(ChangedComponent {
                         counter: changed_counter,
@@ -1481,4 +2824,285 @@ mod tests {
         // And component 1 shouldn't have received any more events
         assert_eq!(4, counter1.get());
     }
+
+    #[test]
+    fn test_resize() {
+        struct ResizeComponent {
+            log: Rc<RefCell<Vec<ResizeEvent>>>,
+            next_log: Option<Rc<RefCell<Vec<ResizeEvent>>>>,
+        }
+
+        impl Component for ResizeComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn on_resize(&mut self, event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(event);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                if let Some(next_log) = self.next_log.take() {
+                    buddy.change_menu(Box::new(move |_old_menu: Box<dyn Component>| {
+                        Box::new(ResizeComponent {
+                            log: next_log,
+                            next_log: None,
+                        })
+                    }));
+                }
+            }
+        }
+
+        let log1 = Rc::new(RefCell::new(Vec::new()));
+        let log2 = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(ResizeComponent {
+            log: Rc::clone(&log1),
+            next_log: Some(Rc::clone(&log2)),
+        }));
+
+        let small = ResizeEvent::new(100, 50);
+        let large = ResizeEvent::new(400, 300);
+        assert!((small.get_aspect_ratio() - 2.0).abs() < 0.001);
+
+        application.fire_resize_event(small);
+        assert_eq!(vec![small], *log1.borrow());
+
+        // Firing the same size again shouldn't have any effect
+        application.fire_resize_event(small);
+        assert_eq!(1, log1.borrow().len());
+
+        application.fire_resize_event(large);
+        assert_eq!(vec![small, large], *log1.borrow());
+
+        // The new root component should immediately receive the current size
+        application.render(
+            &test_renderer(RenderRegion::with_size(0, 0, 400, 300)),
+            false,
+        );
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.5, 0.5),
+            MouseButton::primary(),
+        ));
+        assert_eq!(vec![large], *log2.borrow());
+        assert_eq!(2, log1.borrow().len());
+    }
+
+    #[test]
+    fn test_navigation() {
+        struct NavigatingComponent {
+            name: String,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for NavigatingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+                self.log.borrow_mut().push(format!("{} attach", self.name));
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.log.borrow_mut().push(format!("{} render", self.name));
+                entire_render_result()
+            }
+
+            // Clicking at the left pushes a menu, clicking in the middle pops this menu, and
+            // clicking at the right replaces this menu
+            fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                let x = event.get_point().get_x();
+                if x < 0.3 {
+                    buddy.push_menu(navigating_menu(format!("{}+", self.name), &self.log));
+                } else if x < 0.7 {
+                    buddy.pop_menu();
+                } else {
+                    buddy.replace_menu(navigating_menu(format!("{}'", self.name), &self.log));
+                }
+            }
+
+            fn on_detach(&mut self) {
+                self.log.borrow_mut().push(format!("{} detach", self.name));
+            }
+        }
+
+        // The menus on the navigation stack should keep their children
+        fn navigating_menu(name: String, log: &Rc<RefCell<Vec<String>>>) -> Box<dyn Component> {
+            let mut menu = SimpleFlatMenu::new(None);
+            menu.add_component(
+                Box::new(NavigatingComponent {
+                    name,
+                    log: Rc::clone(log),
+                }),
+                ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+            );
+            Box::new(menu)
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(navigating_menu("A".to_string(), &log));
+        assert_eq!(vec!["A attach"], log.replace(Vec::new()));
+
+        let render = |application: &mut Application| {
+            application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        };
+
+        // The root component needs to render before it can receive clicks
+        let click = |application: &mut Application, x: f32| {
+            render(application);
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, 0.5),
+                MouseButton::primary(),
+            ));
+        };
+
+        // There is nothing to pop yet
+        click(&mut application, 0.5);
+        assert_eq!(vec!["A render"], log.replace(Vec::new()));
+
+        // The pushed menus should be detached
+        click(&mut application, 0.1);
+        click(&mut application, 0.1);
+        assert_eq!(
+            vec![
+                "A detach",
+                "A+ attach",
+                "A+ render",
+                "A+ detach",
+                "A++ attach"
+            ],
+            log.replace(Vec::new())
+        );
+        assert_eq!(2, application.get_navigation_depth());
+
+        // Replacing should keep the navigation stack intact
+        click(&mut application, 0.9);
+        assert_eq!(
+            vec!["A++ render", "A++ detach", "A++' attach"],
+            log.replace(Vec::new())
+        );
+        assert_eq!(2, application.get_navigation_depth());
+
+        // The restored menus should still have their children, which should be attached again
+        click(&mut application, 0.5);
+        render(&mut application);
+        assert_eq!(
+            vec!["A++' render", "A++' detach", "A+ attach", "A+ render"],
+            log.replace(Vec::new())
+        );
+        assert!(application.go_back());
+        render(&mut application);
+        assert_eq!(
+            vec!["A+ detach", "A attach", "A render"],
+            log.replace(Vec::new())
+        );
+        assert!(!application.go_back());
+        assert_eq!(0, application.get_navigation_depth());
+
+        // The popped menus should be restored when going forward
+        assert!(application.go_forward());
+        render(&mut application);
+        assert_eq!(
+            vec!["A detach", "A+ attach", "A+ render"],
+            log.replace(Vec::new())
+        );
+        assert_eq!(1, application.get_navigation_depth());
+
+        // Pushing a menu should clear the forward stack (whose menus were already detached)
+        click(&mut application, 0.1);
+        assert_eq!(vec!["A+ detach", "A++ attach"], log.replace(Vec::new()));
+        assert!(!application.go_forward());
+        assert!(log.replace(Vec::new()).is_empty());
+        assert_eq!(2, application.get_navigation_depth());
+
+        // Only the root menu is still attached when the application is dropped
+        drop(application);
+        assert_eq!(vec!["A++ detach"], log.replace(Vec::new()));
+    }
+
+    #[test]
+    fn test_timers() {
+        struct TimerComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for TimerComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.schedule_timer(Duration::from_millis(100), 1);
+                buddy.schedule_timer(Duration::from_millis(50), 2);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let time = buddy.get_current_time().as_millis();
+                self.log.borrow_mut().push(format!("render at {}", time));
+                entire_render_result()
+            }
+
+            fn on_timer(&mut self, event: TimerEvent, buddy: &mut dyn ComponentBuddy) {
+                let time = buddy.get_current_time().as_millis();
+                self.log
+                    .borrow_mut()
+                    .push(format!("timer {} at {}", event.get_id(), time));
+
+                // Timers that expire immediately should wait until the next tick
+                if event.get_id() == 1 {
+                    buddy.schedule_timer(Duration::from_millis(0), 3);
+                    buddy.request_animation_frame();
+                }
+            }
+
+            fn on_animation_frame(
+                &mut self,
+                event: AnimationFrameEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                let time = event.get_time().as_millis();
+                self.log.borrow_mut().push(format!("frame at {}", time));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut application = Application::new(Box::new(TimerComponent {
+            log: Rc::clone(&log),
+        }));
+
+        application.tick(Duration::from_millis(40));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        assert_eq!(vec!["render at 40"], log.replace(Vec::new()));
+
+        // The timers should be fired in the order in which they expire
+        application.tick(Duration::from_millis(120));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        assert_eq!(
+            vec![
+                "timer 2 at 120",
+                "timer 1 at 120",
+                "frame at 120",
+                "render at 120"
+            ],
+            log.replace(Vec::new())
+        );
+
+        application.tick(Duration::from_millis(130));
+        application.tick(Duration::from_millis(1000));
+        assert_eq!(vec!["timer 3 at 130"], log.replace(Vec::new()));
+    }
 }
@@ -0,0 +1,100 @@
+use crate::*;
+
+/// A helper struct to keep track of keyboard information (currently just the keys that are
+/// pressed). Like the `MouseStore`, this struct is made to make the implementation of
+/// `ComponentBuddy`s easier.
+pub struct KeyboardStore {
+    // The number of pressed keys is expected to be very small, so a Vec is fine
+    pressed_keys: Vec<Key>,
+    keyboard_available: bool,
+}
+
+impl KeyboardStore {
+    /// Constructs a new `KeyboardStore` in which no key is pressed. The keyboard will initially be
+    /// considered available.
+    pub fn new() -> Self {
+        Self {
+            pressed_keys: Vec::with_capacity(4),
+            keyboard_available: true,
+        }
+    }
+
+    /// Checks whether the user has a keyboard that can be used to type text
+    pub fn is_keyboard_available(&self) -> bool {
+        self.keyboard_available
+    }
+
+    /// Changes whether the user has a keyboard that can be used to type text
+    pub fn set_keyboard_available(&mut self, available: bool) {
+        self.keyboard_available = available;
+    }
+
+    /// Marks the given `Key` as pressed. This has no effect if the key was already pressed.
+    pub fn press(&mut self, key: Key) {
+        if !self.is_pressed(key) {
+            self.pressed_keys.push(key);
+        }
+    }
+
+    /// Marks the given `Key` as released. This has no effect if the key wasn't pressed.
+    pub fn release(&mut self, key: Key) {
+        self.pressed_keys.retain(|pressed_key| *pressed_key != key);
+    }
+
+    /// Checks whether the given `Key` is currently pressed
+    pub fn is_pressed(&self, key: Key) -> bool {
+        self.pressed_keys.contains(&key)
+    }
+
+    /// Creates and returns a `Vec` containing all keys that are currently pressed, in the order
+    /// they were pressed.
+    pub fn get_pressed_keys(&self) -> Vec<Key> {
+        self.pressed_keys.clone()
+    }
+}
+
+impl Default for KeyboardStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_press_and_release() {
+        let mut store = KeyboardStore::new();
+        assert!(store.get_pressed_keys().is_empty());
+        assert!(!store.is_pressed(Key::Enter));
+
+        store.press(Key::Enter);
+        store.press(Key::Letter('A'));
+        assert!(store.is_pressed(Key::Enter));
+        assert!(store.is_pressed(Key::Letter('A')));
+        assert!(!store.is_pressed(Key::Letter('B')));
+        assert_eq!(vec![Key::Enter, Key::Letter('A')], store.get_pressed_keys());
+
+        // Pressing a key twice shouldn't matter
+        store.press(Key::Enter);
+        assert_eq!(vec![Key::Enter, Key::Letter('A')], store.get_pressed_keys());
+
+        // Releasing a key that isn't pressed shouldn't matter either
+        store.release(Key::Escape);
+        assert_eq!(vec![Key::Enter, Key::Letter('A')], store.get_pressed_keys());
+
+        store.release(Key::Enter);
+        assert!(!store.is_pressed(Key::Enter));
+        assert_eq!(vec![Key::Letter('A')], store.get_pressed_keys());
+    }
+
+    #[test]
+    fn test_keyboard_available() {
+        let mut store = KeyboardStore::new();
+        assert!(store.is_keyboard_available());
+        store.set_keyboard_available(false);
+        assert!(!store.is_keyboard_available());
+    }
+}
@@ -1,13 +1,35 @@
+mod keyboard_store;
 mod mouse_store;
+mod navigation;
 mod root;
 mod subscriptions;
 
+pub use keyboard_store::*;
 pub use mouse_store::*;
+pub use navigation::*;
 pub use root::*;
 pub use subscriptions::*;
 
 use crate::*;
 
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The error that the `subscribe_char_type` method of `ComponentBuddy` returns when the user
+/// doesn't have a keyboard to type with.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct NoKeyboardError;
+
+impl Display for NoKeyboardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no keyboard is available")
+    }
+}
+
+impl std::error::Error for NoKeyboardError {}
+
 /// Every `Component` will be assigned a *buddy*. This buddy will be passed as
 /// parameter to every method of the `Component` trait. The buddy is the primary
 /// way the component can interact with its parent menu, or the root of knukki
@@ -39,6 +61,10 @@ pub trait ComponentBuddy {
     /// useful to implement 'Back' buttons or modals (so it knows which
     /// component should be drawn in the background).
     ///
+    /// The current menu will be detached before that function is called. If
+    /// the new menu keeps it, it is responsible for attaching it again (and
+    /// for detaching it when it's no longer needed).
+    ///
     /// ### Request
     /// Like the docs above suggest, it is a *request*: it might not happen in
     /// some rare cases (when multiple components request to change the menu at
@@ -49,15 +75,46 @@ pub trait ComponentBuddy {
         create_new_menu: Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>,
     );
 
+    /// Requests to replace the root menu with `new_menu`, but remembers the current root menu so
+    /// that it can be restored with `pop_menu` (or the *back* button of the browser when the web
+    /// wrapper is used).
+    ///
+    /// The current root menu will be detached while it is on the navigation stack, and attached
+    /// again when it is restored. Just like `change_menu`, this is only a *request*.
+    fn push_menu(&mut self, new_menu: Box<dyn Component>);
+
+    /// Requests to restore the menu that was on the root before the last `push_menu`. The current
+    /// root menu will be detached, but it will be remembered so that the user can go *forward*
+    /// again (until another menu is pushed).
+    ///
+    /// If the navigation stack is empty, this request will be ignored.
+    fn pop_menu(&mut self);
+
+    /// Requests to replace the root menu with `new_menu`, *without* remembering the current root
+    /// menu. Unlike `change_menu`, the navigation stack will be kept intact.
+    fn replace_menu(&mut self, new_menu: Box<dyn Component>);
+
     /// Prompts the user to type some text for the component.
     ///
     /// This method will work even if there is no keyboard, but it will always
-    /// block the entire application until the user finishes typing.
+    /// block the input of the entire application until the user finishes typing:
+    /// the `Application` won't propagate any mouse or keyboard events while it is
+    /// waiting for the answer.
     ///
     /// The user will be asked to modify the `start_text`. The user will be
     /// able to either change the start_text and return `Some` replacement
     /// text, or cancel and return `None`.
-    fn request_text_input(&self, start_text: String) -> Option<String>;
+    ///
+    /// ### The answer
+    /// The answer of the user will *not* be returned by this method. Instead,
+    /// the `on_text_input` method of the component will be called once the
+    /// user finished typing. This can happen right after the current event
+    /// handler returns, or much later (depending on the *wrapper*).
+    ///
+    /// Only 1 text input request can be answered at a time: if another
+    /// component requests text input before the user answered, only the
+    /// latest request will be answered.
+    fn request_text_input(&mut self, start_text: String);
 
     /// Requests to re-render this component (by calling its render method)
     /// during the next frame.
@@ -70,6 +127,70 @@ pub trait ComponentBuddy {
     /// not called, for instance when the window is resized.
     fn request_render(&mut self);
 
+    /// Requests to re-render this component during the next frame, just like `request_render`.
+    /// Additionally, the `on_animation_frame` method of this component will be called right before
+    /// that frame is rendered, with the timestamp of the frame. This method should be preferred by
+    /// animations.
+    fn request_animation_frame(&mut self);
+
+    /// Schedules a timer that will expire after the given `delay`. When it expires, the `on_timer`
+    /// method of this component will be called with a `TimerEvent` with the given `id`. The id
+    /// can be used to distinguish the timers of the component: it doesn't need to be unique.
+    ///
+    /// The timers are checked during each call to the `tick` method of the `Application`, so they
+    /// can expire a bit later than requested (typically at most 1 frame). Timers of components
+    /// that are detached before they expire will be cancelled.
+    fn schedule_timer(&mut self, delay: Duration, id: u32);
+
+    /// Requests the keyboard focus for this component. While a component has the focus, it will
+    /// be the only component that receives `CharTypeEvent`s.
+    ///
+    /// Like `request_render`, this is a *request*: the focus will be given to the component soon
+    /// after the current event handler returns, and the `on_focus_gained` method of the component
+    /// will be called when that happens. The request will have no effect if the component already
+    /// has the focus.
+    fn request_focus(&mut self);
+
+    /// Gives up the keyboard focus of this component. If the component has the focus, its
+    /// `on_focus_lost` method will be called soon after the current event handler returns.
+    fn release_focus(&mut self);
+
+    /// Starts a drag-and-drop operation with the given `mouse` that carries the given `payload`.
+    /// Typically, this is called from the `on_drag_start` or `on_mouse_press` method of the
+    /// component.
+    ///
+    /// While the operation is ongoing, the components that are subscribed for drag-and-drop
+    /// events will receive a `DragEnterEvent`, `DragOverEvent`s and a `DragLeaveEvent` as the
+    /// mouse moves over them. When the user releases a button of the mouse, the component under
+    /// the mouse will receive a `DropEvent`. The operation is cancelled when the mouse leaves
+    /// the application.
+    ///
+    /// The payload can be anything: the receiving components can use the `get_payload` method of
+    /// the events to check whether it has a type they can accept. If another drag-and-drop
+    /// operation is still ongoing, it will be cancelled.
+    fn start_drag_and_drop(&mut self, mouse: Mouse, payload: Rc<dyn Any>);
+
+    /// Captures the given `mouse` for this component. While a mouse is captured, all its
+    /// `MouseMoveEvent`s and `MouseReleaseEvent`s will be fired at this component, even when the
+    /// mouse is outside the domain (or drawn region) of this component. This is useful for
+    /// components like sliders and scrollbars that should keep following the mouse while the user
+    /// is dragging them. Other components won't receive any move, enter, leave, or release events
+    /// of the mouse while it is captured.
+    ///
+    /// The component won't receive a `MouseLeaveEvent` for the mouse while it is captured. If the
+    /// mouse is outside the component when the capture is released, the component will receive a
+    /// `MouseLeaveEvent` at that moment. If the mouse leaves the application, the capture will be
+    /// released automatically.
+    ///
+    /// Only 1 component can capture a mouse at a time: if another component captures the same
+    /// mouse, the capture of this component will be released. Note that the component still needs
+    /// to subscribe for the `MouseMoveEvent` and `MouseReleaseEvent` to receive them.
+    fn capture_mouse(&mut self, mouse: Mouse);
+
+    /// Releases the capture of the given `mouse` by this component, if it has captured it.
+    /// Components typically call this in their `on_mouse_release` method.
+    fn release_mouse_capture(&mut self, mouse: Mouse);
+
     // Subscribe methods
 
     /// Subscribes the component for the `MouseClickEvent`
@@ -114,14 +235,71 @@ pub trait ComponentBuddy {
     /// Cancels the components subscription for the `MouseLeaveEvent`
     fn unsubscribe_mouse_leave(&mut self);
 
+    /// Subscribes the component for the `MouseScrollEvent`
+    fn subscribe_mouse_scroll(&mut self);
+
+    /// Cancels the components subscription for the `MouseScrollEvent`
+    fn unsubscribe_mouse_scroll(&mut self);
+
+    /// Subscribes the component for the `DoubleClickEvent`
+    fn subscribe_double_click(&mut self);
+
+    /// Cancels the components subscription for the `DoubleClickEvent`
+    fn unsubscribe_double_click(&mut self);
+
+    /// Subscribes the component for the `LongPressEvent`
+    fn subscribe_long_press(&mut self);
+
+    /// Cancels the components subscription for the `LongPressEvent`
+    fn unsubscribe_long_press(&mut self);
+
+    /// Subscribes the component for the `DragStartEvent`, `DragUpdateEvent` and `DragEndEvent`
+    fn subscribe_drag(&mut self);
+
+    /// Cancels the components subscription for the `DragStartEvent`, `DragUpdateEvent` and
+    /// `DragEndEvent`. If the component unsubscribes during a drag, it won't receive the
+    /// `DragEndEvent` of that drag.
+    fn unsubscribe_drag(&mut self);
+
+    /// Subscribes the component for the `PinchEvent`
+    fn subscribe_pinch(&mut self);
+
+    /// Cancels the components subscription for the `PinchEvent`
+    fn unsubscribe_pinch(&mut self);
+
+    /// Subscribes the component for the `DragEnterEvent`, `DragOverEvent`, `DragLeaveEvent` and
+    /// `DropEvent`. This makes the component a potential target for drag-and-drop operations.
+    fn subscribe_drag_and_drop(&mut self);
+
+    /// Cancels the components subscription for the `DragEnterEvent`, `DragOverEvent`,
+    /// `DragLeaveEvent` and `DropEvent`.
+    fn unsubscribe_drag_and_drop(&mut self);
+
     /// Subscribes the component for the `CharTypeEvent`. This method will return
-    /// `Ok` if a keyboard is available, and `Err` if not. If this method returns
-    /// `Err`, but the component really needs text input, it should call
-    /// `request_text_input`.
-    fn subscribe_char_type(&self) -> Result<(), ()>;
+    /// `Ok` if a keyboard is available, and `Err(NoKeyboardError)` if not. If this
+    /// method returns `Err`, but the component really needs text input, it should
+    /// call `request_text_input`.
+    fn subscribe_char_type(&mut self) -> Result<(), NoKeyboardError>;
 
     /// Cancels the subscription of the component for the `CharTypeEvent`.
-    fn unsubscribe_char_type(&self);
+    fn unsubscribe_char_type(&mut self);
+
+    /// Subscribes the component for the `KeyPressEvent`. If the user doesn't have a keyboard, the
+    /// component simply won't receive any `KeyPressEvent`s.
+    ///
+    /// Note that menus use the Tab key to move the focus between their components. Components will
+    /// only receive Tab presses that didn't move the focus.
+    fn subscribe_key_press(&mut self);
+
+    /// Cancels the subscription of the component for the `KeyPressEvent`
+    fn unsubscribe_key_press(&mut self);
+
+    /// Subscribes the component for the `KeyReleaseEvent`. If the user doesn't have a keyboard,
+    /// the component simply won't receive any `KeyReleaseEvent`s.
+    fn subscribe_key_release(&mut self);
+
+    /// Cancels the subscription of the component for the `KeyReleaseEvent`
+    fn unsubscribe_key_release(&mut self);
 
     // Read methods
 
@@ -195,4 +373,24 @@ pub trait ComponentBuddy {
     /// If this method is called during the `fire_mouse_leave_event` of the `Application` for some
     /// mouse *M*, the result of this method *won't* contain *M*.
     fn get_all_mouses(&self) -> Vec<Mouse>;
+
+    /// Gets a `Vec` containing all `Key`s that are currently pressed/down, in the order they were
+    /// pressed. This method can be called during any event.
+    fn get_pressed_keys(&self) -> Vec<Key>;
+
+    /// Gets the current time, as it was passed to the latest call to the `tick` method of the
+    /// `Application`. During the `render` method, this is the timestamp of the frame that is
+    /// being rendered. The starting point of the time is chosen by the *wrapper*, so only the
+    /// differences between these times are meaningful.
+    fn get_current_time(&self) -> Duration;
+
+    /// Checks whether this component currently has the keyboard focus. This will be true between
+    /// the calls to the `on_focus_gained` and `on_focus_lost` methods of the component.
+    fn has_focus(&self) -> bool;
+
+    /// Checks if the given `Key` is currently being pressed/down. This method can be called during
+    /// any event.
+    fn is_key_down(&self, key: Key) -> bool {
+        self.get_pressed_keys().contains(&key)
+    }
 }
@@ -0,0 +1,20 @@
+use crate::*;
+
+/// A request of a component to change the navigation stack of the `Application`. These are made
+/// with the `push_menu`, `pop_menu` and `replace_menu` methods of `ComponentBuddy`.
+pub enum NavigationRequest {
+    Push(Box<dyn Component>),
+    Pop,
+    Replace(Box<dyn Component>),
+}
+
+impl NavigationRequest {
+    /// Passes this request on to the given (parent) buddy
+    pub(crate) fn forward_to(self, buddy: &mut dyn ComponentBuddy) {
+        match self {
+            Self::Push(menu) => buddy.push_menu(menu),
+            Self::Pop => buddy.pop_menu(),
+            Self::Replace(menu) => buddy.replace_menu(menu),
+        }
+    }
+}
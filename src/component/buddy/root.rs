@@ -1,6 +1,10 @@
 use crate::*;
+use std::any::Any;
 use std::cell::{Ref, RefCell};
 use std::rc::Rc;
+use std::time::Duration;
+
+type CreateNextMenu = Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>;
 
 pub struct RootComponentBuddy {
     subscriptions: ComponentSubscriptions,
@@ -8,12 +12,31 @@ pub struct RootComponentBuddy {
     // This is optional to ease the writing of unit tests, but the *Application* is expected to
     // call set_mouse_store in production environments.
     mouse_store: Option<Rc<RefCell<MouseStore>>>,
+    // Unlike the mouse store, this starts as an empty store rather than None, so that components
+    // can ask for the pressed keys in unit tests without setting a keyboard store first.
+    keyboard_store: Rc<RefCell<KeyboardStore>>,
 
     last_render_result: Option<RenderResultStruct>,
 
-    create_next_menu: Option<Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>>,
+    create_next_menu: Option<CreateNextMenu>,
 
     requested_render: bool,
+    requested_animation_frame: bool,
+
+    has_focus: bool,
+    // Some(true) if the root component requested the focus, Some(false) if it released the focus
+    focus_request: Option<bool>,
+
+    text_input_request: Option<String>,
+
+    drag_and_drop_request: Option<(Mouse, Rc<dyn Any>)>,
+
+    navigation_request: Option<NavigationRequest>,
+
+    timer_requests: Vec<(Duration, u32)>,
+    current_time: Duration,
+
+    mouse_capture_requests: Vec<(Mouse, bool)>,
 }
 
 impl RootComponentBuddy {
@@ -21,12 +44,28 @@ impl RootComponentBuddy {
         Self {
             subscriptions: ComponentSubscriptions::new(),
             mouse_store: None,
+            keyboard_store: Rc::new(RefCell::new(KeyboardStore::default())),
             last_render_result: None,
             create_next_menu: None,
 
             // Components should normally render as soon as possible after they
             // are attached
             requested_render: true,
+            requested_animation_frame: false,
+
+            has_focus: false,
+            focus_request: None,
+
+            text_input_request: None,
+
+            drag_and_drop_request: None,
+
+            navigation_request: None,
+
+            timer_requests: Vec::new(),
+            current_time: Duration::from_secs(0),
+
+            mouse_capture_requests: Vec::new(),
         }
     }
 
@@ -45,6 +84,14 @@ impl RootComponentBuddy {
             .borrow()
     }
 
+    pub fn set_keyboard_store(&mut self, keyboard_store: Rc<RefCell<KeyboardStore>>) {
+        self.keyboard_store = keyboard_store;
+    }
+
+    fn get_keyboard_store(&self) -> Ref<'_, KeyboardStore> {
+        self.keyboard_store.borrow()
+    }
+
     pub fn did_request_render(&self) -> bool {
         self.requested_render
     }
@@ -61,6 +108,50 @@ impl RootComponentBuddy {
         self.requested_render = false;
     }
 
+    pub fn did_request_animation_frame(&self) -> bool {
+        self.requested_animation_frame
+    }
+
+    /// Checks whether the component requested an animation frame, and clears that request
+    pub fn take_animation_frame_request(&mut self) -> bool {
+        std::mem::replace(&mut self.requested_animation_frame, false)
+    }
+
+    pub fn set_focus(&mut self, has_focus: bool) {
+        self.has_focus = has_focus;
+    }
+
+    pub fn take_focus_request(&mut self) -> Option<bool> {
+        self.focus_request.take()
+    }
+
+    pub fn take_text_input_request(&mut self) -> Option<String> {
+        self.text_input_request.take()
+    }
+
+    pub fn take_drag_and_drop_request(&mut self) -> Option<(Mouse, Rc<dyn Any>)> {
+        self.drag_and_drop_request.take()
+    }
+
+    pub fn take_navigation_request(&mut self) -> Option<NavigationRequest> {
+        self.navigation_request.take()
+    }
+
+    /// Takes the timers that were scheduled by the component, as pairs (delay, id)
+    pub fn take_timer_requests(&mut self) -> Vec<(Duration, u32)> {
+        std::mem::take(&mut self.timer_requests)
+    }
+
+    pub fn set_current_time(&mut self, current_time: Duration) {
+        self.current_time = current_time;
+    }
+
+    /// Takes the capture requests of the component, in the order in which they were made. The
+    /// `bool` is true for `capture_mouse` and false for `release_mouse_capture`.
+    pub fn take_mouse_capture_requests(&mut self) -> Vec<(Mouse, bool)> {
+        std::mem::take(&mut self.mouse_capture_requests)
+    }
+
     pub fn has_next_menu(&self) -> bool {
         self.create_next_menu.is_some()
     }
@@ -72,6 +163,11 @@ impl RootComponentBuddy {
             .expect("Only call this method after has_next_menu returned true");
         create_next_menu(current_menu)
     }
+
+    /// Takes the function that was passed to `change_menu`, if any
+    pub fn take_next_menu(&mut self) -> Option<CreateNextMenu> {
+        self.create_next_menu.take()
+    }
 }
 
 impl ComponentBuddy for RootComponentBuddy {
@@ -82,14 +178,55 @@ impl ComponentBuddy for RootComponentBuddy {
         self.create_next_menu = Some(create_new_menu);
     }
 
-    fn request_text_input(&self, _start_text: String) -> Option<String> {
-        todo!()
+    fn push_menu(&mut self, new_menu: Box<dyn Component>) {
+        self.navigation_request = Some(NavigationRequest::Push(new_menu));
+    }
+
+    fn pop_menu(&mut self) {
+        self.navigation_request = Some(NavigationRequest::Pop);
+    }
+
+    fn replace_menu(&mut self, new_menu: Box<dyn Component>) {
+        self.navigation_request = Some(NavigationRequest::Replace(new_menu));
+    }
+
+    fn request_text_input(&mut self, start_text: String) {
+        self.text_input_request = Some(start_text);
     }
 
     fn request_render(&mut self) {
         self.requested_render = true;
     }
 
+    fn request_animation_frame(&mut self) {
+        self.requested_animation_frame = true;
+        self.request_render();
+    }
+
+    fn schedule_timer(&mut self, delay: Duration, id: u32) {
+        self.timer_requests.push((delay, id));
+    }
+
+    fn request_focus(&mut self) {
+        self.focus_request = Some(true);
+    }
+
+    fn release_focus(&mut self) {
+        self.focus_request = Some(false);
+    }
+
+    fn start_drag_and_drop(&mut self, mouse: Mouse, payload: Rc<dyn Any>) {
+        self.drag_and_drop_request = Some((mouse, payload));
+    }
+
+    fn capture_mouse(&mut self, mouse: Mouse) {
+        self.mouse_capture_requests.push((mouse, true));
+    }
+
+    fn release_mouse_capture(&mut self, mouse: Mouse) {
+        self.mouse_capture_requests.push((mouse, false));
+    }
+
     fn subscribe_mouse_click(&mut self) {
         self.subscriptions.mouse_click = true;
     }
@@ -146,12 +283,81 @@ impl ComponentBuddy for RootComponentBuddy {
         self.subscriptions.mouse_leave = false;
     }
 
-    fn subscribe_char_type(&self) -> Result<(), ()> {
-        todo!()
+    fn subscribe_mouse_scroll(&mut self) {
+        self.subscriptions.mouse_scroll = true;
+    }
+
+    fn unsubscribe_mouse_scroll(&mut self) {
+        self.subscriptions.mouse_scroll = false;
+    }
+
+    fn subscribe_double_click(&mut self) {
+        self.subscriptions.double_click = true;
+    }
+
+    fn unsubscribe_double_click(&mut self) {
+        self.subscriptions.double_click = false;
+    }
+
+    fn subscribe_long_press(&mut self) {
+        self.subscriptions.long_press = true;
+    }
+
+    fn unsubscribe_long_press(&mut self) {
+        self.subscriptions.long_press = false;
+    }
+
+    fn subscribe_drag(&mut self) {
+        self.subscriptions.drag = true;
+    }
+
+    fn unsubscribe_drag(&mut self) {
+        self.subscriptions.drag = false;
+    }
+
+    fn subscribe_pinch(&mut self) {
+        self.subscriptions.pinch = true;
+    }
+
+    fn unsubscribe_pinch(&mut self) {
+        self.subscriptions.pinch = false;
+    }
+
+    fn subscribe_drag_and_drop(&mut self) {
+        self.subscriptions.drag_and_drop = true;
     }
 
-    fn unsubscribe_char_type(&self) {
-        todo!()
+    fn unsubscribe_drag_and_drop(&mut self) {
+        self.subscriptions.drag_and_drop = false;
+    }
+
+    fn subscribe_char_type(&mut self) -> Result<(), NoKeyboardError> {
+        if self.get_keyboard_store().is_keyboard_available() {
+            self.subscriptions.char_type = true;
+            Ok(())
+        } else {
+            Err(NoKeyboardError)
+        }
+    }
+
+    fn unsubscribe_char_type(&mut self) {
+        self.subscriptions.char_type = false;
+    }
+
+    fn subscribe_key_press(&mut self) {
+        self.subscriptions.key_press = true;
+    }
+
+    fn unsubscribe_key_press(&mut self) {
+        self.subscriptions.key_press = false;
+    }
+
+    fn subscribe_key_release(&mut self) {
+        self.subscriptions.key_release = true;
+    }
+
+    fn unsubscribe_key_release(&mut self) {
+        self.subscriptions.key_release = false;
     }
 
     fn get_mouse_position(&self, mouse: Mouse) -> Option<Point> {
@@ -197,4 +403,34 @@ impl ComponentBuddy for RootComponentBuddy {
         // All mouses are local for the root component
         self.get_local_mouses()
     }
+
+    fn get_pressed_keys(&self) -> Vec<Key> {
+        self.get_keyboard_store().get_pressed_keys()
+    }
+
+    fn get_current_time(&self) -> Duration {
+        self.current_time
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.get_keyboard_store().is_pressed(key)
+    }
+
+    fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_default_keyboard_store() {
+        // The buddy should work without keyboard store, as if no key is pressed
+        let buddy = RootComponentBuddy::new();
+        assert!(!buddy.is_key_down(Key::Enter));
+        assert!(buddy.get_pressed_keys().is_empty());
+    }
 }
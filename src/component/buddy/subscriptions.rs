@@ -7,8 +7,18 @@ pub struct ComponentSubscriptions {
     pub mouse_move: bool,
     pub mouse_leave: bool,
     pub mouse_enter: bool,
+    pub mouse_scroll: bool,
 
-    // Other subscriptions
+    // Gesture event subscriptions
+    pub double_click: bool,
+    pub long_press: bool,
+    pub drag: bool,
+    pub pinch: bool,
+    pub drag_and_drop: bool,
+
+    // Keyboard event subscriptions
+    pub key_press: bool,
+    pub key_release: bool,
     pub char_type: bool,
 }
 
@@ -22,7 +32,16 @@ impl ComponentSubscriptions {
             mouse_move: false,
             mouse_leave: false,
             mouse_enter: false,
+            mouse_scroll: false,
+
+            double_click: false,
+            long_press: false,
+            drag: false,
+            pinch: false,
+            drag_and_drop: false,
 
+            key_press: false,
+            key_release: false,
             char_type: false,
         }
     }
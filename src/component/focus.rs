@@ -0,0 +1,10 @@
+/// The direction in which the keyboard focus should be moved through the focusable components.
+/// This is used by the `move_focus` method of `Component`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FocusDirection {
+    /// Move the focus to the next focusable component (typically when the user presses Tab)
+    Next,
+    /// Move the focus to the previous focusable component (typically when the user presses
+    /// Shift+Tab)
+    Previous,
+}
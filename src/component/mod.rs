@@ -2,10 +2,12 @@ use crate::*;
 
 mod buddy;
 mod dummy;
+mod focus;
 mod render;
 
 pub use buddy::*;
 pub use dummy::*;
+pub use focus::*;
 pub use render::*;
 
 /// The core trait of this crate. `Component`s are basically event handlers for
@@ -24,7 +26,13 @@ pub use render::*;
 pub trait Component {
     fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy);
 
-    fn on_resize(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+    /// This method will be called whenever the size (in pixels) of the area on which this component
+    /// is drawn changes, and right after the component is attached (if its size is already known).
+    ///
+    /// Unlike most event handlers, this method will always be called: there is no need to
+    /// subscribe for it. Components that depend on their aspect ratio (to avoid distortion for
+    /// instance) can use this to recompute their layout, and call `request_render` if needed.
+    fn on_resize(&mut self, _event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {}
 
     /// Lets this component render itself, and returns some information about the rendering.
     ///
@@ -53,6 +61,11 @@ pub trait Component {
     /// basically requests to be rendered again as soon as possible). This
     /// method will also be called soon after the component is attached.
     ///
+    /// Animations should use the `request_animation_frame` method of `buddy`
+    /// instead. The `on_animation_frame` method will then be called right
+    /// before this method, with the timestamp of the frame that is about to be
+    /// rendered.
+    ///
     /// # The return value
     /// ## The drawn area
     /// For the sake of optimization, it is very interesting for the caller to
@@ -79,7 +92,9 @@ pub trait Component {
     ///
     /// ### Affected mouse events
     /// The following mouse events will be affected by `filter_mouse_actions`: `MouseClickEvent`,
-    /// `MouseClickOutEvent`, `MouseMoveEvent`, `MouseEnterEvent`, and `MouseLeaveEvent`.
+    /// `MouseClickOutEvent`, `MouseMoveEvent`, `MouseEnterEvent`, `MouseLeaveEvent`,
+    /// `MouseScrollEvent`, the gesture events, and the drag-and-drop events. Drag and pinch events
+    /// are filtered based on the point where they *started*.
     ///
     /// This will *not* affect the `get_local_mouses` method of this buddy.
     fn render(
@@ -117,10 +132,124 @@ pub trait Component {
         forgot("MouseLeave")
     }
 
-    fn on_char_type(&mut self, _event: &CharTypeEvent) {
+    fn on_mouse_scroll(&mut self, _event: MouseScrollEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("MouseScroll")
+    }
+
+    fn on_double_click(&mut self, _event: DoubleClickEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DoubleClick")
+    }
+
+    fn on_long_press(&mut self, _event: LongPressEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("LongPress")
+    }
+
+    fn on_drag_start(&mut self, _event: DragStartEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragStart")
+    }
+
+    fn on_drag_update(&mut self, _event: DragUpdateEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragUpdate")
+    }
+
+    fn on_drag_end(&mut self, _event: DragEndEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragEnd")
+    }
+
+    fn on_pinch(&mut self, _event: PinchEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Pinch")
+    }
+
+    fn on_drag_enter(&mut self, _event: &DragEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragEnter")
+    }
+
+    fn on_drag_over(&mut self, _event: &DragOverEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragOver")
+    }
+
+    fn on_drag_leave(&mut self, _event: &DragLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("DragLeave")
+    }
+
+    fn on_drop(&mut self, _event: &DropEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Drop")
+    }
+
+    fn on_key_press(&mut self, _event: KeyPressEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("KeyPress")
+    }
+
+    fn on_key_release(&mut self, _event: KeyReleaseEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("KeyRelease")
+    }
+
+    fn on_char_type(&mut self, _event: &CharTypeEvent, _buddy: &mut dyn ComponentBuddy) {
         forgot("CharType")
     }
 
+    /// Checks whether this component can receive the keyboard focus when the user moves the focus
+    /// with Tab or Shift+Tab, or presses a mouse button inside the domain of this component.
+    ///
+    /// Components that are subscribed for the `CharTypeEvent` can always gain the focus by
+    /// pressing on them, even if this method returns false.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Asks this component to move the keyboard focus to the next or previous focusable component
+    /// *inside* this component. This is only relevant for menu components: other components can
+    /// simply use the default implementation, which returns false.
+    ///
+    /// If this component doesn't have the focus, it should move the focus to its first (or last,
+    /// when the `direction` is `Previous`) focusable child. Otherwise, it should move the focus
+    /// from its currently focused child to the next (or previous) one.
+    ///
+    /// This method should return true if the focus was moved to one of its children, and false if
+    /// it has no (more) children to move the focus to. In the latter case, the parent will move
+    /// the focus to the next (or previous) component in its own traversal order.
+    fn move_focus(&mut self, _direction: FocusDirection, _buddy: &mut dyn ComponentBuddy) -> bool {
+        false
+    }
+
+    /// This method will be called when this component gains the keyboard focus. This happens when
+    /// the component requested the focus via its buddy, when the user moved the focus to it with
+    /// Tab, or when the user pressed a mouse button inside the domain of the component while it
+    /// was focusable or subscribed for the `CharTypeEvent`.
+    fn on_focus_gained(&mut self, _buddy: &mut dyn ComponentBuddy) {
+        // Components don't need to subscribe for focus changes, so no need for a panic
+    }
+
+    /// This method will be called when this component loses the keyboard focus. This happens when
+    /// the component released the focus via its buddy, or when another component gained the focus.
+    fn on_focus_lost(&mut self, _buddy: &mut dyn ComponentBuddy) {
+        // Components don't need to subscribe for focus changes, so no need for a panic
+    }
+
+    fn on_text_input(&mut self, _event: &TextInputEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("TextInput")
+    }
+
+    /// This method will be called when a timer that was scheduled with the `schedule_timer` method
+    /// of the buddy of this component expires. Components don't need to subscribe for this event,
+    /// but components that schedule timers must implement this method.
+    fn on_timer(&mut self, _event: TimerEvent, _buddy: &mut dyn ComponentBuddy) {
+        forgot("Timer")
+    }
+
+    /// This method will be called right before the next frame is rendered, after this component
+    /// called the `request_animation_frame` method of its buddy. The event contains the timestamp
+    /// of that frame, which animations should use to compute their state (since the time between 2
+    /// frames can vary). Components don't need to subscribe for this event.
+    fn on_animation_frame(
+        &mut self,
+        _event: AnimationFrameEvent,
+        _buddy: &mut dyn ComponentBuddy,
+    ) {
+        // Components that only need to be rendered again won't need to implement
+        // this, so no need for a panic
+    }
+
     fn on_detach(&mut self) {
         // Components don't register for this event explicitly and many events
         // won't need to implement this, so no need for a panic
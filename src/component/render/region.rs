@@ -16,8 +16,8 @@
 /// components to create regions for its child components.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct RenderRegion {
-    min_x: u32,
-    min_y: u32,
+    min_x: i32,
+    min_y: i32,
     width: u32,
     height: u32,
 }
@@ -26,9 +26,12 @@ impl RenderRegion {
     /// Constructs a new `RenderRegion` with the given minimum x-coordinate,
     /// minimum y-coordinate, width, and height.
     ///
+    /// The minimum coordinates can be negative: this can happen when a menu component (for
+    /// instance a scroll container) renders a child that is partially outside of the window.
+    ///
     /// ## Panics
     /// This function will panic if `width == 0` or `height == 0`
-    pub fn with_size(min_x: u32, min_y: u32, width: u32, height: u32) -> Self {
+    pub fn with_size(min_x: i32, min_y: i32, width: u32, height: u32) -> Self {
         if width == 0 || height == 0 {
             panic!("width is {} and height is {}", width, height);
         }
@@ -49,7 +52,7 @@ impl RenderRegion {
     ///
     /// ## Panic
     /// This function will panic if `bound_x <= min_x` or `bound_y <= min_y`
-    pub fn between(min_x: u32, min_y: u32, bound_x: u32, bound_y: u32) -> Self {
+    pub fn between(min_x: i32, min_y: i32, bound_x: i32, bound_y: i32) -> Self {
         if bound_x <= min_x {
             panic!("Bound x is {}, but min x is {}", bound_x, min_x);
         }
@@ -59,47 +62,47 @@ impl RenderRegion {
         Self {
             min_x,
             min_y,
-            width: bound_x - min_x,
-            height: bound_y - min_y,
+            width: (bound_x - min_x) as u32,
+            height: (bound_y - min_y) as u32,
         }
     }
 
     /// Gets the minimum x-coordinate of this region. This is the x-coordinate of
     /// the left-most pixel(s) of this region.
-    pub fn get_min_x(&self) -> u32 {
+    pub fn get_min_x(&self) -> i32 {
         self.min_x
     }
 
     /// Gets the minimum y-coordinate of this region. This is the y-coordinate of
     /// the top (closest to the top of the screen) pixel(s) of this region.
-    pub fn get_min_y(&self) -> u32 {
+    pub fn get_min_y(&self) -> i32 {
         self.min_y
     }
 
     /// Gets the maximum x-coordinate of this region. This is the x-coordinate of
     /// the right-most pixel(s) that is within this region.
-    pub fn get_max_x(&self) -> u32 {
-        self.min_x + self.width - 1
+    pub fn get_max_x(&self) -> i32 {
+        self.min_x + self.width as i32 - 1
     }
 
     /// Gets the maximum y-coordinate of this region. This is the y-coordinate of
     /// the bottom (closest to the bottom of the screen) pixel(s) of this region.
-    pub fn get_max_y(&self) -> u32 {
-        self.min_y + self.height - 1
+    pub fn get_max_y(&self) -> i32 {
+        self.min_y + self.height as i32 - 1
     }
 
     /// Gets the bound x-coordinate of this region. This is the x-coordinate of
     /// the left-most pixel(s) that are on the right of this render region. This is
     /// always equal to 1 + the *maximum* x-coordinate.
-    pub fn get_bound_x(&self) -> u32 {
-        self.min_x + self.width
+    pub fn get_bound_x(&self) -> i32 {
+        self.min_x + self.width as i32
     }
 
     /// Gets the bound y-coordinate of this region. This is the y-coordinate of
     /// the top-most pixel(s) that are below this render region. This is always equal
     /// to 1 + the *maximum* y-coordinate.
-    pub fn get_bound_y(&self) -> u32 {
-        self.min_y + self.height
+    pub fn get_bound_y(&self) -> i32 {
+        self.min_y + self.height as i32
     }
 
     /// Gets the width of this region, in pixels
@@ -137,6 +140,12 @@ impl RenderRegion {
     ///     region.child_region(0.0, 0.0, 0.5, 0.5)
     /// );
     /// assert!(region.child_region(0.0, 0.0, 0.001, 0.001).is_none());
+    ///
+    /// // The relative coordinates can be outside the range [0, 1]
+    /// assert_eq!(
+    ///     Some(RenderRegion::between(10, 20, 30, 30)),
+    ///     region.child_region(-1.0, 0.0, 1.0, 1.0)
+    /// );
     /// ```
     pub fn child_region(
         &self,
@@ -145,11 +154,11 @@ impl RenderRegion {
         relative_max_x: f32,
         relative_max_y: f32,
     ) -> Option<Self> {
-        let min_x = self.get_min_x() + (self.get_width() as f32 * relative_min_x).round() as u32;
-        let min_y = self.get_min_y() + (self.get_height() as f32 * relative_min_y).round() as u32;
+        let min_x = self.get_min_x() + (self.get_width() as f32 * relative_min_x).round() as i32;
+        let min_y = self.get_min_y() + (self.get_height() as f32 * relative_min_y).round() as i32;
 
-        let bound_x = self.get_min_x() + (self.get_width() as f32 * relative_max_x).round() as u32;
-        let bound_y = self.get_min_y() + (self.get_height() as f32 * relative_max_y).round() as u32;
+        let bound_x = self.get_min_x() + (self.get_width() as f32 * relative_max_x).round() as i32;
+        let bound_y = self.get_min_y() + (self.get_height() as f32 * relative_max_y).round() as i32;
 
         if bound_x > min_x && bound_y > min_y {
             Some(Self::between(min_x, min_y, bound_x, bound_y))
@@ -191,25 +200,44 @@ impl RenderRegion {
         }
     }
 
-    /// Sets the viewport of the given golem `Context` to this render region.
+    /// Gets the part of this region that is not left of or below the origin of the window, or
+    /// `None` if the entire region is outside the window.
+    #[cfg(feature = "golem_rendering")]
+    fn visible_part(&self) -> Option<Self> {
+        self.intersection(Self::between(0, 0, i32::MAX, i32::MAX))
+    }
+
+    /// Sets the viewport of the given golem `Context` to this render region. Golem only accepts
+    /// unsigned coordinates, so only the part of this region that is inside the window will be
+    /// used.
     #[cfg(feature = "golem_rendering")]
     pub fn set_viewport(&self, golem: &golem::Context) {
-        golem.set_viewport(
-            self.get_min_x(),
-            self.get_min_y(),
-            self.get_width(),
-            self.get_height(),
-        );
+        match self.visible_part() {
+            // The coordinates of the visible part are never negative
+            Some(visible) => golem.set_viewport(
+                visible.get_min_x() as u32,
+                visible.get_min_y() as u32,
+                visible.get_width(),
+                visible.get_height(),
+            ),
+            None => golem.set_viewport(0, 0, 0, 0),
+        }
     }
 
+    /// Sets the scissor of the given golem `Context` to the part of this render region that is
+    /// inside the window.
     #[cfg(feature = "golem_rendering")]
     pub fn set_scissor(&self, golem: &golem::Context) {
-        golem.set_scissor(
-            self.get_min_x(),
-            self.get_min_y(),
-            self.get_width(),
-            self.get_height(),
-        );
+        match self.visible_part() {
+            // The coordinates of the visible part are never negative
+            Some(visible) => golem.set_scissor(
+                visible.get_min_x() as u32,
+                visible.get_min_y() as u32,
+                visible.get_width(),
+                visible.get_height(),
+            ),
+            None => golem.set_scissor(0, 0, 0, 0),
+        }
     }
 }
 
@@ -271,6 +299,17 @@ mod tests {
             mini_region.child_region(0.0, 0.0, 1.0, 1.0)
         );
         assert!(mini_region.child_region(0.1, 0.1, 0.4, 0.4).is_none());
+
+        // Child regions can extend beyond their parent, even to negative coordinates
+        let corner = RenderRegion::between(0, 0, 100, 50);
+        assert_eq!(
+            Some(RenderRegion::between(-50, -100, 100, 50)),
+            corner.child_region(-0.5, -2.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Some(RenderRegion::between(-20, 0, 180, 50)),
+            corner.child_region(-0.2, 0.0, 1.8, 1.0)
+        );
     }
 
     #[test]
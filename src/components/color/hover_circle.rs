@@ -1,10 +1,12 @@
 use crate::*;
 #[cfg(feature = "golem_rendering")]
 use golem::*;
+use std::time::Duration;
 
 /// A component that will draw a simple circle at its position. It has a `base_color` and a
 /// `hover_color`. It will fill the circle with the `hover_color` while a `Mouse` is hovering over
-/// it. If not, it will fill the circle with the `base_color`.
+/// it. If not, it will fill the circle with the `base_color`. The color fades smoothly when a
+/// mouse starts or stops hovering over it.
 ///
 /// This is clearly not a useful component in a real application, but it is a nice example because
 /// it demonstrates how to avoid distortion and how to use hover mechanics correctly.
@@ -13,13 +15,24 @@ pub struct HoverColorCircleComponent {
     base_color: Color,
     #[allow(dead_code)] // Only used when golem rendering is enabled
     hover_color: Color,
+
+    #[allow(dead_code)] // Only used when golem rendering is enabled
+    color: Animated<Color>,
+    #[allow(dead_code)] // Only used when golem rendering is enabled
+    was_hovering: bool,
 }
 
+/// The time it takes to fade between the base color and the hover color
+#[allow(dead_code)] // Only used when golem rendering is enabled
+const FADE_DURATION: Duration = Duration::from_millis(150);
+
 impl HoverColorCircleComponent {
     pub fn new(base_color: Color, hover_color: Color) -> Self {
         Self {
             base_color,
             hover_color,
+            color: Animated::new(base_color),
+            was_hovering: false,
         }
     }
 }
@@ -64,6 +77,11 @@ impl Component for HoverColorCircleComponent {
         buddy.subscribe_mouse_leave();
     }
 
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The drawn region depends on the aspect ratio, so it needs to be recomputed
+        buddy.request_render();
+    }
+
     fn render(
         &mut self,
         renderer: &Renderer,
@@ -96,10 +114,15 @@ impl Component for HoverColorCircleComponent {
                 }
             });
 
-            let color = match is_hovering {
-                true => self.hover_color,
-                false => self.base_color,
-            };
+            if is_hovering != self.was_hovering {
+                let target_color = match is_hovering {
+                    true => self.hover_color,
+                    false => self.base_color,
+                };
+                self.color.animate_to(target_color, FADE_DURATION, Easing::QuadOut, buddy);
+                self.was_hovering = is_hovering;
+            }
+            let color = self.color.get(buddy);
 
             let shader_id = ShaderId::from_strs("knukki", "Simple.HoverColorCircle");
             renderer.use_cached_shader(&shader_id, create_shader, |shader| {
@@ -1,11 +1,16 @@
 use crate::*;
-use std::cell::RefCell;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::time::Duration;
 
 pub struct SimpleFlatBuddy {
     subscriptions: ComponentSubscriptions,
 
     mouse_buddy: Rc<RefCell<MouseBuddy>>,
+    keyboard_buddy: Rc<RefCell<KeyboardBuddy>>,
+    // The current time of the menu, which is shared by all its buddies
+    clock: Rc<Cell<Duration>>,
     domain: ComponentDomain,
 
     last_render_result: Option<RenderResultStruct>,
@@ -13,15 +18,37 @@ pub struct SimpleFlatBuddy {
     create_next_menu: Option<Box<dyn FnOnce(Box<dyn Component>) -> Box<dyn Component>>>,
 
     requested_render: bool,
+    requested_animation_frame: bool,
     has_changes: bool,
+
+    has_focus: bool,
+    // Some(true) if the component requested the focus, Some(false) if it released the focus
+    focus_request: Option<bool>,
+
+    text_input_request: Option<String>,
+
+    drag_and_drop_request: Option<(Mouse, Rc<dyn Any>)>,
+
+    navigation_request: Option<NavigationRequest>,
+
+    timer_requests: Vec<(Duration, u32)>,
+
+    mouse_capture_requests: Vec<(Mouse, bool)>,
 }
 
 impl SimpleFlatBuddy {
-    pub(super) fn new(domain: ComponentDomain, mouse_buddy: Rc<RefCell<MouseBuddy>>) -> Self {
+    pub(super) fn new(
+        domain: ComponentDomain,
+        mouse_buddy: Rc<RefCell<MouseBuddy>>,
+        keyboard_buddy: Rc<RefCell<KeyboardBuddy>>,
+        clock: Rc<Cell<Duration>>,
+    ) -> Self {
         Self {
             subscriptions: ComponentSubscriptions::new(),
 
             mouse_buddy,
+            keyboard_buddy,
+            clock,
             domain,
 
             last_render_result: None,
@@ -29,8 +56,22 @@ impl SimpleFlatBuddy {
 
             // Components should always render right after they are attached
             requested_render: true,
+            requested_animation_frame: false,
             // This one is initially true to indicate the requested_render
             has_changes: true,
+
+            has_focus: false,
+            focus_request: None,
+
+            text_input_request: None,
+
+            drag_and_drop_request: None,
+
+            navigation_request: None,
+
+            timer_requests: Vec::new(),
+
+            mouse_capture_requests: Vec::new(),
         }
     }
 
@@ -46,6 +87,15 @@ impl SimpleFlatBuddy {
         self.requested_render = false;
     }
 
+    pub fn did_request_animation_frame(&self) -> bool {
+        self.requested_animation_frame
+    }
+
+    /// Checks whether the component requested an animation frame, and clears that request
+    pub fn take_animation_frame_request(&mut self) -> bool {
+        std::mem::replace(&mut self.requested_animation_frame, false)
+    }
+
     pub fn has_changes(&self) -> bool {
         self.has_changes
     }
@@ -62,6 +112,41 @@ impl SimpleFlatBuddy {
         self.last_render_result = Some(result);
     }
 
+    pub fn set_domain(&mut self, domain: ComponentDomain) {
+        self.domain = domain;
+    }
+
+    pub fn set_focus(&mut self, has_focus: bool) {
+        self.has_focus = has_focus;
+    }
+
+    pub fn take_focus_request(&mut self) -> Option<bool> {
+        self.focus_request.take()
+    }
+
+    pub fn take_text_input_request(&mut self) -> Option<String> {
+        self.text_input_request.take()
+    }
+
+    pub fn take_drag_and_drop_request(&mut self) -> Option<(Mouse, Rc<dyn Any>)> {
+        self.drag_and_drop_request.take()
+    }
+
+    pub fn take_navigation_request(&mut self) -> Option<NavigationRequest> {
+        self.navigation_request.take()
+    }
+
+    /// Takes the timers that were scheduled by the component, as pairs (delay, id)
+    pub fn take_timer_requests(&mut self) -> Vec<(Duration, u32)> {
+        std::mem::take(&mut self.timer_requests)
+    }
+
+    /// Takes the capture requests of the component, in the order in which they were made. The
+    /// `bool` is true for `capture_mouse` and false for `release_mouse_capture`.
+    pub fn take_mouse_capture_requests(&mut self) -> Vec<(Mouse, bool)> {
+        std::mem::take(&mut self.mouse_capture_requests)
+    }
+
     pub fn has_next_menu(&self) -> bool {
         self.create_next_menu.is_some()
     }
@@ -84,8 +169,23 @@ impl ComponentBuddy for SimpleFlatBuddy {
         self.has_changes = true;
     }
 
-    fn request_text_input(&self, _start_text: String) -> Option<String> {
-        todo!()
+    fn push_menu(&mut self, new_menu: Box<dyn Component>) {
+        self.navigation_request = Some(NavigationRequest::Push(new_menu));
+        self.has_changes = true;
+    }
+
+    fn pop_menu(&mut self) {
+        self.navigation_request = Some(NavigationRequest::Pop);
+        self.has_changes = true;
+    }
+
+    fn replace_menu(&mut self, new_menu: Box<dyn Component>) {
+        self.navigation_request = Some(NavigationRequest::Replace(new_menu));
+        self.has_changes = true;
+    }
+
+    fn request_text_input(&mut self, start_text: String) {
+        self.text_input_request = Some(start_text);
     }
 
     fn request_render(&mut self) {
@@ -95,6 +195,43 @@ impl ComponentBuddy for SimpleFlatBuddy {
         }
     }
 
+    fn request_animation_frame(&mut self) {
+        if !self.requested_animation_frame {
+            self.requested_animation_frame = true;
+            self.has_changes = true;
+        }
+        self.request_render();
+    }
+
+    fn schedule_timer(&mut self, delay: Duration, id: u32) {
+        self.timer_requests.push((delay, id));
+    }
+
+    fn request_focus(&mut self) {
+        self.focus_request = Some(true);
+        self.has_changes = true;
+    }
+
+    fn release_focus(&mut self) {
+        self.focus_request = Some(false);
+        self.has_changes = true;
+    }
+
+    fn start_drag_and_drop(&mut self, mouse: Mouse, payload: Rc<dyn Any>) {
+        self.drag_and_drop_request = Some((mouse, payload));
+        self.has_changes = true;
+    }
+
+    fn capture_mouse(&mut self, mouse: Mouse) {
+        self.mouse_capture_requests.push((mouse, true));
+        self.has_changes = true;
+    }
+
+    fn release_mouse_capture(&mut self, mouse: Mouse) {
+        self.mouse_capture_requests.push((mouse, false));
+        self.has_changes = true;
+    }
+
     fn subscribe_mouse_click(&mut self) {
         if !self.subscriptions.mouse_click {
             self.subscriptions.mouse_click = true;
@@ -193,12 +330,134 @@ impl ComponentBuddy for SimpleFlatBuddy {
         }
     }
 
-    fn subscribe_char_type(&self) -> Result<(), ()> {
-        todo!()
+    fn subscribe_mouse_scroll(&mut self) {
+        if !self.subscriptions.mouse_scroll {
+            self.subscriptions.mouse_scroll = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_mouse_scroll(&mut self) {
+        if self.subscriptions.mouse_scroll {
+            self.subscriptions.mouse_scroll = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_double_click(&mut self) {
+        if !self.subscriptions.double_click {
+            self.subscriptions.double_click = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_double_click(&mut self) {
+        if self.subscriptions.double_click {
+            self.subscriptions.double_click = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_long_press(&mut self) {
+        if !self.subscriptions.long_press {
+            self.subscriptions.long_press = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_long_press(&mut self) {
+        if self.subscriptions.long_press {
+            self.subscriptions.long_press = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_drag(&mut self) {
+        if !self.subscriptions.drag {
+            self.subscriptions.drag = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_drag(&mut self) {
+        if self.subscriptions.drag {
+            self.subscriptions.drag = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_pinch(&mut self) {
+        if !self.subscriptions.pinch {
+            self.subscriptions.pinch = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_pinch(&mut self) {
+        if self.subscriptions.pinch {
+            self.subscriptions.pinch = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_drag_and_drop(&mut self) {
+        if !self.subscriptions.drag_and_drop {
+            self.subscriptions.drag_and_drop = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_drag_and_drop(&mut self) {
+        if self.subscriptions.drag_and_drop {
+            self.subscriptions.drag_and_drop = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_char_type(&mut self) -> Result<(), NoKeyboardError> {
+        if !self.keyboard_buddy.borrow().keyboard_available {
+            return Err(NoKeyboardError);
+        }
+        if !self.subscriptions.char_type {
+            self.subscriptions.char_type = true;
+            self.has_changes = true;
+        }
+        Ok(())
+    }
+
+    fn unsubscribe_char_type(&mut self) {
+        if self.subscriptions.char_type {
+            self.subscriptions.char_type = false;
+            self.has_changes = true;
+        }
     }
 
-    fn unsubscribe_char_type(&self) {
-        todo!()
+    fn subscribe_key_press(&mut self) {
+        if !self.subscriptions.key_press {
+            self.subscriptions.key_press = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_key_press(&mut self) {
+        if self.subscriptions.key_press {
+            self.subscriptions.key_press = false;
+            self.has_changes = true;
+        }
+    }
+
+    fn subscribe_key_release(&mut self) {
+        if !self.subscriptions.key_release {
+            self.subscriptions.key_release = true;
+            self.has_changes = true;
+        }
+    }
+
+    fn unsubscribe_key_release(&mut self) {
+        if self.subscriptions.key_release {
+            self.subscriptions.key_release = false;
+            self.has_changes = true;
+        }
     }
 
     fn get_mouse_position(&self, mouse: Mouse) -> Option<Point> {
@@ -250,6 +509,18 @@ impl ComponentBuddy for SimpleFlatBuddy {
         let mouse_buddy = self.mouse_buddy.borrow();
         return mouse_buddy.all_mouses.clone();
     }
+
+    fn get_pressed_keys(&self) -> Vec<Key> {
+        self.keyboard_buddy.borrow().pressed_keys.clone()
+    }
+
+    fn get_current_time(&self) -> Duration {
+        self.clock.get()
+    }
+
+    fn has_focus(&self) -> bool {
+        self.has_focus
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -258,6 +529,12 @@ pub(super) struct MouseBuddy {
     pub local_mouses: Vec<MouseEntry>,
 }
 
+#[derive(Clone, Debug)]
+pub(super) struct KeyboardBuddy {
+    pub pressed_keys: Vec<Key>,
+    pub keyboard_available: bool,
+}
+
 #[derive(Clone, Debug)]
 pub(super) struct MouseEntry {
     pub mouse: Mouse,
@@ -1,6 +1,6 @@
 use crate::Point;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ComponentDomain {
     min_x: f32,
     min_y: f32,
@@ -58,6 +58,14 @@ impl ComponentDomain {
             && point.get_y() <= self.get_max_y()
     }
 
+    /// Checks whether this domain and the other domain have some overlap
+    pub fn intersects(&self, other: &ComponentDomain) -> bool {
+        self.get_min_x() < other.get_max_x()
+            && other.get_min_x() < self.get_max_x()
+            && self.get_min_y() < other.get_max_y()
+            && other.get_min_y() < self.get_max_y()
+    }
+
     pub fn transform(&self, outer: Point) -> Point {
         let inner_x = (outer.get_x() - self.get_min_x()) / self.get_width();
         let inner_y = (outer.get_y() - self.get_min_y()) / self.get_height();
@@ -116,6 +124,19 @@ mod tests {
         assert!(domain.is_inside(Point::new(2.0, 3.0)));
     }
 
+    #[test]
+    fn test_intersects() {
+        let domain = ComponentDomain::between(0.0, 0.0, 0.5, 0.5);
+        assert!(domain.intersects(&domain));
+        assert!(domain.intersects(&ComponentDomain::between(0.25, 0.25, 1.0, 1.0)));
+        assert!(domain.intersects(&ComponentDomain::between(0.1, 0.1, 0.2, 0.2)));
+        assert!(ComponentDomain::between(0.1, 0.1, 0.2, 0.2).intersects(&domain));
+
+        // Domains that only share an edge don't overlap
+        assert!(!domain.intersects(&ComponentDomain::between(0.5, 0.0, 1.0, 0.5)));
+        assert!(!domain.intersects(&ComponentDomain::between(0.0, 0.6, 0.5, 1.0)));
+    }
+
     #[test]
     fn test_transform() {
         // These numbers are carefully chosen to avoid rounding errors
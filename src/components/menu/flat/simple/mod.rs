@@ -1,7 +1,9 @@
 use crate::*;
 
-use std::cell::RefCell;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::time::Duration;
 
 mod buddy;
 mod domain;
@@ -15,10 +17,25 @@ type RR<T> = Rc<RefCell<T>>;
 pub struct SimpleFlatMenu {
     components: Vec<RR<ComponentEntry>>,
     components_to_add: Vec<ComponentToAdd>,
+    focused_component: Option<RR<ComponentEntry>>,
+    text_input_component: Option<RR<ComponentEntry>>,
+    drag_and_drop_target: Option<RR<ComponentEntry>>,
+    mouse_captures: Vec<(Mouse, RR<ComponentEntry>)>,
+    last_resize: Option<ResizeEvent>,
+    changed_domains: bool,
+    needs_full_render: bool,
+    focus_order: Option<Vec<usize>>,
     background_color: Option<Color>,
     has_rendered_before: bool,
+    layered: bool,
 
     mouse_buddy: RR<MouseBuddy>,
+    keyboard_buddy: RR<KeyboardBuddy>,
+    clock: Rc<Cell<Duration>>,
+
+    // The timers of the components, as triples (own timer id, component, timer id of component)
+    timers: Vec<(u32, RR<ComponentEntry>, u32)>,
+    next_timer_id: u32,
 }
 
 impl SimpleFlatMenu {
@@ -26,31 +43,154 @@ impl SimpleFlatMenu {
         Self {
             components: Vec::new(),
             components_to_add: Vec::new(),
+            focused_component: None,
+            text_input_component: None,
+            drag_and_drop_target: None,
+            mouse_captures: Vec::new(),
+            last_resize: None,
+            changed_domains: false,
+            needs_full_render: false,
+            focus_order: None,
             background_color,
             has_rendered_before: false,
+            layered: false,
 
             mouse_buddy: Rc::new(RefCell::new(MouseBuddy {
                 all_mouses: Vec::new(),
                 local_mouses: Vec::new(),
             })),
+            keyboard_buddy: Rc::new(RefCell::new(KeyboardBuddy {
+                pressed_keys: Vec::new(),
+                // This will be overwritten as soon as this menu is attached
+                keyboard_available: true,
+            })),
+            clock: Rc::new(Cell::new(Duration::from_secs(0))),
+
+            timers: Vec::new(),
+            next_timer_id: 0,
+        }
+    }
+
+    /// Constructs a menu whose components can overlap each other. See the docs of `LayeredMenu`
+    /// for the differences with a regular `SimpleFlatMenu`.
+    pub(crate) fn new_layered(background_color: Option<Color>) -> Self {
+        Self {
+            layered: true,
+            ..Self::new(background_color)
         }
     }
 
     pub fn add_component(&mut self, component: Box<dyn Component>, domain: ComponentDomain) {
-        self.components_to_add
-            .push(ComponentToAdd { component, domain });
+        self.add_layered_component(component, domain, 0);
+    }
+
+    /// Adds a component with the given `z_index`. Components with a larger z-index are drawn on
+    /// top of components with a smaller z-index. This is only useful for layered menus.
+    pub(crate) fn add_layered_component(
+        &mut self,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+        z_index: i32,
+    ) {
+        self.components_to_add.push(ComponentToAdd {
+            component,
+            domain,
+            z_index,
+        });
+    }
+
+    /// Moves the component at the given `index` to the given `domain`: the first component that
+    /// was added to this menu has index 0, the second one has index 1, etc...
+    ///
+    /// Since the component might leave some area empty, this will cause the entire menu to render
+    /// again. If the menu knows its size, the component will also receive a `ResizeEvent`.
+    ///
+    /// This method will panic if `index` is not smaller than the number of added components.
+    pub fn set_component_domain(&mut self, index: usize, domain: ComponentDomain) {
+        if index < self.components.len() {
+            let mut entry = self.components[index].borrow_mut();
+            if entry.domain == domain {
+                return;
+            }
+            entry.domain = domain;
+            entry.buddy.set_domain(domain);
+        } else {
+            self.components_to_add[index - self.components.len()].domain = domain;
+        }
+
+        self.changed_domains = true;
+        self.needs_full_render = true;
+    }
+
+    /// Changes the z-index of the component at the given `index`. This will cause the entire menu
+    /// to render again. This method will panic if `index` is not smaller than the number of added
+    /// components.
+    pub(crate) fn set_z_index(&mut self, index: usize, z_index: i32) {
+        if index < self.components.len() {
+            let mut entry = self.components[index].borrow_mut();
+            if entry.z_index == z_index {
+                return;
+            }
+            entry.z_index = z_index;
+        } else {
+            self.components_to_add[index - self.components.len()].z_index = z_index;
+        }
+
+        self.needs_full_render = true;
+    }
+
+    /// Overrides the order in which the keyboard focus moves through the components of this menu
+    /// when the user presses Tab (or Shift+Tab). Each element of `order` is the index of a
+    /// component: the first component that was added to this menu has index 0, the second one
+    /// has index 1, etc...
+    ///
+    /// Components whose index is not in `order` can't receive the focus via Tab. By default, the
+    /// focus moves through the components in the order they were added.
+    pub fn set_focus_order(&mut self, order: Vec<usize>) {
+        self.focus_order = Some(order);
+    }
+
+    fn get_focus_order(&self) -> Vec<RR<ComponentEntry>> {
+        match &self.focus_order {
+            // Indices of components that are not yet attached are simply skipped
+            Some(order) => order
+                .iter()
+                .filter_map(|index| self.components.get(*index))
+                .map(Rc::clone)
+                .collect(),
+            None => self.components.iter().map(Rc::clone).collect(),
+        }
+    }
+
+    /// Gets all components, sorted from bottom to top. Components with the same z-index are
+    /// sorted in the order in which they were added.
+    fn get_z_order(&self) -> Vec<RR<ComponentEntry>> {
+        let mut z_order: Vec<_> = self.components.iter().map(Rc::clone).collect();
+        z_order.sort_by_key(|entry_cell| entry_cell.borrow().z_index);
+        z_order
     }
 
     fn update_internal(&mut self, own_buddy: &mut dyn ComponentBuddy, is_about_to_render: bool) {
         while !self.components_to_add.is_empty() {
-            let to_add = self.components_to_add.swap_remove(0);
+            // Use remove rather than swap_remove to preserve the order of the components
+            let to_add = self.components_to_add.remove(0);
             let mut entry_to_add = ComponentEntry {
                 component: to_add.component,
                 domain: to_add.domain,
-                buddy: SimpleFlatBuddy::new(to_add.domain, Rc::clone(&self.mouse_buddy)),
+                z_index: to_add.z_index,
+                buddy: SimpleFlatBuddy::new(
+                    to_add.domain,
+                    Rc::clone(&self.mouse_buddy),
+                    Rc::clone(&self.keyboard_buddy),
+                    Rc::clone(&self.clock),
+                ),
+                last_size: None,
             };
 
             entry_to_add.attach();
+            if let Some(resize_event) = self.last_resize {
+                entry_to_add.resize(resize_event);
+            }
             self.check_buddy(own_buddy, &mut entry_to_add, is_about_to_render);
 
             // Don't forget this x)
@@ -82,6 +222,122 @@ impl SimpleFlatMenu {
             }
         }
         drop(mouse_buddy);
+
+        // And the keyboard buddy
+        self.keyboard_buddy.borrow_mut().pressed_keys = own_buddy.get_pressed_keys();
+
+        // And the clock
+        self.clock.set(own_buddy.get_current_time());
+
+        // The components whose domain changed need to know their new size
+        if self.changed_domains {
+            self.changed_domains = false;
+            if let Some(resize_event) = self.last_resize {
+                for entry_cell in &self.components {
+                    let mut entry = entry_cell.borrow_mut();
+                    entry.resize(resize_event);
+                    self.check_buddy(own_buddy, &mut entry, is_about_to_render);
+                }
+            }
+        }
+        if self.needs_full_render && !is_about_to_render {
+            own_buddy.request_render();
+        }
+
+        // The new components might have requested the focus
+        self.update_requests(own_buddy);
+    }
+
+    /// Forwards the timers that were scheduled by the child components to the own buddy, using
+    /// new ids that are unique within this menu.
+    fn update_timers(&mut self, own_buddy: &mut dyn ComponentBuddy) {
+        for entry_cell in &self.components {
+            let timer_requests = entry_cell.borrow_mut().buddy.take_timer_requests();
+            for (delay, child_id) in timer_requests {
+                let own_id = self.next_timer_id;
+                self.next_timer_id = self.next_timer_id.wrapping_add(1);
+                self.timers.push((own_id, Rc::clone(entry_cell), child_id));
+                own_buddy.schedule_timer(delay, own_id);
+            }
+        }
+    }
+
+    /// Handles the focus requests, text input requests and mouse capture requests of the child
+    /// components. Unlike the requests handled by `check_buddy`, these need to know *which*
+    /// component made the request.
+    fn update_requests(&mut self, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_timers(own_buddy);
+
+        // Iterate by index because set_focused_component needs a mutable reference to self
+        for index in 0..self.components.len() {
+            let entry_cell = Rc::clone(&self.components[index]);
+
+            let capture_requests = entry_cell.borrow_mut().buddy.take_mouse_capture_requests();
+            for (mouse, capture) in capture_requests {
+                if capture {
+                    self.capture_mouse(own_buddy, mouse, &entry_cell);
+                } else {
+                    self.release_mouse_capture(own_buddy, mouse, &entry_cell);
+                }
+            }
+
+            let text_input_request = entry_cell.borrow_mut().buddy.take_text_input_request();
+            if let Some(start_text) = text_input_request {
+                own_buddy.request_text_input(start_text);
+                self.text_input_component = Some(Rc::clone(&entry_cell));
+            }
+
+            let focus_request = entry_cell.borrow_mut().buddy.take_focus_request();
+            match focus_request {
+                Some(true) => self.set_focused_component(own_buddy, Some(entry_cell)),
+                Some(false) if self.is_focused(&entry_cell) => {
+                    self.set_focused_component(own_buddy, None)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn is_focused(&self, entry_cell: &RR<ComponentEntry>) -> bool {
+        match &self.focused_component {
+            Some(focused_cell) => Rc::ptr_eq(focused_cell, entry_cell),
+            None => false,
+        }
+    }
+
+    fn set_focused_component(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        new_focus: Option<RR<ComponentEntry>>,
+    ) {
+        if let Some(new_cell) = &new_focus {
+            if self.is_focused(new_cell) {
+                // The parent might have given the focus to another component in the meantime
+                own_buddy.request_focus();
+                return;
+            }
+        }
+
+        if let Some(old_cell) = self.focused_component.take() {
+            let mut old_entry = old_cell.borrow_mut();
+            old_entry.lose_focus();
+            self.check_buddy(own_buddy, &mut old_entry, false);
+        }
+
+        self.focused_component = new_focus;
+        match &self.focused_component {
+            Some(new_cell) => {
+                // The child can only have the focus if this menu has the focus. Requesting it
+                // again is harmless and overrides any pending release_focus of this menu.
+                own_buddy.request_focus();
+                if own_buddy.has_focus() {
+                    let mut new_entry = new_cell.borrow_mut();
+                    new_entry.gain_focus();
+                    self.check_buddy(own_buddy, &mut new_entry, false);
+                }
+            }
+            None => own_buddy.release_focus(),
+        }
     }
 
     fn check_buddy(
@@ -92,7 +348,11 @@ impl SimpleFlatMenu {
     ) {
         if entry.buddy.has_changes() {
             if !is_about_to_render && entry.buddy.did_request_render() {
-                own_buddy.request_render();
+                if entry.buddy.did_request_animation_frame() {
+                    own_buddy.request_animation_frame();
+                } else {
+                    own_buddy.request_render();
+                }
                 // Don't clear the render request until we have really rendered it
             }
 
@@ -100,11 +360,263 @@ impl SimpleFlatMenu {
                 own_buddy.change_menu(entry.buddy.create_next_menu());
             }
 
+            if let Some(request) = entry.buddy.take_navigation_request() {
+                request.forward_to(own_buddy);
+            }
+
+            if let Some((mouse, payload)) = entry.buddy.take_drag_and_drop_request() {
+                own_buddy.start_drag_and_drop(mouse, payload);
+            }
+
             entry.buddy.clear_changes();
         }
     }
 
+    fn on_gesture(&mut self, event: GestureEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        if let Some(hit_cell) = self.get_component_at(event.get_routing_point()) {
+            let mut hit_entry = hit_cell.borrow_mut();
+            hit_entry.gesture(event);
+            self.check_buddy(own_buddy, &mut hit_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn get_drag_and_drop_target_at(&self, point: Point) -> Option<RR<ComponentEntry>> {
+        self.get_component_at(point)
+            .filter(|entry_cell| entry_cell.borrow().accepts_drag_and_drop(point))
+    }
+
+    fn is_drag_and_drop_target(&self, entry_cell: &Option<RR<ComponentEntry>>) -> bool {
+        match (&self.drag_and_drop_target, entry_cell) {
+            (Some(target_cell), Some(entry_cell)) => Rc::ptr_eq(target_cell, entry_cell),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn leave_drag_and_drop_target(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        mouse: Mouse,
+        point: Point,
+        payload: &Rc<dyn Any>,
+    ) {
+        if let Some(target_cell) = self.drag_and_drop_target.take() {
+            let mut target_entry = target_cell.borrow_mut();
+            target_entry.drag_leave(&DragLeaveEvent::new(mouse, point, Rc::clone(payload)));
+            self.check_buddy(own_buddy, &mut target_entry, false);
+        }
+    }
+
+    fn get_mouse_capturer(&self, mouse: Mouse) -> Option<RR<ComponentEntry>> {
+        self.mouse_captures
+            .iter()
+            .find(|(captured_mouse, _)| *captured_mouse == mouse)
+            .map(|(_, entry_cell)| Rc::clone(entry_cell))
+    }
+
+    fn is_mouse_capturer(&self, mouse: Mouse, entry_cell: &RR<ComponentEntry>) -> bool {
+        match self.get_mouse_capturer(mouse) {
+            Some(capturer_cell) => Rc::ptr_eq(&capturer_cell, entry_cell),
+            None => false,
+        }
+    }
+
+    fn capture_mouse(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        mouse: Mouse,
+        entry_cell: &RR<ComponentEntry>,
+    ) {
+        if self.is_mouse_capturer(mouse, entry_cell) {
+            return;
+        }
+
+        // Only 1 component can capture the mouse at a time
+        if let Some(old_capturer) = self.get_mouse_capturer(mouse) {
+            self.remove_mouse_capture(own_buddy, mouse, &old_capturer);
+        }
+
+        self.mouse_captures.push((mouse, Rc::clone(entry_cell)));
+
+        // The parent needs to keep sending the events of the mouse to this menu
+        own_buddy.capture_mouse(mouse);
+    }
+
+    fn release_mouse_capture(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        mouse: Mouse,
+        entry_cell: &RR<ComponentEntry>,
+    ) {
+        if self.is_mouse_capturer(mouse, entry_cell) {
+            self.remove_mouse_capture(own_buddy, mouse, entry_cell);
+            own_buddy.release_mouse_capture(mouse);
+
+            // The other components didn't see the mouse enter while it was captured
+            if let Some(position) = own_buddy.get_mouse_position(mouse) {
+                if let Some(hit_cell) = self.get_component_at(position) {
+                    if !Rc::ptr_eq(&hit_cell, entry_cell) {
+                        let mut hit_entry = hit_cell.borrow_mut();
+                        hit_entry.mouse_enter(MouseEnterEvent::new(mouse, position));
+                        self.check_buddy(own_buddy, &mut hit_entry, false);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes the capture of `mouse` by the given component, without informing the parent
+    fn remove_mouse_capture(
+        &mut self,
+        own_buddy: &mut dyn ComponentBuddy,
+        mouse: Mouse,
+        entry_cell: &RR<ComponentEntry>,
+    ) {
+        self.mouse_captures
+            .retain(|(captured_mouse, _)| *captured_mouse != mouse);
+
+        // The component didn't receive a MouseLeaveEvent while the mouse was captured
+        if let Some(position) = own_buddy.get_mouse_position(mouse) {
+            let mut entry = entry_cell.borrow_mut();
+            entry.leave_if_outside(mouse, position);
+            self.check_buddy(own_buddy, &mut entry, false);
+        }
+    }
+
+    /// Handles a `MouseMoveEvent` in a layered menu. Only the top-most component at a point can
+    /// be *on* that point, so the components below it should not receive enter or move events.
+    /// The mouse enters and leaves components at the start and end points of the movements.
+    fn layered_mouse_move(&mut self, event: MouseMoveEvent, own_buddy: &mut dyn ComponentBuddy) {
+        let mouse = event.get_mouse();
+        let maybe_old_cell = self.get_component_at(event.get_from());
+        let maybe_new_cell = self.get_component_at(event.get_to());
+        match (&maybe_old_cell, &maybe_new_cell) {
+            (Some(old_cell), Some(new_cell)) if Rc::ptr_eq(old_cell, new_cell) => {
+                let mut entry = old_cell.borrow_mut();
+                entry.forced_mouse_move(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+            }
+            _ => {
+                if let Some(old_cell) = &maybe_old_cell {
+                    let mut old_entry = old_cell.borrow_mut();
+                    old_entry.forced_mouse_leave(MouseLeaveEvent::new(mouse, event.get_to()));
+                    self.check_buddy(own_buddy, &mut old_entry, false);
+                }
+                if let Some(new_cell) = &maybe_new_cell {
+                    let mut new_entry = new_cell.borrow_mut();
+                    new_entry.mouse_enter(MouseEnterEvent::new(mouse, event.get_to()));
+                    self.check_buddy(own_buddy, &mut new_entry, false);
+                }
+            }
+        }
+    }
+
+    /// Renders the given entry and (optionally) adds its drawn region to `drawn_regions`. If the
+    /// entry fails to render, its (bad) render result is returned as error.
+    fn render_entry(
+        &self,
+        renderer: &Renderer,
+        own_buddy: &mut dyn ComponentBuddy,
+        entry: &mut ComponentEntry,
+        force: bool,
+        report_drawn_region: bool,
+        drawn_regions: &mut Vec<Box<dyn DrawnRegion>>,
+    ) -> Result<(), RenderResult> {
+        let component_domain = entry.domain;
+
+        if let Some(entry_result) = entry.render(renderer, force) {
+            match entry_result {
+                Ok(good_entry_result) => {
+                    let transformed_region = TransformedDrawnRegion::new(
+                        good_entry_result.drawn_region.clone(),
+                        move |point| component_domain.transform(point),
+                        move |point| component_domain.transform_back(point),
+                    );
+                    if report_drawn_region {
+                        drawn_regions.push(Box::new(transformed_region));
+                    }
+                    self.check_buddy(own_buddy, entry, false);
+                }
+                Err(bad_result) => {
+                    return Err(Err(bad_result));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the components of a layered menu that requested a render. Since components can
+    /// overlap and be partially transparent, the area of each of these components is cleared and
+    /// all components in that area are drawn again (from bottom to top). A scissor is used to
+    /// ensure that the other parts of the components below (and above) are not affected.
+    fn render_layers(
+        &self,
+        renderer: &Renderer,
+        own_buddy: &mut dyn ComponentBuddy,
+        z_order: &[RR<ComponentEntry>],
+        drawn_regions: &mut Vec<Box<dyn DrawnRegion>>,
+    ) -> Result<(), RenderResult> {
+        let dirty_domains: Vec<ComponentDomain> = z_order
+            .iter()
+            .map(|entry_cell| entry_cell.borrow())
+            .filter(|entry| entry.buddy.did_request_render())
+            .map(|entry| entry.domain)
+            .collect();
+
+        for dirty_domain in dirty_domains {
+            let maybe_result = renderer.push_scissor(
+                dirty_domain.get_min_x(),
+                dirty_domain.get_min_y(),
+                dirty_domain.get_max_x(),
+                dirty_domain.get_max_y(),
+                || {
+                    if let Some(background_color) = self.background_color {
+                        renderer.clear(background_color);
+                        drawn_regions.push(Box::new(RectangularDrawnRegion::new(
+                            dirty_domain.get_min_x(),
+                            dirty_domain.get_min_y(),
+                            dirty_domain.get_max_x(),
+                            dirty_domain.get_max_y(),
+                        )));
+                    }
+                    for entry_cell in z_order {
+                        let mut entry = entry_cell.borrow_mut();
+                        if entry.domain.intersects(&dirty_domain) {
+                            self.render_entry(
+                                renderer,
+                                own_buddy,
+                                &mut entry,
+                                true,
+                                true,
+                                drawn_regions,
+                            )?;
+                        }
+                    }
+                    Ok(())
+                },
+            );
+            if let Some(Err(bad_result)) = maybe_result {
+                return Err(bad_result);
+            }
+        }
+        Ok(())
+    }
+
     fn get_component_at(&self, point: Point) -> Option<RR<ComponentEntry>> {
+        // In layered menus, the top-most component that actually drew something at the point wins
+        if self.layered {
+            for entry_cell in self.get_z_order().iter().rev() {
+                let entry = entry_cell.borrow();
+                if entry.domain.is_inside(point) && entry.is_drawn_at(point) {
+                    return Some(Rc::clone(entry_cell));
+                }
+            }
+            return None;
+        }
+
         // TODO PERFORMANCE Use some kind of 2d range tree instead
         for entry_cell in &self.components {
             let entry = entry_cell.borrow();
@@ -119,6 +631,11 @@ impl SimpleFlatMenu {
 
 impl Component for SimpleFlatMenu {
     fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        // This must happen before the components are attached because they might want to subscribe
+        // to the CharTypeEvent as well
+        let keyboard_available = buddy.subscribe_char_type().is_ok();
+        self.keyboard_buddy.borrow_mut().keyboard_available = keyboard_available;
+
         self.update_internal(buddy, false);
         buddy.subscribe_mouse_click();
         buddy.subscribe_mouse_click_out();
@@ -127,6 +644,26 @@ impl Component for SimpleFlatMenu {
         buddy.subscribe_mouse_move();
         buddy.subscribe_mouse_enter();
         buddy.subscribe_mouse_leave();
+        buddy.subscribe_mouse_scroll();
+        buddy.subscribe_double_click();
+        buddy.subscribe_long_press();
+        buddy.subscribe_drag();
+        buddy.subscribe_pinch();
+        buddy.subscribe_drag_and_drop();
+        buddy.subscribe_key_press();
+        buddy.subscribe_key_release();
+    }
+
+    fn on_resize(&mut self, event: ResizeEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.last_resize = Some(event);
+        self.update_internal(own_buddy, false);
+
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            entry.resize(event);
+            self.check_buddy(own_buddy, &mut entry, false);
+        }
+        self.update_requests(own_buddy);
     }
 
     // Variables only used when the golem_rendering feature is enabled are
@@ -141,6 +678,10 @@ impl Component for SimpleFlatMenu {
         // This needs to happen before each event
         self.update_internal(buddy, true);
 
+        // When the domain of a component changed, the area it left needs to be redrawn as well
+        let force = force || self.needs_full_render;
+        self.needs_full_render = false;
+
         // Now onto the 'actual' drawing
         if force || !self.has_rendered_before {
             if let Some(background_color) = self.background_color {
@@ -149,30 +690,33 @@ impl Component for SimpleFlatMenu {
             }
         }
         let mut drawn_regions: Vec<Box<dyn DrawnRegion>> = Vec::new();
-        for entry_cell in &self.components {
-            let mut entry = entry_cell.borrow_mut();
-            let component_domain = entry.domain;
-
-            if let Some(entry_result) = entry.render(renderer, force) {
-                match entry_result {
-                    Ok(good_entry_result) => {
-                        let transformed_region = TransformedDrawnRegion::new(
-                            good_entry_result.drawn_region.clone(),
-                            move |point| component_domain.transform(point),
-                            move |point| component_domain.transform_back(point),
-                        );
-                        if !force || self.background_color.is_none() {
-                            drawn_regions.push(Box::new(transformed_region));
-                        }
-                        self.check_buddy(buddy, &mut entry, false);
-                    }
-                    Err(bad_result) => {
-                        return Err(bad_result);
-                    }
+        let z_order = self.get_z_order();
+        if self.layered && !force && self.has_rendered_before {
+            if let Err(bad_result) =
+                self.render_layers(renderer, buddy, &z_order, &mut drawn_regions)
+            {
+                return bad_result;
+            }
+        } else {
+            let report_drawn_regions = !force || self.background_color.is_none();
+            for entry_cell in &z_order {
+                let mut entry = entry_cell.borrow_mut();
+                if let Err(bad_result) = self.render_entry(
+                    renderer,
+                    buddy,
+                    &mut entry,
+                    force,
+                    report_drawn_regions,
+                    &mut drawn_regions,
+                ) {
+                    return bad_result;
                 }
             }
         }
 
+        // Animations will typically schedule their timers during render
+        self.update_timers(buddy);
+
         if (force || !self.has_rendered_before) && self.background_color.is_some() {
             self.has_rendered_before = true;
             entire_render_result()
@@ -209,6 +753,7 @@ impl Component for SimpleFlatMenu {
                 self.check_buddy(own_buddy, &mut component_entry, false);
             }
         }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_click_out(
@@ -224,6 +769,7 @@ impl Component for SimpleFlatMenu {
             component_entry.mouse_click_out(event);
             self.check_buddy(own_buddy, &mut component_entry, false);
         }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_press(&mut self, event: MousePressEvent, own_buddy: &mut dyn ComponentBuddy) {
@@ -238,32 +784,64 @@ impl Component for SimpleFlatMenu {
             clicked_entry.mouse_press(event);
             self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
+
+        // Pressing inside the domain of another component should move the focus to that component
+        // if it is focusable or interested in text input, or remove the focus otherwise
+        let keep_focus = match &maybe_clicked_cell {
+            Some(clicked_cell) => self.is_focused(clicked_cell),
+            None => false,
+        };
+        if !keep_focus {
+            let new_focus = maybe_clicked_cell.filter(|clicked_cell| {
+                let clicked_entry = clicked_cell.borrow();
+                clicked_entry.component.is_focusable()
+                    || clicked_entry.buddy.get_subscriptions().char_type
+            });
+            if new_focus.is_some() || self.focused_component.is_some() {
+                self.set_focused_component(own_buddy, new_focus);
+            }
+        }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_release(&mut self, event: MouseReleaseEvent, own_buddy: &mut dyn ComponentBuddy) {
         // This should be done before every important action
         self.update_internal(own_buddy, false);
 
-        // Lets now handle the actual press event
-        let maybe_clicked_cell = self.get_component_at(event.get_point());
-
-        if let Some(clicked_cell) = &maybe_clicked_cell {
+        // The component that captured the mouse should be the only component that receives the
+        // event, wherever it is
+        if let Some(capturer_cell) = self.get_mouse_capturer(event.get_mouse()) {
+            let mut capturer_entry = capturer_cell.borrow_mut();
+            capturer_entry.captured_mouse_release(event);
+            self.check_buddy(own_buddy, &mut capturer_entry, false);
+        } else if let Some(clicked_cell) = self.get_component_at(event.get_point()) {
             let mut clicked_entry = clicked_cell.borrow_mut();
             clicked_entry.mouse_release(event);
             self.check_buddy(own_buddy, &mut clicked_entry, false);
         }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_move(&mut self, event: MouseMoveEvent, own_buddy: &mut dyn ComponentBuddy) {
         self.update_internal(own_buddy, false);
 
-        // TODO PERFORMANCE Consider only the components intersecting the rectangle around the line from
-        // event.from to event.to (using some kind of 2d range tree)
-        for entry_cell in &self.components {
-            let mut entry = entry_cell.borrow_mut();
-            entry.mouse_move(event);
-            self.check_buddy(own_buddy, &mut entry, false);
+        // The movements of a captured mouse should only be seen by the component that captured it
+        if let Some(capturer_cell) = self.get_mouse_capturer(event.get_mouse()) {
+            let mut capturer_entry = capturer_cell.borrow_mut();
+            capturer_entry.forced_mouse_move(event);
+            self.check_buddy(own_buddy, &mut capturer_entry, false);
+        } else if self.layered {
+            self.layered_mouse_move(event, own_buddy);
+        } else {
+            // TODO PERFORMANCE Consider only the components intersecting the rectangle around the line from
+            // event.from to event.to (using some kind of 2d range tree)
+            for entry_cell in &self.components {
+                let mut entry = entry_cell.borrow_mut();
+                entry.mouse_move(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+            }
         }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_enter(&mut self, event: MouseEnterEvent, own_buddy: &mut dyn ComponentBuddy) {
@@ -274,70 +852,435 @@ impl Component for SimpleFlatMenu {
             borrowed_entry.mouse_enter(event);
             self.check_buddy(own_buddy, &mut borrowed_entry, false);
         }
+        self.update_requests(own_buddy);
     }
 
     fn on_mouse_leave(&mut self, event: MouseLeaveEvent, own_buddy: &mut dyn ComponentBuddy) {
         self.update_internal(own_buddy, false);
 
+        // The capture of a mouse ends when it leaves
+        let maybe_capturer_cell = self.get_mouse_capturer(event.get_mouse());
+        self.mouse_captures
+            .retain(|(captured_mouse, _)| *captured_mouse != event.get_mouse());
+        if let Some(capturer_cell) = &maybe_capturer_cell {
+            let mut capturer_entry = capturer_cell.borrow_mut();
+            capturer_entry.forced_mouse_leave(event);
+            self.check_buddy(own_buddy, &mut capturer_entry, false);
+        }
+
         if let Some(hit_component_entry) = self.get_component_at(event.get_exit_point()) {
-            let mut borrowed_entry = hit_component_entry.borrow_mut();
-            borrowed_entry.mouse_leave(event);
-            self.check_buddy(own_buddy, &mut borrowed_entry, false);
+            let was_capturer = match &maybe_capturer_cell {
+                Some(capturer_cell) => Rc::ptr_eq(capturer_cell, &hit_component_entry),
+                None => false,
+            };
+            if !was_capturer {
+                let mut borrowed_entry = hit_component_entry.borrow_mut();
+                borrowed_entry.mouse_leave(event);
+                self.check_buddy(own_buddy, &mut borrowed_entry, false);
+            }
         }
+        self.update_requests(own_buddy);
     }
 
-    fn on_detach(&mut self) {
-        self.components.clear();
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        if let Some(scrolled_cell) = self.get_component_at(event.get_point()) {
+            let mut scrolled_entry = scrolled_cell.borrow_mut();
+            scrolled_entry.mouse_scroll(event);
+            self.check_buddy(own_buddy, &mut scrolled_entry, false);
+        }
+        self.update_requests(own_buddy);
     }
-}
 
-struct ComponentToAdd {
-    component: Box<dyn Component>,
-    domain: ComponentDomain,
-}
+    fn on_double_click(&mut self, event: DoubleClickEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::DoubleClick(event), own_buddy);
+    }
 
-struct ComponentEntry {
-    component: Box<dyn Component>,
-    domain: ComponentDomain,
-    buddy: SimpleFlatBuddy,
-}
+    fn on_long_press(&mut self, event: LongPressEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::LongPress(event), own_buddy);
+    }
 
-impl ComponentEntry {
-    fn attach(&mut self) {
-        self.component.on_attach(&mut self.buddy);
+    fn on_drag_start(&mut self, event: DragStartEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::DragStart(event), own_buddy);
     }
 
-    fn mouse_click(&mut self, outer_event: MouseClickEvent) {
-        let mut filtered = false;
-        if self.buddy.get_subscriptions().mouse_click {
-            let transformed_point = self.domain.transform(outer_event.get_point());
-            if let Some(render_result) = self.buddy.get_last_render_result() {
-                if !render_result.filter_mouse_actions
-                    || render_result.drawn_region.is_inside(transformed_point)
-                {
-                    let transformed_event = MouseClickEvent::new(
-                        outer_event.get_mouse(),
-                        transformed_point,
-                        outer_event.get_button(),
-                    );
+    fn on_drag_update(&mut self, event: DragUpdateEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::DragUpdate(event), own_buddy);
+    }
 
-                    self.component
-                        .on_mouse_click(transformed_event, &mut self.buddy);
-                } else {
-                    filtered = true;
-                }
+    fn on_drag_end(&mut self, event: DragEndEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::DragEnd(event), own_buddy);
+    }
+
+    fn on_pinch(&mut self, event: PinchEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.on_gesture(GestureEvent::Pinch(event), own_buddy);
+    }
+
+    fn on_drag_enter(&mut self, _event: &DragEnterEvent, own_buddy: &mut dyn ComponentBuddy) {
+        // The DragOverEvent that always follows this event will determine which child is entered
+        self.update_internal(own_buddy, false);
+        self.update_requests(own_buddy);
+    }
+
+    fn on_drag_over(&mut self, event: &DragOverEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let point = event.get_point();
+        let new_target = self.get_drag_and_drop_target_at(point);
+        if !self.is_drag_and_drop_target(&new_target) {
+            self.leave_drag_and_drop_target(
+                own_buddy,
+                event.get_mouse(),
+                point,
+                event.get_raw_payload(),
+            );
+            if let Some(new_cell) = &new_target {
+                let mut new_entry = new_cell.borrow_mut();
+                new_entry.drag_enter(&DragEnterEvent::new(
+                    event.get_mouse(),
+                    point,
+                    Rc::clone(event.get_raw_payload()),
+                ));
+                self.check_buddy(own_buddy, &mut new_entry, false);
             }
+            self.drag_and_drop_target = new_target;
         }
 
-        if filtered && self.buddy.get_subscriptions().mouse_click_out {
-            self.component.on_mouse_click_out(
-                MouseClickOutEvent::new(outer_event.get_mouse(), outer_event.get_button()),
-                &mut self.buddy,
-            );
+        if let Some(target_cell) = &self.drag_and_drop_target {
+            let mut target_entry = target_cell.borrow_mut();
+            target_entry.drag_over(event);
+            self.check_buddy(own_buddy, &mut target_entry, false);
         }
+        self.update_requests(own_buddy);
     }
 
-    fn mouse_click_out(&mut self, event: MouseClickOutEvent) {
+    fn on_drag_leave(&mut self, event: &DragLeaveEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+        self.leave_drag_and_drop_target(
+            own_buddy,
+            event.get_mouse(),
+            event.get_point(),
+            event.get_raw_payload(),
+        );
+        self.update_requests(own_buddy);
+    }
+
+    fn on_drop(&mut self, event: &DropEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let drop_target = self.get_drag_and_drop_target_at(event.get_point());
+        if !self.is_drag_and_drop_target(&drop_target) {
+            self.leave_drag_and_drop_target(
+                own_buddy,
+                event.get_mouse(),
+                event.get_point(),
+                event.get_raw_payload(),
+            );
+        }
+        // The drop target won't receive a DragLeaveEvent
+        self.drag_and_drop_target = None;
+
+        if let Some(target_cell) = drop_target {
+            let mut target_entry = target_cell.borrow_mut();
+            target_entry.drop_payload(event);
+            self.check_buddy(own_buddy, &mut target_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        // Tab is used for moving the focus, so it won't be passed to the child components when it
+        // moves the focus. Nested menus won't receive it either: the outermost menu moves the focus
+        // through them with move_focus. If this menu doesn't have any component that can receive
+        // the focus, Tab is passed to the child components like any other key.
+        if event.get_key() == Key::Tab {
+            let direction = match event.get_modifiers().is_shift_down() {
+                true => FocusDirection::Previous,
+                false => FocusDirection::Next,
+            };
+            // When the end of the focus order is reached, start again at the beginning
+            if self.move_focus(direction, own_buddy) || self.move_focus(direction, own_buddy) {
+                return;
+            }
+        }
+
+        // Key events don't have a position, so all interested components should receive them
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            entry.key_press(event);
+            self.check_buddy(own_buddy, &mut entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_key_release(&mut self, event: KeyReleaseEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            entry.key_release(event);
+            self.check_buddy(own_buddy, &mut entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        // Only the focused component should receive the typed text
+        if let Some(focused_cell) = &self.focused_component {
+            let mut focused_entry = focused_cell.borrow_mut();
+            focused_entry.char_type(event);
+            self.check_buddy(own_buddy, &mut focused_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn move_focus(
+        &mut self,
+        direction: FocusDirection,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) -> bool {
+        self.update_internal(own_buddy, false);
+
+        let order = self.get_focus_order();
+
+        // If this menu doesn't have the focus, the traversal should start at the beginning (or end)
+        let current_index = match (&self.focused_component, own_buddy.has_focus()) {
+            (Some(focused_cell), true) => order
+                .iter()
+                .position(|entry_cell| Rc::ptr_eq(entry_cell, focused_cell)),
+            _ => None,
+        };
+
+        // If the focused component is a menu, it should get the chance to move the focus to its
+        // next child first
+        if let Some(current_index) = current_index {
+            let mut current_entry = order[current_index].borrow_mut();
+            let moved = current_entry.move_focus(direction);
+            self.check_buddy(own_buddy, &mut current_entry, false);
+            drop(current_entry);
+            if moved {
+                self.update_requests(own_buddy);
+                return true;
+            }
+        }
+
+        let candidates: Vec<usize> = match (direction, current_index) {
+            (FocusDirection::Next, Some(current_index)) => {
+                (current_index + 1..order.len()).collect()
+            }
+            (FocusDirection::Next, None) => (0..order.len()).collect(),
+            (FocusDirection::Previous, Some(current_index)) => (0..current_index).rev().collect(),
+            (FocusDirection::Previous, None) => (0..order.len()).rev().collect(),
+        };
+
+        for candidate_index in candidates {
+            let candidate_cell = &order[candidate_index];
+            let mut candidate_entry = candidate_cell.borrow_mut();
+
+            // Nested menus accept the focus if they have a focusable child
+            let accepts_focus =
+                candidate_entry.component.is_focusable() || candidate_entry.move_focus(direction);
+            self.check_buddy(own_buddy, &mut candidate_entry, false);
+            drop(candidate_entry);
+
+            if accepts_focus {
+                self.set_focused_component(own_buddy, Some(Rc::clone(candidate_cell)));
+                self.update_requests(own_buddy);
+                return true;
+            }
+        }
+
+        // There is nothing left to focus in this menu, so the focus should leave it
+        self.set_focused_component(own_buddy, None);
+        self.update_requests(own_buddy);
+        false
+    }
+
+    fn on_focus_gained(&mut self, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        // Now that this menu has the focus, it can pass it on to the focused component
+        if let Some(focused_cell) = &self.focused_component {
+            let mut focused_entry = focused_cell.borrow_mut();
+            focused_entry.gain_focus();
+            self.check_buddy(own_buddy, &mut focused_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_focus_lost(&mut self, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        // The focused component will be kept, so that it gets the focus back once this menu
+        // regains the focus
+        if let Some(focused_cell) = &self.focused_component {
+            let mut focused_entry = focused_cell.borrow_mut();
+            focused_entry.lose_focus();
+            self.check_buddy(own_buddy, &mut focused_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_text_input(&mut self, event: &TextInputEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        if let Some(requesting_cell) = self.text_input_component.take() {
+            let mut requesting_entry = requesting_cell.borrow_mut();
+            requesting_entry.text_input(event);
+            self.check_buddy(own_buddy, &mut requesting_entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_timer(&mut self, event: TimerEvent, own_buddy: &mut dyn ComponentBuddy) {
+        self.update_internal(own_buddy, false);
+
+        let maybe_index = self
+            .timers
+            .iter()
+            .position(|(own_id, _, _)| *own_id == event.get_id());
+        if let Some(index) = maybe_index {
+            let (_, entry_cell, child_id) = self.timers.remove(index);
+            let mut entry = entry_cell.borrow_mut();
+            entry.timer(TimerEvent::new(child_id));
+            self.check_buddy(own_buddy, &mut entry, false);
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_animation_frame(
+        &mut self,
+        event: AnimationFrameEvent,
+        own_buddy: &mut dyn ComponentBuddy,
+    ) {
+        self.update_internal(own_buddy, false);
+
+        for entry_cell in &self.components {
+            let mut entry = entry_cell.borrow_mut();
+            if entry.buddy.take_animation_frame_request() {
+                entry.animation_frame(event);
+                self.check_buddy(own_buddy, &mut entry, false);
+            }
+        }
+        self.update_requests(own_buddy);
+    }
+
+    fn on_detach(&mut self) {
+        self.focused_component = None;
+        self.text_input_component = None;
+        self.drag_and_drop_target = None;
+        self.mouse_captures.clear();
+        self.timers.clear();
+
+        // Keep the components, so that they can be attached again when this menu is attached again
+        // (for instance when it is restored from the navigation stack)
+        let detached_components = self.components.drain(..).map(|entry_cell| {
+            let mut entry = entry_cell.borrow_mut();
+            let mut component =
+                std::mem::replace(&mut entry.component, Box::new(DummyComponent {}));
+            component.on_detach();
+            ComponentToAdd {
+                component,
+                domain: entry.domain,
+                z_index: entry.z_index,
+            }
+        });
+        let mut components_to_add: Vec<_> = detached_components.collect();
+        components_to_add.append(&mut self.components_to_add);
+        self.components_to_add = components_to_add;
+
+        // Everything needs to be drawn again after the next attach
+        self.has_rendered_before = false;
+        self.needs_full_render = true;
+    }
+}
+
+struct ComponentToAdd {
+    component: Box<dyn Component>,
+    domain: ComponentDomain,
+    z_index: i32,
+}
+
+struct ComponentEntry {
+    component: Box<dyn Component>,
+    domain: ComponentDomain,
+    z_index: i32,
+    buddy: SimpleFlatBuddy,
+    // The size that the component received in its latest ResizeEvent
+    last_size: Option<ResizeEvent>,
+}
+
+impl ComponentEntry {
+    fn attach(&mut self) {
+        self.component.on_attach(&mut self.buddy);
+    }
+
+    fn timer(&mut self, event: TimerEvent) {
+        self.component.on_timer(event, &mut self.buddy);
+    }
+
+    fn animation_frame(&mut self, event: AnimationFrameEvent) {
+        self.component.on_animation_frame(event, &mut self.buddy);
+    }
+
+    fn resize(&mut self, outer_event: ResizeEvent) {
+        // Use the same rounding as the renderer uses for the viewport of the component. This can
+        // be off by 1 pixel for nested menus because the renderer also rounds their offsets.
+        let outer_region =
+            RenderRegion::with_size(0, 0, outer_event.get_width(), outer_event.get_height());
+        let maybe_region = outer_region.child_region(
+            self.domain.get_min_x(),
+            self.domain.get_min_y(),
+            self.domain.get_max_x(),
+            self.domain.get_max_y(),
+        );
+
+        // Components without any pixels won't be rendered, so they don't need to know their size
+        if let Some(region) = maybe_region {
+            let resize_event = ResizeEvent::new(region.get_width(), region.get_height());
+            if self.last_size != Some(resize_event) {
+                self.last_size = Some(resize_event);
+                self.component.on_resize(resize_event, &mut self.buddy);
+            }
+        }
+    }
+
+    fn mouse_click(&mut self, outer_event: MouseClickEvent) {
+        let mut filtered = false;
+        if self.buddy.get_subscriptions().mouse_click {
+            let transformed_point = self.domain.transform(outer_event.get_point());
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                if !render_result.filter_mouse_actions
+                    || render_result.drawn_region.is_inside(transformed_point)
+                {
+                    let transformed_event = MouseClickEvent::new(
+                        outer_event.get_mouse(),
+                        transformed_point,
+                        outer_event.get_button(),
+                    );
+
+                    self.component
+                        .on_mouse_click(transformed_event, &mut self.buddy);
+                } else {
+                    filtered = true;
+                }
+            }
+        }
+
+        if filtered && self.buddy.get_subscriptions().mouse_click_out {
+            self.component.on_mouse_click_out(
+                MouseClickOutEvent::new(outer_event.get_mouse(), outer_event.get_button()),
+                &mut self.buddy,
+            );
+        }
+    }
+
+    fn mouse_click_out(&mut self, event: MouseClickOutEvent) {
         if self.buddy.get_subscriptions().mouse_click_out {
             if self.buddy.get_last_render_result().is_some() {
                 self.component.on_mouse_click_out(event, &mut self.buddy);
@@ -385,6 +1328,125 @@ impl ComponentEntry {
         }
     }
 
+    fn captured_mouse_release(&mut self, outer_event: MouseReleaseEvent) {
+        // The drawn region doesn't matter while the mouse is captured
+        if self.buddy.get_subscriptions().mouse_release {
+            let transformed_event = MouseReleaseEvent::new(
+                outer_event.get_mouse(),
+                self.domain.transform(outer_event.get_point()),
+                outer_event.get_button(),
+            );
+            self.component
+                .on_mouse_release(transformed_event, &mut self.buddy);
+        }
+    }
+
+    fn mouse_scroll(&mut self, outer_event: MouseScrollEvent) {
+        if self.buddy.get_subscriptions().mouse_scroll {
+            let transformed_point = self.domain.transform(outer_event.get_point());
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                if !render_result.filter_mouse_actions
+                    || render_result.drawn_region.is_inside(transformed_point)
+                {
+                    // The scroll deltas are expressed in lines, so they don't need to be transformed
+                    let transformed_event = MouseScrollEvent::new(
+                        outer_event.get_mouse(),
+                        transformed_point,
+                        outer_event.get_delta_x(),
+                        outer_event.get_delta_y(),
+                    );
+
+                    self.component
+                        .on_mouse_scroll(transformed_event, &mut self.buddy);
+                }
+            }
+        }
+    }
+
+    fn gesture(&mut self, outer_event: GestureEvent) {
+        if outer_event.is_subscribed(self.buddy.get_subscriptions()) {
+            let domain = self.domain;
+            let transformed_event = outer_event.transform(|point| domain.transform(point));
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                if !render_result.filter_mouse_actions
+                    || render_result
+                        .drawn_region
+                        .is_inside(transformed_event.get_routing_point())
+                {
+                    transformed_event.fire(self.component.as_mut(), &mut self.buddy);
+                }
+            }
+        }
+    }
+
+    fn accepts_drag_and_drop(&self, outer_point: Point) -> bool {
+        if !self.buddy.get_subscriptions().drag_and_drop {
+            return false;
+        }
+        match self.buddy.get_last_render_result() {
+            Some(render_result) => {
+                !render_result.filter_mouse_actions
+                    || render_result
+                        .drawn_region
+                        .is_inside(self.domain.transform(outer_point))
+            }
+            None => false,
+        }
+    }
+
+    fn drag_enter(&mut self, outer_event: &DragEnterEvent) {
+        let transformed_event = DragEnterEvent::new(
+            outer_event.get_mouse(),
+            self.domain.transform(outer_event.get_point()),
+            Rc::clone(outer_event.get_raw_payload()),
+        );
+        self.component
+            .on_drag_enter(&transformed_event, &mut self.buddy);
+    }
+
+    fn drag_over(&mut self, outer_event: &DragOverEvent) {
+        let transformed_event = DragOverEvent::new(
+            outer_event.get_mouse(),
+            self.domain.transform(outer_event.get_point()),
+            Rc::clone(outer_event.get_raw_payload()),
+        );
+        self.component
+            .on_drag_over(&transformed_event, &mut self.buddy);
+    }
+
+    fn drag_leave(&mut self, outer_event: &DragLeaveEvent) {
+        // The child might have unsubscribed in the meantime
+        if self.buddy.get_subscriptions().drag_and_drop {
+            let transformed_event = DragLeaveEvent::new(
+                outer_event.get_mouse(),
+                self.domain.transform(outer_event.get_point()),
+                Rc::clone(outer_event.get_raw_payload()),
+            );
+            self.component
+                .on_drag_leave(&transformed_event, &mut self.buddy);
+        }
+    }
+
+    fn drop_payload(&mut self, outer_event: &DropEvent) {
+        let transformed_event = DropEvent::new(
+            outer_event.get_mouse(),
+            self.domain.transform(outer_event.get_point()),
+            Rc::clone(outer_event.get_raw_payload()),
+        );
+        self.component.on_drop(&transformed_event, &mut self.buddy);
+    }
+
+    /// Checks whether the component drew something at the given point (in the coordinates of the
+    /// menu) during its last render
+    fn is_drawn_at(&self, outer_point: Point) -> bool {
+        match self.buddy.get_last_render_result() {
+            Some(render_result) => render_result
+                .drawn_region
+                .is_inside(self.domain.transform(outer_point)),
+            None => false,
+        }
+    }
+
     fn mouse_enter(&mut self, event: MouseEnterEvent) {
         if self.buddy.get_subscriptions().mouse_enter {
             if let Some(render_result) = self.buddy.get_last_render_result() {
@@ -419,6 +1481,51 @@ impl ComponentEntry {
         }
     }
 
+    /// Passes the `MouseMoveEvent` to the component, regardless of its domain and drawn region.
+    /// This is used for captured mouses (which should receive all their movements and no enter
+    /// or leave events) and for the top-most component in layered menus.
+    fn forced_mouse_move(&mut self, event: MouseMoveEvent) {
+        if self.buddy.get_subscriptions().mouse_move {
+            let transformed_event = MouseMoveEvent::new(
+                event.get_mouse(),
+                self.domain.transform(event.get_from()),
+                self.domain.transform(event.get_to()),
+            );
+            self.component
+                .on_mouse_move(transformed_event, &mut self.buddy);
+        }
+    }
+
+    fn forced_mouse_leave(&mut self, event: MouseLeaveEvent) {
+        if self.buddy.get_subscriptions().mouse_leave {
+            let transformed_event = MouseLeaveEvent::new(
+                event.get_mouse(),
+                self.domain.transform(event.get_exit_point()),
+            );
+            self.component
+                .on_mouse_leave(transformed_event, &mut self.buddy);
+        }
+    }
+
+    /// Fires a `MouseLeaveEvent` at the component if `outer_point` is outside its drawn region (or
+    /// its domain if it doesn't filter mouse actions). This is needed when the component releases
+    /// its mouse capture, because it didn't receive any leave events while it had the capture.
+    fn leave_if_outside(&mut self, mouse: Mouse, outer_point: Point) {
+        if self.buddy.get_subscriptions().mouse_leave {
+            if let Some(render_result) = self.buddy.get_last_render_result() {
+                let transformed_point = self.domain.transform(outer_point);
+                let is_inside = match render_result.filter_mouse_actions {
+                    true => render_result.drawn_region.is_inside(transformed_point),
+                    false => self.domain.is_inside(outer_point),
+                };
+                if !is_inside {
+                    let leave_event = MouseLeaveEvent::new(mouse, transformed_point);
+                    self.component.on_mouse_leave(leave_event, &mut self.buddy);
+                }
+            }
+        }
+    }
+
     fn mouse_move(&mut self, event: MouseMoveEvent) {
         let sub_enter = self.buddy.get_subscriptions().mouse_enter;
         let sub_move = self.buddy.get_subscriptions().mouse_move;
@@ -500,6 +1607,46 @@ impl ComponentEntry {
         }
     }
 
+    fn key_press(&mut self, event: KeyPressEvent) {
+        if self.buddy.get_subscriptions().key_press {
+            self.component.on_key_press(event, &mut self.buddy);
+        }
+    }
+
+    fn key_release(&mut self, event: KeyReleaseEvent) {
+        if self.buddy.get_subscriptions().key_release {
+            self.component.on_key_release(event, &mut self.buddy);
+        }
+    }
+
+    fn move_focus(&mut self, direction: FocusDirection) -> bool {
+        self.component.move_focus(direction, &mut self.buddy)
+    }
+
+    fn gain_focus(&mut self) {
+        if !self.buddy.has_focus() {
+            self.buddy.set_focus(true);
+            self.component.on_focus_gained(&mut self.buddy);
+        }
+    }
+
+    fn lose_focus(&mut self) {
+        if self.buddy.has_focus() {
+            self.buddy.set_focus(false);
+            self.component.on_focus_lost(&mut self.buddy);
+        }
+    }
+
+    fn text_input(&mut self, event: &TextInputEvent) {
+        self.component.on_text_input(event, &mut self.buddy);
+    }
+
+    fn char_type(&mut self, event: &CharTypeEvent) {
+        if self.buddy.get_subscriptions().char_type && self.buddy.has_focus() {
+            self.component.on_char_type(event, &mut self.buddy);
+        }
+    }
+
     fn render(&mut self, renderer: &Renderer, force: bool) -> Option<RenderResult> {
         if force || self.buddy.did_request_render() {
             self.buddy.clear_render_request();
@@ -542,6 +1689,7 @@ mod tests {
 
     use std::cell::*;
     use std::rc::Rc;
+    use std::time::Duration;
 
     fn root_buddy() -> RootComponentBuddy {
         let mut buddy = RootComponentBuddy::new();
@@ -551,6 +1699,7 @@ mod tests {
 
     fn init(buddy: &mut RootComponentBuddy) {
         buddy.set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        buddy.set_keyboard_store(Rc::new(RefCell::new(KeyboardStore::new())));
     }
 
     #[test]
@@ -1815,6 +2964,12 @@ mod tests {
                 buddy.subscribe_mouse_move();
                 buddy.subscribe_mouse_enter();
                 buddy.subscribe_mouse_leave();
+                buddy.subscribe_mouse_scroll();
+                buddy.subscribe_double_click();
+                buddy.subscribe_long_press();
+                buddy.subscribe_drag();
+                buddy.subscribe_pinch();
+                buddy.subscribe_drag_and_drop();
             }
 
             fn render(
@@ -1844,6 +2999,15 @@ mod tests {
         assert!(subs.mouse_move);
         assert!(subs.mouse_enter);
         assert!(subs.mouse_leave);
+        assert!(subs.mouse_scroll);
+        assert!(subs.double_click);
+        assert!(subs.long_press);
+        assert!(subs.drag);
+        assert!(subs.pinch);
+        assert!(subs.drag_and_drop);
+        assert!(subs.key_press);
+        assert!(subs.key_release);
+        assert!(subs.char_type);
     }
 
     #[test]
@@ -2712,4 +3876,1467 @@ mod tests {
         application.render(&renderer, true);
         check_counters(5);
     }
+
+    #[test]
+    fn test_mouse_scroll() {
+        struct ScrollComponent {
+            scroll_log: Rc<RefCell<Vec<MouseScrollEvent>>>,
+        }
+
+        impl Component for ScrollComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_scroll();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    filter_mouse_actions: true,
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
+                })
+            }
+
+            fn on_mouse_scroll(
+                &mut self,
+                event: MouseScrollEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.scroll_log.borrow_mut().push(event);
+            }
+        }
+
+        let scroll_log1 = Rc::new(RefCell::new(Vec::new()));
+        let scroll_log2 = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ScrollComponent {
+                scroll_log: Rc::clone(&scroll_log1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            Box::new(ScrollComponent {
+                scroll_log: Rc::clone(&scroll_log2),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 0.5),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+
+        // Only the component under the mouse should receive the event, with a transformed point
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            mouse,
+            Point::new(0.25, 0.2),
+            1.5,
+            -2.0,
+        ));
+        {
+            let log1 = take_log(&scroll_log1);
+            assert_eq!(1, log1.len());
+            assert!(log1[0].get_point().nearly_equal(Point::new(0.5, 0.4)));
+            assert_eq!(1.5, log1[0].get_delta_x());
+            assert_eq!(-2.0, log1[0].get_delta_y());
+        }
+        assert!(scroll_log2.borrow().is_empty());
+
+        // Scrolling outside the drawn region of the component should be filtered
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            mouse,
+            Point::new(0.55, 0.25),
+            0.0,
+            1.0,
+        ));
+        assert!(scroll_log1.borrow().is_empty());
+        assert!(scroll_log2.borrow().is_empty());
+
+        // Scrolling outside both domains shouldn't reach any component
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            mouse,
+            Point::new(0.5, 0.75),
+            0.0,
+            1.0,
+        ));
+        assert!(scroll_log1.borrow().is_empty());
+        assert!(scroll_log2.borrow().is_empty());
+
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            mouse,
+            Point::new(0.75, 0.25),
+            0.0,
+            1.0,
+        ));
+        assert!(scroll_log1.borrow().is_empty());
+        assert_eq!(1, take_log(&scroll_log2).len());
+    }
+
+    #[test]
+    fn test_gestures() {
+        struct GestureComponent {
+            log: Rc<RefCell<Vec<GestureEvent>>>,
+        }
+
+        impl Component for GestureComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_double_click();
+                buddy.subscribe_drag();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_double_click(
+                &mut self,
+                event: DoubleClickEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(GestureEvent::DoubleClick(event));
+            }
+
+            fn on_drag_start(&mut self, event: DragStartEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(GestureEvent::DragStart(event));
+            }
+
+            fn on_drag_update(&mut self, event: DragUpdateEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(GestureEvent::DragUpdate(event));
+            }
+
+            fn on_drag_end(&mut self, event: DragEndEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(GestureEvent::DragEnd(event));
+            }
+        }
+
+        let log1 = Rc::new(RefCell::new(Vec::new()));
+        let log2 = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(GestureComponent {
+                log: Rc::clone(&log1),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(GestureComponent {
+                log: Rc::clone(&log2),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+        application.tick(Duration::from_secs(10));
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let left = Point::new(0.25, 0.5);
+        let right = Point::new(0.75, 0.5);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, left));
+
+        // The drag should be fired at the left component, even after the mouse moved to the right
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, left, button));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, left, right));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, right, button));
+        let log = take_log(&log1);
+        assert_eq!(3, log.len());
+        if let GestureEvent::DragUpdate(update) = log[1] {
+            assert!(update.get_start_point().nearly_equal(Point::new(0.5, 0.5)));
+            assert!(update.get_to().nearly_equal(Point::new(1.5, 0.5)));
+        } else {
+            panic!("Expected a drag update, but got {:?}", log[1]);
+        }
+        if let GestureEvent::DragEnd(end) = log[2] {
+            assert!(end.get_end_point().nearly_equal(Point::new(1.5, 0.5)));
+        } else {
+            panic!("Expected a drag end, but got {:?}", log[2]);
+        }
+        assert!(log2.borrow().is_empty());
+
+        // Double clicks should only be fired at the clicked component
+        for _ in 0..2 {
+            application.fire_mouse_press_event(MousePressEvent::new(mouse, right, button));
+            application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, right, button));
+            application.fire_mouse_click_event(MouseClickEvent::new(mouse, right, button));
+        }
+        assert!(log1.borrow().is_empty());
+        let log = take_log(&log2);
+        assert_eq!(1, log.len());
+        if let GestureEvent::DoubleClick(double_click) = log[0] {
+            assert!(double_click.get_point().nearly_equal(Point::new(0.5, 0.5)));
+        } else {
+            panic!("Expected a double click, but got {:?}", log[0]);
+        }
+    }
+
+    #[test]
+    fn test_drag_and_drop() {
+        struct SourceComponent {}
+
+        impl Component for SourceComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.start_drag_and_drop(event.get_mouse(), Rc::new("item".to_string()));
+            }
+        }
+
+        struct TargetComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for TargetComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_drag_and_drop();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.2, 0.2, 0.8, 0.8)),
+                    filter_mouse_actions: true,
+                })
+            }
+
+            fn on_drag_enter(&mut self, event: &DragEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                assert!(event.get_point().nearly_equal(Point::new(0.5, 0.5)));
+                self.log.borrow_mut().push("enter".to_string());
+            }
+
+            fn on_drag_over(&mut self, _event: &DragOverEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("over".to_string());
+            }
+
+            fn on_drag_leave(&mut self, _event: &DragLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("leave".to_string());
+            }
+
+            fn on_drop(&mut self, event: &DropEvent, _buddy: &mut dyn ComponentBuddy) {
+                // The payload should only be available with the right type
+                assert!(event.get_payload::<u32>().is_none());
+                let payload = event.get_payload::<String>().unwrap();
+                self.log.borrow_mut().push(format!("drop {}", payload));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        // Put the target in a nested menu to test the propagation of the requests and events
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(TargetComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.5, 1.0, 1.0),
+        );
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(SourceComponent {}),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(inner_menu),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let source_point = Point::new(0.25, 0.5);
+        let target_point = Point::new(0.75, 0.75);
+        let filtered_point = Point::new(0.55, 0.55);
+        let move_mouse = |application: &mut Application, from: Point, to: Point| {
+            application.fire_mouse_move_event(MouseMoveEvent::new(mouse, from, to));
+        };
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, source_point));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, source_point, button));
+        assert!(take_log(&log).is_empty());
+
+        move_mouse(&mut application, source_point, target_point);
+        assert_eq!(vec!["enter", "over"], take_log(&log));
+
+        // Moving outside the drawn region of the target should be considered leaving
+        move_mouse(&mut application, target_point, filtered_point);
+        assert_eq!(vec!["leave"], take_log(&log));
+        move_mouse(&mut application, filtered_point, target_point);
+        assert_eq!(vec!["enter", "over"], take_log(&log));
+
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, target_point, button));
+        assert_eq!(vec!["drop item"], take_log(&log));
+
+        // The drag-and-drop operation should be finished
+        move_mouse(&mut application, target_point, filtered_point);
+        move_mouse(&mut application, filtered_point, target_point);
+        assert!(take_log(&log).is_empty());
+
+        // Dropping outside the target shouldn't reach the target
+        move_mouse(&mut application, target_point, source_point);
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, source_point, button));
+        move_mouse(&mut application, source_point, target_point);
+        move_mouse(&mut application, target_point, source_point);
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, source_point, button));
+        assert_eq!(vec!["enter", "over", "leave"], take_log(&log));
+
+        // When the mouse leaves the application, the operation should be cancelled
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, source_point, button));
+        move_mouse(&mut application, source_point, target_point);
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, target_point));
+        assert_eq!(vec!["enter", "over", "leave"], take_log(&log));
+    }
+
+    #[test]
+    fn test_mouse_capture() {
+        struct CaptureComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for CaptureComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_enter();
+                buddy.subscribe_mouse_leave();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                Ok(RenderResultStruct {
+                    drawn_region: Box::new(RectangularDrawnRegion::new(0.4, 0.4, 0.6, 0.6)),
+                    filter_mouse_actions: true,
+                })
+            }
+
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("press".to_string());
+                buddy.capture_mouse(event.get_mouse());
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                let point = event.get_point();
+                self.log
+                    .borrow_mut()
+                    .push(format!("release {} {}", point.get_x(), point.get_y()));
+                buddy.release_mouse_capture(event.get_mouse());
+            }
+
+            fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                let point = event.get_to();
+                self.log
+                    .borrow_mut()
+                    .push(format!("move {} {}", point.get_x(), point.get_y()));
+            }
+
+            fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("enter".to_string());
+            }
+
+            fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push("leave".to_string());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(CaptureComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(CaptureComponent {
+                log: Rc::new(RefCell::new(Vec::new())),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let inside = Point::new(0.25, 0.5);
+        let outside = Point::new(0.9, 0.9);
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, inside));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, inside, button));
+        assert_eq!(vec!["enter", "press"], take_log(&log));
+
+        // The events should be forwarded to the capturing component, even outside its domain
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, outside));
+        assert_eq!(vec!["move 1.8 0.9"], take_log(&log));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, outside, button));
+        assert_eq!(vec!["release 1.8 0.9", "leave"], take_log(&log));
+
+        // The capture is released, so the component should only receive its own events again
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            outside,
+            Point::new(0.8, 0.9),
+        ));
+        assert!(take_log(&log).is_empty());
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, Point::new(0.8, 0.9), inside));
+        assert_eq!(vec!["enter", "move 0.5 0.5"], take_log(&log));
+
+        // The capture should end when the mouse leaves the application
+        let far_outside = Point::new(0.75, 0.25);
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, inside, button));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, far_outside));
+        application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, far_outside));
+        assert_eq!(vec!["press", "move 1.5 0.25", "leave"], take_log(&log));
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, inside));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, inside, far_outside));
+        assert_eq!(vec!["enter", "move 0.6 0.475", "leave"], take_log(&log));
+    }
+
+    #[test]
+    fn test_exclusive_mouse_capture() {
+        struct DragComponent {
+            name: &'static str,
+            captures: bool,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for DragComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_press();
+                buddy.subscribe_mouse_release();
+                buddy.subscribe_mouse_move();
+                buddy.subscribe_mouse_enter();
+                buddy.subscribe_mouse_leave();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("{} press", self.name));
+                if self.captures {
+                    buddy.capture_mouse(event.get_mouse());
+                }
+            }
+
+            fn on_mouse_release(
+                &mut self,
+                event: MouseReleaseEvent,
+                buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(format!("{} release", self.name));
+                buddy.release_mouse_capture(event.get_mouse());
+            }
+
+            fn on_mouse_move(&mut self, _event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("{} move", self.name));
+            }
+
+            fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("{} enter", self.name));
+            }
+
+            fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!("{} leave", self.name));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(DragComponent {
+                name: "capturer",
+                captures: true,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(DragComponent {
+                name: "sibling",
+                captures: false,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let left = Point::new(0.25, 0.5);
+        let right = Point::new(0.75, 0.5);
+        let far_right = Point::new(0.9, 0.5);
+
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, left));
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, left, button));
+        assert_eq!(vec!["capturer enter", "capturer press"], take_log(&log));
+
+        // The sibling shouldn't notice the captured mouse, even when it is dragged across it
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, left, right));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, right, far_right));
+        assert_eq!(vec!["capturer move", "capturer move"], take_log(&log));
+
+        // Only the capturer should receive the release, after which the sibling can see the mouse
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, far_right, button));
+        assert_eq!(
+            vec!["capturer release", "capturer leave", "sibling enter"],
+            take_log(&log)
+        );
+
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, far_right, right));
+        assert_eq!(vec!["sibling move"], take_log(&log));
+    }
+
+    #[test]
+    fn test_resize() {
+        struct ResizeComponent {
+            log: Rc<RefCell<Vec<ResizeEvent>>>,
+        }
+
+        impl Component for ResizeComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn on_resize(&mut self, event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(event);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
+
+        let left_log = Rc::new(RefCell::new(Vec::new()));
+        let top_right_log = Rc::new(RefCell::new(Vec::new()));
+        let tiny_log = Rc::new(RefCell::new(Vec::new()));
+        let late_log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(ResizeComponent {
+                log: Rc::clone(&left_log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(ResizeComponent {
+                log: Rc::clone(&top_right_log),
+            }),
+            ComponentDomain::between(0.5, 0.75, 1.0, 1.0),
+        );
+        menu.add_component(
+            Box::new(ResizeComponent {
+                log: Rc::clone(&tiny_log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 0.501, 0.001),
+        );
+
+        let mut buddy = root_buddy();
+        menu.on_attach(&mut buddy);
+
+        // The components shouldn't receive anything before the menu knows its size
+        assert!(left_log.borrow().is_empty());
+
+        menu.on_resize(ResizeEvent::new(200, 100), &mut buddy);
+        assert_eq!(vec![ResizeEvent::new(100, 100)], take_log(&left_log));
+        assert_eq!(vec![ResizeEvent::new(100, 25)], take_log(&top_right_log));
+
+        // Components without pixels shouldn't receive resize events
+        assert!(take_log(&tiny_log).is_empty());
+
+        menu.on_resize(ResizeEvent::new(40, 400), &mut buddy);
+        assert_eq!(vec![ResizeEvent::new(20, 400)], take_log(&left_log));
+        assert_eq!(vec![ResizeEvent::new(20, 100)], take_log(&top_right_log));
+
+        // Components that are added later should receive their size right after being attached
+        menu.add_component(
+            Box::new(ResizeComponent {
+                log: Rc::clone(&late_log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 0.5),
+        );
+        menu.on_mouse_click(
+            MouseClickEvent::new(Mouse::new(0), Point::new(0.1, 0.1), MouseButton::primary()),
+            &mut buddy,
+        );
+        assert_eq!(vec![ResizeEvent::new(20, 200)], take_log(&late_log));
+        assert!(take_log(&left_log).is_empty());
+    }
+
+    #[test]
+    fn test_key_press_and_release() {
+        struct KeyComponent {
+            press_counter: Rc<Cell<u8>>,
+            release_counter: Rc<Cell<u8>>,
+            subscribe_release: bool,
+        }
+
+        impl Component for KeyComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_key_press();
+                if self.subscribe_release {
+                    buddy.subscribe_key_release();
+                }
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+                assert!(buddy.is_key_down(event.get_key()));
+                self.press_counter.set(self.press_counter.get() + 1);
+            }
+
+            fn on_key_release(&mut self, event: KeyReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+                assert!(!buddy.is_key_down(event.get_key()));
+                self.release_counter.set(self.release_counter.get() + 1);
+            }
+        }
+
+        let press_counter1 = Rc::new(Cell::new(0));
+        let release_counter1 = Rc::new(Cell::new(0));
+        let press_counter2 = Rc::new(Cell::new(0));
+        let release_counter2 = Rc::new(Cell::new(0));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(KeyComponent {
+                press_counter: Rc::clone(&press_counter1),
+                release_counter: Rc::clone(&release_counter1),
+                subscribe_release: true,
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(KeyComponent {
+                press_counter: Rc::clone(&press_counter2),
+                release_counter: Rc::clone(&release_counter2),
+                subscribe_release: false,
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+
+        // All interested components should receive the key events, regardless of their domain
+        let modifiers = KeyModifiers::none();
+        application.fire_key_press_event(KeyPressEvent::new(Key::Down, modifiers));
+        assert_eq!(1, press_counter1.get());
+        assert_eq!(1, press_counter2.get());
+
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Down, modifiers));
+        assert_eq!(1, release_counter1.get());
+        assert_eq!(0, release_counter2.get());
+    }
+
+    struct TypingComponent {
+        typed_text: Rc<RefCell<String>>,
+        subscribe_result: Rc<Cell<Option<Result<(), NoKeyboardError>>>>,
+    }
+
+    impl Component for TypingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            let subscribe_result = buddy.subscribe_char_type();
+            // Only the focused component will receive the typed text
+            if subscribe_result.is_ok() {
+                buddy.request_focus();
+            }
+            self.subscribe_result.set(Some(subscribe_result));
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_char_type(&mut self, event: &CharTypeEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.typed_text.borrow_mut().push_str(event.get_text());
+        }
+    }
+
+    #[test]
+    fn test_char_type() {
+        let typed_text = Rc::new(RefCell::new(String::new()));
+        let subscribe_result = Rc::new(Cell::new(None));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(TypingComponent {
+                typed_text: Rc::clone(&typed_text),
+                subscribe_result: Rc::clone(&subscribe_result),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+
+        // This component is not interested in char type events
+        struct IgnoringComponent {}
+
+        impl Component for IgnoringComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+        }
+        menu.add_component(
+            Box::new(IgnoringComponent {}),
+            ComponentDomain::between(0.5, 0.5, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        assert_eq!(Some(Ok(())), subscribe_result.get());
+
+        application.fire_char_type_event(CharTypeEvent::new("a".to_string()));
+        application.fire_char_type_event(CharTypeEvent::new("b".to_string()));
+        assert_eq!("ab", typed_text.borrow().as_str());
+    }
+
+    #[test]
+    fn test_char_type_without_keyboard() {
+        let typed_text = Rc::new(RefCell::new(String::new()));
+        let subscribe_result = Rc::new(Cell::new(None));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(TypingComponent {
+                typed_text: Rc::clone(&typed_text),
+                subscribe_result: Rc::clone(&subscribe_result),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+
+        let mut buddy = root_buddy();
+        let mut keyboard_store = KeyboardStore::new();
+        keyboard_store.set_keyboard_available(false);
+        buddy.set_keyboard_store(Rc::new(RefCell::new(keyboard_store)));
+
+        // Since there is no keyboard, the subscription of the component should fail
+        menu.on_attach(&mut buddy);
+        assert_eq!(Some(Err(NoKeyboardError)), subscribe_result.get());
+        assert!(!buddy.get_subscriptions().char_type);
+    }
+
+    struct FocusComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+        request_focus_on_attach: bool,
+    }
+
+    impl FocusComponent {
+        fn boxed(
+            name: &'static str,
+            log: &Rc<RefCell<Vec<String>>>,
+            request_focus_on_attach: bool,
+        ) -> Box<dyn Component> {
+            Box::new(Self {
+                name,
+                log: Rc::clone(log),
+                request_focus_on_attach,
+            })
+        }
+    }
+
+    impl Component for FocusComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_char_type().unwrap();
+            buddy.subscribe_mouse_click();
+            if self.request_focus_on_attach {
+                buddy.request_focus();
+            }
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            // Clicking while having the focus releases the focus, for the sake of testing
+            if buddy.has_focus() {
+                buddy.release_focus();
+            }
+        }
+
+        fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+            assert!(buddy.has_focus());
+            self.log
+                .borrow_mut()
+                .push(format!("{} typed {}", self.name, event.get_text()));
+        }
+
+        fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+            assert!(buddy.has_focus());
+            self.log.borrow_mut().push(format!("{} gained", self.name));
+        }
+
+        fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+            assert!(!buddy.has_focus());
+            self.log.borrow_mut().push(format!("{} lost", self.name));
+        }
+    }
+
+    fn take_log<T>(log: &Rc<RefCell<Vec<T>>>) -> Vec<T> {
+        log.replace(Vec::new())
+    }
+
+    #[test]
+    fn test_focus_by_pressing() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            FocusComponent::boxed("A", &log, false),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            FocusComponent::boxed("B", &log, false),
+            ComponentDomain::between(0.5, 0.0, 1.0, 0.5),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), true);
+
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+        let press = |application: &mut Application, x: f32, y: f32| {
+            let point = Point::new(x, y);
+            application.fire_mouse_move_event(MouseMoveEvent::new(mouse, point, point));
+            application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+        };
+        let type_char = |application: &mut Application, text: &str| {
+            application.fire_char_type_event(CharTypeEvent::new(text.to_string()));
+        };
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.9, 0.9)));
+
+        // Nobody has the focus yet, so nobody should receive the typed text
+        type_char(&mut application, "a");
+        assert!(take_log(&log).is_empty());
+
+        // Pressing on A should give it the focus
+        press(&mut application, 0.2, 0.2);
+        type_char(&mut application, "b");
+        assert_eq!(vec!["A gained", "A typed b"], take_log(&log));
+
+        // Pressing on A again shouldn't change anything
+        press(&mut application, 0.3, 0.2);
+        type_char(&mut application, "c");
+        assert_eq!(vec!["A typed c"], take_log(&log));
+
+        // Pressing on B should move the focus to B
+        press(&mut application, 0.7, 0.2);
+        type_char(&mut application, "d");
+        assert_eq!(vec!["A lost", "B gained", "B typed d"], take_log(&log));
+
+        // Pressing outside both components should remove the focus
+        press(&mut application, 0.5, 0.8);
+        type_char(&mut application, "e");
+        assert_eq!(vec!["B lost"], take_log(&log));
+    }
+
+    #[test]
+    fn test_focus_requests() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            FocusComponent::boxed("A", &log, false),
+            ComponentDomain::between(0.0, 0.0, 0.5, 0.5),
+        );
+        menu.add_component(
+            FocusComponent::boxed("B", &log, true),
+            ComponentDomain::between(0.5, 0.0, 1.0, 0.5),
+        );
+
+        // B should get the focus right after it is attached
+        let mut application = Application::new(Box::new(menu));
+        application.fire_char_type_event(CharTypeEvent::new("a".to_string()));
+        assert_eq!(vec!["B gained", "B typed a"], take_log(&log));
+
+        // When B releases the focus, nobody should have it
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), true);
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.7, 0.2),
+            MouseButton::primary(),
+        ));
+        application.fire_char_type_event(CharTypeEvent::new("b".to_string()));
+        assert_eq!(vec!["B lost"], take_log(&log));
+    }
+
+    #[test]
+    fn test_focus_in_nested_menus() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            FocusComponent::boxed("Inner", &log, false),
+            ComponentDomain::between(0.0, 0.0, 1.0, 0.5),
+        );
+
+        let mut outer_menu = SimpleFlatMenu::new(None);
+        outer_menu.add_component(
+            Box::new(inner_menu),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        outer_menu.add_component(
+            FocusComponent::boxed("Outer", &log, false),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(outer_menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), true);
+
+        let mouse = Mouse::new(0);
+        let press = |application: &mut Application, x: f32, y: f32| {
+            let point = Point::new(x, y);
+            application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+            application.fire_mouse_press_event(MousePressEvent::new(
+                mouse,
+                point,
+                MouseButton::primary(),
+            ));
+            application.fire_mouse_leave_event(MouseLeaveEvent::new(mouse, point));
+        };
+
+        // Pressing on the inner component should give it the focus
+        press(&mut application, 0.2, 0.2);
+        application.fire_char_type_event(CharTypeEvent::new("a".to_string()));
+        assert_eq!(vec!["Inner gained", "Inner typed a"], take_log(&log));
+
+        // Pressing on the outer component should move the focus out of the inner menu
+        press(&mut application, 0.7, 0.2);
+        application.fire_char_type_event(CharTypeEvent::new("b".to_string()));
+        assert_eq!(
+            vec!["Inner lost", "Outer gained", "Outer typed b"],
+            take_log(&log)
+        );
+
+        // And pressing on the inner component should move it back
+        press(&mut application, 0.3, 0.3);
+        application.fire_char_type_event(CharTypeEvent::new("c".to_string()));
+        assert_eq!(
+            vec!["Outer lost", "Inner gained", "Inner typed c"],
+            take_log(&log)
+        );
+
+        // Pressing inside the inner menu, but outside the inner component, should remove the focus
+        press(&mut application, 0.3, 0.8);
+        application.fire_char_type_event(CharTypeEvent::new("d".to_string()));
+        assert_eq!(vec!["Inner lost"], take_log(&log));
+    }
+
+    struct TabComponent {
+        name: &'static str,
+        focusable: bool,
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl TabComponent {
+        fn boxed(
+            name: &'static str,
+            focusable: bool,
+            log: &Rc<RefCell<Vec<&'static str>>>,
+        ) -> Box<dyn Component> {
+            Box::new(Self {
+                name,
+                focusable,
+                log: Rc::clone(log),
+            })
+        }
+    }
+
+    impl Component for TabComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_key_press();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_key_press(&mut self, event: KeyPressEvent, _buddy: &mut dyn ComponentBuddy) {
+            // The Tab key should be intercepted by the menu
+            assert_ne!(Key::Tab, event.get_key());
+        }
+
+        fn is_focusable(&self) -> bool {
+            self.focusable
+        }
+
+        fn on_focus_gained(&mut self, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(self.name);
+        }
+    }
+
+    fn press_tab(application: &mut Application, shift: bool) {
+        application.fire_key_press_event(KeyPressEvent::new(
+            Key::Tab,
+            KeyModifiers::new(shift, false, false, false),
+        ));
+        application.fire_key_release_event(KeyReleaseEvent::new(Key::Tab, KeyModifiers::none()));
+    }
+
+    fn press_tabs(application: &mut Application, amount: usize, shift: bool) {
+        for _counter in 0..amount {
+            press_tab(application, shift);
+        }
+    }
+
+    fn add_tab_components(
+        menu: &mut SimpleFlatMenu,
+        names: &[&'static str],
+        log: &Rc<RefCell<Vec<&'static str>>>,
+    ) {
+        for (index, name) in names.iter().enumerate() {
+            let min_x = index as f32 / names.len() as f32;
+            let max_x = (index + 1) as f32 / names.len() as f32;
+            menu.add_component(
+                TabComponent::boxed(name, *name != "-", log),
+                ComponentDomain::between(min_x, 0.0, max_x, 1.0),
+            );
+        }
+    }
+
+    #[test]
+    fn test_tab_traversal() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+
+        // The components named "-" are not focusable
+        add_tab_components(&mut menu, &["A", "-", "B", "C", "-"], &log);
+        let mut application = Application::new(Box::new(menu));
+
+        // The focus should move in insertion order and start again at the beginning
+        press_tabs(&mut application, 5, false);
+        assert_eq!(vec!["A", "B", "C", "A", "B"], take_log(&log));
+
+        // Shift+Tab should move in the opposite direction
+        press_tabs(&mut application, 3, true);
+        assert_eq!(vec!["A", "C", "B"], take_log(&log));
+    }
+
+    #[test]
+    fn test_tab_without_focusable_components() {
+        struct KeyComponent {
+            log: Rc<RefCell<Vec<Key>>>,
+        }
+
+        impl Component for KeyComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_key_press();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_key_press(&mut self, event: KeyPressEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(event.get_key());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(KeyComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(menu));
+
+        // Nothing can receive the focus, so Tab should be passed to the key press subscribers
+        press_tab(&mut application, false);
+        press_tab(&mut application, true);
+        assert_eq!(vec![Key::Tab, Key::Tab], take_log(&log));
+    }
+
+    #[test]
+    fn test_custom_focus_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        add_tab_components(&mut menu, &["A", "B", "C", "D"], &log);
+
+        // D is not part of the focus order, so it can't be reached with Tab
+        menu.set_focus_order(vec![2, 0, 1]);
+        let mut application = Application::new(Box::new(menu));
+
+        press_tabs(&mut application, 4, false);
+        assert_eq!(vec!["C", "A", "B", "C"], take_log(&log));
+
+        press_tabs(&mut application, 2, true);
+        assert_eq!(vec!["B", "A"], take_log(&log));
+    }
+
+    #[test]
+    fn test_tab_traversal_in_nested_menus() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        add_tab_components(&mut inner_menu, &["B", "-", "C"], &log);
+
+        // This menu doesn't have any focusable components, so it should be skipped
+        let mut empty_menu = SimpleFlatMenu::new(None);
+        add_tab_components(&mut empty_menu, &["-", "-"], &log);
+
+        let mut outer_menu = SimpleFlatMenu::new(None);
+        outer_menu.add_component(
+            TabComponent::boxed("A", true, &log),
+            ComponentDomain::between(0.0, 0.0, 0.25, 1.0),
+        );
+        outer_menu.add_component(
+            Box::new(inner_menu),
+            ComponentDomain::between(0.25, 0.0, 0.5, 1.0),
+        );
+        outer_menu.add_component(
+            Box::new(empty_menu),
+            ComponentDomain::between(0.5, 0.0, 0.75, 1.0),
+        );
+        outer_menu.add_component(
+            TabComponent::boxed("D", true, &log),
+            ComponentDomain::between(0.75, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(outer_menu));
+
+        // The focus should go through the inner menu and leave it to continue with D
+        press_tabs(&mut application, 6, false);
+        assert_eq!(vec!["A", "B", "C", "D", "A", "B"], take_log(&log));
+
+        // The same should work in the opposite direction
+        press_tabs(&mut application, 5, true);
+        assert_eq!(vec!["A", "D", "C", "B", "A"], take_log(&log));
+
+        // Pressing on C should give it the focus, and the next Tab should move the focus to D
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), true);
+        let point = Point::new(0.45, 0.5);
+        let mouse = Mouse::new(0);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            point,
+            MouseButton::primary(),
+        ));
+        press_tab(&mut application, false);
+        assert_eq!(vec!["C", "D"], take_log(&log));
+    }
+
+    #[test]
+    fn test_text_input() {
+        struct TextInputComponent {
+            name: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for TextInputComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                buddy.request_text_input(self.name.to_string());
+            }
+
+            fn on_text_input(&mut self, event: &TextInputEvent, _buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!(
+                    "{}: {}",
+                    self.name,
+                    event.get_text().unwrap_or("cancelled")
+                ));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut inner_menu = SimpleFlatMenu::new(None);
+        inner_menu.add_component(
+            Box::new(TextInputComponent {
+                name: "inner",
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+
+        let mut outer_menu = SimpleFlatMenu::new(None);
+        outer_menu.add_component(
+            Box::new(inner_menu),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        outer_menu.add_component(
+            Box::new(TextInputComponent {
+                name: "outer",
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(outer_menu));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), true);
+        let click = |application: &mut Application, x: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, 0.5),
+                MouseButton::primary(),
+            ));
+        };
+
+        // The answer should be routed to the nested component that requested it
+        click(&mut application, 0.2);
+        assert_eq!(
+            Some("inner".to_string()),
+            application.take_text_input_request()
+        );
+        application.fire_text_input_event(Some("abc".to_string()));
+        assert_eq!(vec!["inner: abc"], take_log(&log));
+
+        click(&mut application, 0.7);
+        assert_eq!(
+            Some("outer".to_string()),
+            application.take_text_input_request()
+        );
+        application.fire_text_input_event(None);
+        assert_eq!(vec!["outer: cancelled"], take_log(&log));
+
+        // The input is blocked while the outer component is waiting, so the inner component
+        // shouldn't be able to take over the request
+        click(&mut application, 0.7);
+        click(&mut application, 0.2);
+        assert_eq!(
+            Some("outer".to_string()),
+            application.take_text_input_request()
+        );
+        application.fire_text_input_event(Some("def".to_string()));
+        assert_eq!(vec!["outer: def"], take_log(&log));
+    }
+
+    #[test]
+    fn test_timers() {
+        struct TimerComponent {
+            name: &'static str,
+            delay: u64,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for TimerComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                // Both components use the same timer id
+                buddy.schedule_timer(Duration::from_millis(self.delay), 7);
+            }
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                entire_render_result()
+            }
+
+            fn on_timer(&mut self, event: TimerEvent, buddy: &mut dyn ComponentBuddy) {
+                self.log.borrow_mut().push(format!(
+                    "{}: timer {} at {}",
+                    self.name,
+                    event.get_id(),
+                    buddy.get_current_time().as_millis()
+                ));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(TimerComponent {
+                name: "slow",
+                delay: 200,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(TimerComponent {
+                name: "fast",
+                delay: 100,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        application.tick(Duration::from_millis(150));
+        assert_eq!(vec!["fast: timer 7 at 150"], take_log(&log));
+        application.tick(Duration::from_millis(250));
+        assert_eq!(vec!["slow: timer 7 at 250"], take_log(&log));
+    }
+
+    #[test]
+    fn test_animation_frames() {
+        struct AnimatingComponent {
+            name: &'static str,
+            frames_left: u32,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for AnimatingComponent {
+            fn on_attach(&mut self, _buddy: &mut dyn ComponentBuddy) {}
+
+            fn render(
+                &mut self,
+                _renderer: &Renderer,
+                buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                self.log.borrow_mut().push(format!("{}: render", self.name));
+                if self.frames_left > 0 {
+                    self.frames_left -= 1;
+                    buddy.request_animation_frame();
+                }
+                entire_render_result()
+            }
+
+            fn on_animation_frame(
+                &mut self,
+                event: AnimationFrameEvent,
+                _buddy: &mut dyn ComponentBuddy,
+            ) {
+                self.log.borrow_mut().push(format!(
+                    "{}: frame at {}",
+                    self.name,
+                    event.get_time().as_millis()
+                ));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = SimpleFlatMenu::new(None);
+        menu.add_component(
+            Box::new(AnimatingComponent {
+                name: "short",
+                frames_left: 1,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 0.5, 1.0),
+        );
+        menu.add_component(
+            Box::new(AnimatingComponent {
+                name: "long",
+                frames_left: 2,
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.5, 0.0, 1.0, 1.0),
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        let render_at = |application: &mut Application, millis: u64| {
+            application.tick(Duration::from_millis(millis));
+            application.render(&test_renderer(RenderRegion::between(0, 0, 10, 10)), false)
+        };
+
+        assert!(render_at(&mut application, 0));
+        assert_eq!(vec!["short: render", "long: render"], take_log(&log));
+
+        // Only the components that requested a frame should get its timestamp
+        assert!(render_at(&mut application, 16));
+        assert_eq!(
+            vec![
+                "short: frame at 16",
+                "long: frame at 16",
+                "short: render",
+                "long: render"
+            ],
+            take_log(&log)
+        );
+        assert!(render_at(&mut application, 40));
+        assert_eq!(vec!["long: frame at 40", "long: render"], take_log(&log));
+
+        // Both animations are finished
+        assert!(!render_at(&mut application, 50));
+        assert!(take_log(&log).is_empty());
+    }
 }
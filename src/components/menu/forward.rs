@@ -0,0 +1,228 @@
+/// Implements methods of `Component` by forwarding them to the field `$inner` (usually a
+/// `SimpleFlatMenu`) of a menu that wraps another component. This avoids the need to write all
+/// these methods by hand for each such menu.
+///
+/// The methods can be given by name (for instance `on_mouse_click`), or by group:
+/// - `mouse_events`: all `on_mouse_*` methods
+/// - `gesture_events`: `on_double_click`, `on_long_press`, `on_drag_*` and `on_pinch`
+/// - `drag_and_drop_events`: `on_drag_enter`, `on_drag_over`, `on_drag_leave` and `on_drop`
+/// - `keyboard_events`: `on_key_press`, `on_key_release`, `on_char_type` and `on_text_input`
+/// - `focus_events`: `is_focusable`, `move_focus`, `on_focus_gained` and `on_focus_lost`
+///
+/// When `then $after` is given, the method `$after(&mut self, buddy)` of the menu will be called
+/// after every forwarded method that has a buddy. The `render` method can only be forwarded
+/// without `then`.
+macro_rules! forward_component_methods {
+    ($inner:ident $(, then $after:ident)?; $($method:ident),+ $(,)?) => {
+        forward_component_methods!(@methods $inner, [$($after)?], $($method),+);
+    };
+    (@methods $inner:ident, $after:tt, $($method:ident),+) => {
+        $(forward_component_methods!(@method $inner, $after, $method);)+
+    };
+
+    (@method $inner:ident, [$($after:ident)?], mouse_events) => {
+        forward_component_methods!(
+            $inner $(, then $after)?;
+            on_mouse_click,
+            on_mouse_click_out,
+            on_mouse_press,
+            on_mouse_release,
+            on_mouse_move,
+            on_mouse_enter,
+            on_mouse_leave,
+            on_mouse_scroll
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], gesture_events) => {
+        forward_component_methods!(
+            $inner $(, then $after)?;
+            on_double_click,
+            on_long_press,
+            on_drag_start,
+            on_drag_update,
+            on_drag_end,
+            on_pinch
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], drag_and_drop_events) => {
+        forward_component_methods!(
+            $inner $(, then $after)?;
+            on_drag_enter,
+            on_drag_over,
+            on_drag_leave,
+            on_drop
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], keyboard_events) => {
+        forward_component_methods!(
+            $inner $(, then $after)?;
+            on_key_press,
+            on_key_release,
+            on_char_type,
+            on_text_input
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], focus_events) => {
+        forward_component_methods!(
+            $inner $(, then $after)?;
+            is_focusable,
+            move_focus,
+            on_focus_gained,
+            on_focus_lost
+        );
+    };
+
+    (@method $inner:ident, [$($after:ident)?], on_attach) => {
+        forward_component_methods!(@buddy $inner, [$($after)?], on_attach);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_resize) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_resize, ResizeEvent);
+    };
+    (@method $inner:ident, [], render) => {
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            buddy: &mut dyn ComponentBuddy,
+            force: bool,
+        ) -> RenderResult {
+            self.$inner.render(renderer, buddy, force)
+        }
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_click) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_mouse_click, MouseClickEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_click_out) => {
+        forward_component_methods!(
+            @event $inner, [$($after)?], on_mouse_click_out, MouseClickOutEvent
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_press) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_mouse_press, MousePressEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_release) => {
+        forward_component_methods!(
+            @event $inner, [$($after)?], on_mouse_release, MouseReleaseEvent
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_move) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_mouse_move, MouseMoveEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_enter) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_mouse_enter, MouseEnterEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_leave) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_mouse_leave, MouseLeaveEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_mouse_scroll) => {
+        forward_component_methods!(
+            @event $inner, [$($after)?], on_mouse_scroll, MouseScrollEvent
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], on_double_click) => {
+        forward_component_methods!(
+            @event $inner, [$($after)?], on_double_click, DoubleClickEvent
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], on_long_press) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_long_press, LongPressEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_start) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_drag_start, DragStartEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_update) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_drag_update, DragUpdateEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_end) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_drag_end, DragEndEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_pinch) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_pinch, PinchEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_enter) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_drag_enter, DragEnterEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_over) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_drag_over, DragOverEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drag_leave) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_drag_leave, DragLeaveEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_drop) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_drop, DropEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_key_press) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_key_press, KeyPressEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_key_release) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_key_release, KeyReleaseEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_char_type) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_char_type, CharTypeEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_text_input) => {
+        forward_component_methods!(@ref_event $inner, [$($after)?], on_text_input, TextInputEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_focus_gained) => {
+        forward_component_methods!(@buddy $inner, [$($after)?], on_focus_gained);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_focus_lost) => {
+        forward_component_methods!(@buddy $inner, [$($after)?], on_focus_lost);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_timer) => {
+        forward_component_methods!(@event $inner, [$($after)?], on_timer, TimerEvent);
+    };
+    (@method $inner:ident, [$($after:ident)?], on_animation_frame) => {
+        forward_component_methods!(
+            @event $inner, [$($after)?], on_animation_frame, AnimationFrameEvent
+        );
+    };
+    (@method $inner:ident, [$($after:ident)?], is_focusable) => {
+        fn is_focusable(&self) -> bool {
+            self.$inner.is_focusable()
+        }
+    };
+    (@method $inner:ident, [], move_focus) => {
+        fn move_focus(
+            &mut self,
+            direction: FocusDirection,
+            buddy: &mut dyn ComponentBuddy,
+        ) -> bool {
+            self.$inner.move_focus(direction, buddy)
+        }
+    };
+    (@method $inner:ident, [$after:ident], move_focus) => {
+        fn move_focus(
+            &mut self,
+            direction: FocusDirection,
+            buddy: &mut dyn ComponentBuddy,
+        ) -> bool {
+            let result = self.$inner.move_focus(direction, buddy);
+            self.$after(buddy);
+            result
+        }
+    };
+    (@method $inner:ident, [$($after:ident)?], on_detach) => {
+        fn on_detach(&mut self) {
+            self.$inner.on_detach();
+        }
+    };
+
+    (@event $inner:ident, [$($after:ident)?], $method:ident, $event:ty) => {
+        fn $method(&mut self, event: $event, buddy: &mut dyn ComponentBuddy) {
+            self.$inner.$method(event, buddy);
+            $(self.$after(buddy);)?
+        }
+    };
+    (@ref_event $inner:ident, [$($after:ident)?], $method:ident, $event:ty) => {
+        fn $method(&mut self, event: &$event, buddy: &mut dyn ComponentBuddy) {
+            self.$inner.$method(event, buddy);
+            $(self.$after(buddy);)?
+        }
+    };
+    (@buddy $inner:ident, [$($after:ident)?], $method:ident) => {
+        fn $method(&mut self, buddy: &mut dyn ComponentBuddy) {
+            self.$inner.$method(buddy);
+            $(self.$after(buddy);)?
+        }
+    };
+}
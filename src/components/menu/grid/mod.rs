@@ -0,0 +1,409 @@
+use crate::*;
+
+use super::LayoutCache;
+
+/// The size of a column or row of a `GridMenu`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GridTrackSize {
+    /// The column or row has a fixed size of this number of pixels
+    Pixels(u32),
+    /// The column or row takes a share of the space that is left after subtracting the gutters
+    /// and the columns or rows with a fixed size. The share is proportional to this weight: a
+    /// column with weight 2.0 will be twice as wide as a column with weight 1.0.
+    Weight(f32),
+}
+
+/// The cell (or block of cells) of a `GridMenu` in which a component is placed. The columns are
+/// numbered from left to right, and the rows are numbered from top to bottom (both starting at
+/// 0). The component will cover `column_span` columns and `row_span` rows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridCell {
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+}
+
+impl GridCell {
+    /// Constructs a `GridCell` that covers only the cell at the given `column` and `row`
+    pub fn single(column: usize, row: usize) -> Self {
+        Self {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+
+    /// Constructs a `GridCell` that covers `column_span` columns and `row_span` rows, starting
+    /// at the given `column` and `row`
+    pub fn spanning(column: usize, row: usize, column_span: usize, row_span: usize) -> Self {
+        Self {
+            column,
+            row,
+            column_span,
+            row_span,
+        }
+    }
+}
+
+/// A menu component that places its children in the cells of a grid. The widths of the columns
+/// and the heights of the rows can be fixed (in pixels) or proportional, and there can be empty
+/// space (gutters) between the columns and rows.
+///
+/// The domains of the children are computed from the size of this menu, and recomputed whenever
+/// this menu is resized. Internally, this menu uses a `SimpleFlatMenu` to propagate the events
+/// to its children, so the children behave exactly like they would in a `SimpleFlatMenu`.
+///
+/// The components are numbered in the order in which they were added, and these indices are
+/// used by `set_focus_order`.
+pub struct GridMenu {
+    flat_menu: SimpleFlatMenu,
+    columns: Vec<GridTrackSize>,
+    rows: Vec<GridTrackSize>,
+    column_gutter: LayoutSize,
+    row_gutter: LayoutSize,
+    cells: Vec<GridCell>,
+    layout_cache: LayoutCache,
+}
+
+impl GridMenu {
+    /// Constructs a new empty `GridMenu` with the given `columns` (from left to right) and `rows`
+    /// (from top to bottom). There won't be any gutters unless `set_gutters` is called.
+    pub fn new(
+        background_color: Option<Color>,
+        columns: Vec<GridTrackSize>,
+        rows: Vec<GridTrackSize>,
+    ) -> Self {
+        Self {
+            flat_menu: SimpleFlatMenu::new(background_color),
+            columns,
+            rows,
+            column_gutter: LayoutSize::Pixels(0),
+            row_gutter: LayoutSize::Pixels(0),
+            cells: Vec::new(),
+            layout_cache: LayoutCache::new(),
+        }
+    }
+
+    /// Sets the empty space between 2 consecutive columns and between 2 consecutive rows. The
+    /// fractional gutters are relative to the width (for columns) or the height (for rows) of
+    /// this menu.
+    pub fn set_gutters(&mut self, column_gutter: LayoutSize, row_gutter: LayoutSize) {
+        self.column_gutter = column_gutter;
+        self.row_gutter = row_gutter;
+        self.layout_cache.invalidate();
+    }
+
+    /// Adds a component that will be placed in the given `cell`. This method will panic if the
+    /// cell (including its span) is not within the columns and rows of this grid, or if its span
+    /// is 0.
+    pub fn add_component(&mut self, component: Box<dyn Component>, cell: GridCell) {
+        if cell.column_span == 0 || cell.row_span == 0 {
+            panic!("The spans of {:?} must be positive", cell);
+        }
+        if cell.column + cell.column_span > self.columns.len()
+            || cell.row + cell.row_span > self.rows.len()
+        {
+            panic!(
+                "{:?} is outside the grid with {} columns and {} rows",
+                cell,
+                self.columns.len(),
+                self.rows.len()
+            );
+        }
+
+        // The real domain will be computed as soon as the size of this menu is known
+        self.flat_menu
+            .add_component(component, ComponentDomain::between(0.0, 0.0, 0.0, 0.0));
+        self.cells.push(cell);
+        if let Some((width, height)) = self.layout_cache.get_size() {
+            let domain = self.compute_domain(cell, width, height);
+            self.flat_menu
+                .set_component_domain(self.cells.len() - 1, domain);
+        }
+    }
+
+    /// Overrides the order in which the keyboard focus moves through the components of this menu.
+    /// See the docs of the `set_focus_order` method of `SimpleFlatMenu`.
+    pub fn set_focus_order(&mut self, order: Vec<usize>) {
+        self.flat_menu.set_focus_order(order);
+    }
+
+    fn compute_domain(&self, cell: GridCell, width: u32, height: u32) -> ComponentDomain {
+        let (min_x, domain_width) = track_bounds(
+            &self.columns,
+            self.column_gutter,
+            width as f32,
+            cell.column,
+            cell.column_span,
+        );
+        // The rows are numbered from top to bottom, so the bounds are mirrored
+        let (top, domain_height) = track_bounds(
+            &self.rows,
+            self.row_gutter,
+            height as f32,
+            cell.row,
+            cell.row_span,
+        );
+        ComponentDomain::with_size(
+            min_x,
+            1.0 - top - domain_height,
+            domain_width,
+            domain_height,
+        )
+    }
+
+    fn update_layout(&mut self, width: u32, height: u32) {
+        for index in 0..self.cells.len() {
+            let domain = self.compute_domain(self.cells[index], width, height);
+            self.flat_menu.set_component_domain(index, domain);
+        }
+    }
+}
+
+/// Computes the start and size of the block of `span` tracks (columns or rows) that starts at
+/// track `first`, as a fraction of the `total_size` (in pixels). The block includes the gutters
+/// between its tracks.
+fn track_bounds(
+    tracks: &[GridTrackSize],
+    gutter: LayoutSize,
+    total_size: f32,
+    first: usize,
+    span: usize,
+) -> (f32, f32) {
+    if total_size <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let gutter = match gutter {
+        LayoutSize::Pixels(pixels) => pixels as f32,
+        LayoutSize::Fraction(fraction) => fraction * total_size,
+    };
+    let mut fixed_size = gutter * (tracks.len().max(1) - 1) as f32;
+    let mut total_weight = 0.0;
+    for track in tracks {
+        match track {
+            GridTrackSize::Pixels(pixels) => fixed_size += *pixels as f32,
+            GridTrackSize::Weight(weight) => total_weight += weight,
+        }
+    }
+    let remaining = (total_size - fixed_size).max(0.0);
+
+    let pixel_size = |track: &GridTrackSize| match track {
+        GridTrackSize::Pixels(pixels) => *pixels as f32,
+        GridTrackSize::Weight(weight) => match total_weight > 0.0 {
+            true => remaining * weight / total_weight,
+            false => 0.0,
+        },
+    };
+
+    let start: f32 = tracks[..first]
+        .iter()
+        .map(|track| pixel_size(track) + gutter)
+        .sum();
+    let size: f32 = tracks[first..first + span]
+        .iter()
+        .map(pixel_size)
+        .sum::<f32>()
+        + gutter * (span - 1) as f32;
+    (start / total_size, size / total_size)
+}
+
+impl Component for GridMenu {
+    forward_component_methods!(
+        flat_menu;
+        on_attach,
+        mouse_events,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+        on_animation_frame,
+        on_detach,
+    );
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((width, height)) = self.layout_cache.resize(event) {
+            self.update_layout(width, height);
+        }
+        self.flat_menu.on_resize(event, buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some((width, height)) = self.layout_cache.before_render(renderer) {
+            self.update_layout(width, height);
+        }
+        self.flat_menu.render(renderer, buddy, force)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::track_bounds;
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn assert_bounds(expected: (f32, f32), actual: (f32, f32)) {
+        assert!(
+            (expected.0 - actual.0).abs() < 0.0001 && (expected.1 - actual.1).abs() < 0.0001,
+            "Expected {:?}, but got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_track_bounds() {
+        use GridTrackSize::*;
+
+        // 20 pixels are fixed, so the weighted tracks share the remaining 80 pixels
+        let tracks = [Pixels(20), Weight(1.0), Weight(3.0)];
+        let no_gutter = LayoutSize::Pixels(0);
+        assert_bounds((0.0, 0.2), track_bounds(&tracks, no_gutter, 100.0, 0, 1));
+        assert_bounds((0.2, 0.2), track_bounds(&tracks, no_gutter, 100.0, 1, 1));
+        assert_bounds((0.4, 0.6), track_bounds(&tracks, no_gutter, 100.0, 2, 1));
+        assert_bounds((0.0, 1.0), track_bounds(&tracks, no_gutter, 100.0, 0, 3));
+
+        // With 2 gutters of 10 pixels, only 60 pixels remain for the weighted tracks
+        let gutter = LayoutSize::Pixels(10);
+        assert_bounds((0.0, 0.2), track_bounds(&tracks, gutter, 100.0, 0, 1));
+        assert_bounds((0.3, 0.15), track_bounds(&tracks, gutter, 100.0, 1, 1));
+        assert_bounds((0.55, 0.45), track_bounds(&tracks, gutter, 100.0, 2, 1));
+
+        // A span should include the gutters between its tracks
+        assert_bounds((0.3, 0.7), track_bounds(&tracks, gutter, 100.0, 1, 2));
+
+        // Fractional gutters are relative to the total size
+        let gutter = LayoutSize::Fraction(0.1);
+        assert_bounds((0.55, 0.45), track_bounds(&tracks, gutter, 100.0, 2, 1));
+    }
+
+    struct LoggingComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn on_resize(&mut self, event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!(
+                "{} resize {}x{}",
+                self.name,
+                event.get_width(),
+                event.get_height()
+            ));
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} click", self.name));
+        }
+    }
+
+    #[test]
+    fn test_grid_menu() {
+        use GridTrackSize::*;
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let component = |name| {
+            Box::new(LoggingComponent {
+                name,
+                log: Rc::clone(&log),
+            })
+        };
+
+        // A header that spans both columns, above a sidebar of 20 pixels and a content area
+        let mut menu = GridMenu::new(
+            None,
+            vec![Pixels(20), Weight(1.0)],
+            vec![Pixels(10), Weight(1.0)],
+        );
+        menu.set_gutters(LayoutSize::Pixels(10), LayoutSize::Pixels(0));
+        menu.add_component(component("header"), GridCell::spanning(0, 0, 2, 1));
+        menu.add_component(component("sidebar"), GridCell::single(0, 1));
+        menu.add_component(component("content"), GridCell::single(1, 1));
+
+        let mut application = Application::new(Box::new(menu));
+        application.fire_resize_event(ResizeEvent::new(100, 100));
+        assert_eq!(
+            vec![
+                "header resize 100x10",
+                "sidebar resize 20x90",
+                "content resize 70x90"
+            ],
+            log.replace(Vec::new())
+        );
+
+        application.render(
+            &test_renderer(RenderRegion::with_size(0, 0, 100, 100)),
+            false,
+        );
+
+        let click = |application: &mut Application, x: f32, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, y),
+                MouseButton::primary(),
+            ));
+        };
+        click(&mut application, 0.5, 0.95);
+        click(&mut application, 0.1, 0.5);
+        click(&mut application, 0.5, 0.5);
+
+        // This is in the gutter between the sidebar and the content
+        click(&mut application, 0.25, 0.5);
+        assert_eq!(
+            vec!["header click", "sidebar click", "content click"],
+            log.replace(Vec::new())
+        );
+
+        // The sidebar and header should keep their size in pixels
+        application.fire_resize_event(ResizeEvent::new(200, 200));
+        assert_eq!(
+            vec![
+                "header resize 200x10",
+                "sidebar resize 20x190",
+                "content resize 170x190"
+            ],
+            log.replace(Vec::new())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_cell_outside() {
+        let mut menu = GridMenu::new(
+            None,
+            vec![GridTrackSize::Weight(1.0)],
+            vec![GridTrackSize::Weight(1.0)],
+        );
+        menu.add_component(
+            Box::new(LoggingComponent {
+                name: "outside",
+                log: Rc::new(RefCell::new(Vec::new())),
+            }),
+            GridCell::spanning(0, 0, 2, 1),
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::*;
+
+/// A menu component whose children can overlap each other. Every child has a z-index: children
+/// with a larger z-index are drawn on top of children with a smaller z-index. Children with the
+/// same z-index are drawn in the order in which they were added.
+///
+/// ### Mouse events
+/// Mouse events at a point are passed to the top-most child that *drew* something at that point
+/// (according to the `drawn_region` of its last `RenderResultStruct`), regardless of whether it
+/// filters mouse actions. So a mostly transparent child on top won't block the mouse events for
+/// the children below it. The mouse enters and leaves the children at the end points of the
+/// `MouseMoveEvent`s, so the exact entrance and exit points are not computed.
+///
+/// ### Rendering
+/// When a child requests a render, the children that overlap with it will be rendered again as
+/// well, but only within the domain of the child that requested the render (using
+/// `push_scissor`). This ensures that (partially) transparent children are drawn correctly.
+///
+/// Internally, this menu uses a `SimpleFlatMenu`, so the other events are handled exactly like
+/// they would in a `SimpleFlatMenu`.
+pub struct LayeredMenu {
+    flat_menu: SimpleFlatMenu,
+}
+
+impl LayeredMenu {
+    pub fn new(background_color: Option<Color>) -> Self {
+        Self {
+            flat_menu: SimpleFlatMenu::new_layered(background_color),
+        }
+    }
+
+    pub fn add_component(
+        &mut self,
+        component: Box<dyn Component>,
+        domain: ComponentDomain,
+        z_index: i32,
+    ) {
+        self.flat_menu
+            .add_layered_component(component, domain, z_index);
+    }
+
+    /// Changes the z-index of the component at the given `index`: the first component that was
+    /// added to this menu has index 0, the second one has index 1, etc...
+    ///
+    /// This method will panic if `index` is not smaller than the number of added components.
+    pub fn set_z_index(&mut self, index: usize, z_index: i32) {
+        self.flat_menu.set_z_index(index, z_index);
+    }
+
+    /// See the docs of the `set_component_domain` method of `SimpleFlatMenu`
+    pub fn set_component_domain(&mut self, index: usize, domain: ComponentDomain) {
+        self.flat_menu.set_component_domain(index, domain);
+    }
+
+    /// See the docs of the `set_focus_order` method of `SimpleFlatMenu`
+    pub fn set_focus_order(&mut self, order: Vec<usize>) {
+        self.flat_menu.set_focus_order(order);
+    }
+}
+
+impl Component for LayeredMenu {
+    forward_component_methods!(
+        flat_menu;
+        on_attach,
+        on_resize,
+        render,
+        mouse_events,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+        on_animation_frame,
+        on_detach,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct LayerComponent {
+        name: &'static str,
+        drawn_region: RectangularDrawnRegion,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for LayerComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+            buddy.subscribe_mouse_move();
+            buddy.subscribe_mouse_enter();
+            buddy.subscribe_mouse_leave();
+        }
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            let scissor = renderer.get_scissor();
+            self.log.borrow_mut().push(format!(
+                "{} render ({}, {}, {}, {})",
+                self.name,
+                scissor.get_min_x(),
+                scissor.get_min_y(),
+                scissor.get_bound_x(),
+                scissor.get_bound_y()
+            ));
+            Ok(RenderResultStruct {
+                drawn_region: Box::new(self.drawn_region),
+                filter_mouse_actions: true,
+            })
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} click", self.name));
+            buddy.request_render();
+        }
+
+        fn on_mouse_move(&mut self, _event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} move", self.name));
+        }
+
+        fn on_mouse_enter(&mut self, _event: MouseEnterEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} enter", self.name));
+        }
+
+        fn on_mouse_leave(&mut self, _event: MouseLeaveEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} leave", self.name));
+        }
+    }
+
+    #[test]
+    fn test_layered_menu() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = LayeredMenu::new(None);
+
+        // The top component is added first, but it should still be on top because of its z-index.
+        // It only draws something on the left half of its domain.
+        menu.add_component(
+            Box::new(LayerComponent {
+                name: "top",
+                drawn_region: RectangularDrawnRegion::new(0.0, 0.0, 0.5, 1.0),
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.25, 0.25, 0.75, 0.75),
+            1,
+        );
+        menu.add_component(
+            Box::new(LayerComponent {
+                name: "bottom",
+                drawn_region: RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0),
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+            0,
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        let region = RenderRegion::between(0, 0, 100, 100);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "bottom render (0, 0, 100, 100)",
+                "top render (25, 25, 75, 75)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        let click = |application: &mut Application, x: f32, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, y),
+                MouseButton::primary(),
+            ));
+        };
+        click(&mut application, 0.3, 0.5);
+        assert_eq!(vec!["top click"], log.replace(Vec::new()));
+
+        // The top component requested a render, so the bottom component should be redrawn behind it
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "bottom render (25, 25, 75, 75)",
+                "top render (25, 25, 75, 75)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // The top component didn't draw anything on the right half of its domain
+        click(&mut application, 0.6, 0.5);
+        click(&mut application, 0.1, 0.1);
+        assert_eq!(
+            vec!["bottom click", "bottom click"],
+            log.replace(Vec::new())
+        );
+
+        // The bottom component requested a render, so the top component should be redrawn as well
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "bottom render (0, 0, 100, 100)",
+                "top render (25, 25, 75, 75)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        let mouse = Mouse::new(0);
+        let move_mouse = |application: &mut Application, from_x: f32, to_x: f32| {
+            application.fire_mouse_move_event(MouseMoveEvent::new(
+                mouse,
+                Point::new(from_x, 0.5),
+                Point::new(to_x, 0.5),
+            ));
+        };
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.1, 0.5)));
+        assert_eq!(vec!["bottom enter"], log.replace(Vec::new()));
+
+        move_mouse(&mut application, 0.1, 0.2);
+        move_mouse(&mut application, 0.2, 0.3);
+        assert_eq!(
+            vec!["bottom move", "bottom leave", "top enter"],
+            log.replace(Vec::new())
+        );
+
+        move_mouse(&mut application, 0.3, 0.4);
+        move_mouse(&mut application, 0.4, 0.6);
+        assert_eq!(
+            vec!["top move", "top leave", "bottom enter"],
+            log.replace(Vec::new())
+        );
+    }
+}
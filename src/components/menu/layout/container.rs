@@ -0,0 +1,589 @@
+use crate::*;
+
+/// A size in a layout of a `LayoutMenu`. It can be either an absolute number of pixels, or a
+/// fraction of the size of the container it is used in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LayoutSize {
+    Pixels(u32),
+    Fraction(f32),
+}
+
+impl LayoutSize {
+    pub(crate) fn to_pixels(self, container_size: f32) -> f32 {
+        match self {
+            Self::Pixels(pixels) => pixels as f32,
+            Self::Fraction(fraction) => fraction * container_size,
+        }
+    }
+}
+
+/// The constraints of a child of a `LayoutContainer`.
+///
+/// The fractional minimum and maximum sizes are relative to the size of the container (after
+/// subtracting its padding). When a child doesn't have a maximum size, it can grow as large as
+/// the container allows.
+///
+/// ### Weight
+/// In rows and columns, every child starts with its minimum size (along the main axis of the
+/// container). Any remaining space is distributed over the children with a positive weight, in
+/// proportion to their weights. A child with weight 2.0 will grow twice as fast as a child with
+/// weight 1.0 (until it reaches its maximum size). The weight is ignored in stacks.
+///
+/// Along the other axis (and along both axes in stacks), the child will be as large as its
+/// constraints allow, and it will be centered in the container.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LayoutConstraints {
+    pub min_width: LayoutSize,
+    pub max_width: Option<LayoutSize>,
+    pub min_height: LayoutSize,
+    pub max_height: Option<LayoutSize>,
+    pub weight: f32,
+}
+
+impl LayoutConstraints {
+    /// Constructs constraints without minimum or maximum size, and with the given `weight`
+    pub fn flexible(weight: f32) -> Self {
+        Self {
+            min_width: LayoutSize::Pixels(0),
+            max_width: None,
+            min_height: LayoutSize::Pixels(0),
+            max_height: None,
+            weight,
+        }
+    }
+
+    /// Constructs constraints for a child with the given fixed `width` and `height`. The weight
+    /// will be 0.0, so the child won't take any of the remaining space.
+    pub fn fixed(width: LayoutSize, height: LayoutSize) -> Self {
+        Self {
+            min_width: width,
+            max_width: Some(width),
+            min_height: height,
+            max_height: Some(height),
+            weight: 0.0,
+        }
+    }
+}
+
+/// The empty space between the border of a `LayoutContainer` and its children. The fractional
+/// sizes are relative to the size of the container.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LayoutPadding {
+    pub left: LayoutSize,
+    pub bottom: LayoutSize,
+    pub right: LayoutSize,
+    pub top: LayoutSize,
+}
+
+impl LayoutPadding {
+    /// Constructs a `LayoutPadding` that uses the same `size` on all 4 sides
+    pub fn uniform(size: LayoutSize) -> Self {
+        Self {
+            left: size,
+            bottom: size,
+            right: size,
+            top: size,
+        }
+    }
+
+    /// Constructs a `LayoutPadding` without any padding
+    pub fn none() -> Self {
+        Self::uniform(LayoutSize::Pixels(0))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ContainerKind {
+    Row,
+    Column,
+    Stack,
+}
+
+enum LayoutChild {
+    Component(Box<dyn Component>),
+    Container(LayoutContainer),
+}
+
+/// A container in the layout of a `LayoutMenu`. Containers can hold components and other
+/// containers, and determine where their children are placed:
+/// - In a *row*, the children are placed from left to right.
+/// - In a *column*, the children are placed from top to bottom.
+/// - In a *stack*, all children are placed on top of each other (the child that was added last
+///   will be drawn on top).
+///
+/// See `LayoutConstraints` for the details of the sizes of the children.
+pub struct LayoutContainer {
+    kind: ContainerKind,
+    padding: LayoutPadding,
+    spacing: LayoutSize,
+    children: Vec<(LayoutChild, LayoutConstraints)>,
+}
+
+impl LayoutContainer {
+    fn new(kind: ContainerKind) -> Self {
+        Self {
+            kind,
+            padding: LayoutPadding::none(),
+            spacing: LayoutSize::Pixels(0),
+            children: Vec::new(),
+        }
+    }
+
+    /// Constructs an empty container that places its children from left to right
+    pub fn row() -> Self {
+        Self::new(ContainerKind::Row)
+    }
+
+    /// Constructs an empty container that places its children from top to bottom
+    pub fn column() -> Self {
+        Self::new(ContainerKind::Column)
+    }
+
+    /// Constructs an empty container that places its children on top of each other
+    pub fn stack() -> Self {
+        Self::new(ContainerKind::Stack)
+    }
+
+    /// Sets the empty space between the border of this container and its children
+    pub fn set_padding(&mut self, padding: LayoutPadding) {
+        self.padding = padding;
+    }
+
+    /// Sets the empty space between 2 consecutive children of this container. The fractional
+    /// spacing is relative to the size of this container (after subtracting its padding). The
+    /// spacing is ignored by stacks.
+    pub fn set_spacing(&mut self, spacing: LayoutSize) {
+        self.spacing = spacing;
+    }
+
+    pub fn add_component(&mut self, component: Box<dyn Component>, constraints: LayoutConstraints) {
+        self.children
+            .push((LayoutChild::Component(component), constraints));
+    }
+
+    pub fn add_container(&mut self, container: LayoutContainer, constraints: LayoutConstraints) {
+        self.children
+            .push((LayoutChild::Container(container), constraints));
+    }
+
+    /// Moves all components in this container (and its child containers) to `components`, and
+    /// returns the `LayoutNode` that remembers where they should be placed.
+    pub(super) fn into_node(self, components: &mut Vec<Box<dyn Component>>) -> LayoutNode {
+        let children = self
+            .children
+            .into_iter()
+            .map(|(child, constraints)| {
+                let node_child = match child {
+                    LayoutChild::Component(component) => {
+                        components.push(component);
+                        LayoutNodeChild::Component(components.len() - 1)
+                    }
+                    LayoutChild::Container(container) => {
+                        LayoutNodeChild::Container(container.into_node(components))
+                    }
+                };
+                (node_child, constraints)
+            })
+            .collect();
+
+        LayoutNode {
+            kind: self.kind,
+            padding: self.padding,
+            spacing: self.spacing,
+            children,
+        }
+    }
+}
+
+enum LayoutNodeChild {
+    // The index of the component in the SimpleFlatMenu of the LayoutMenu
+    Component(usize),
+    Container(LayoutNode),
+}
+
+/// The counterpart of `LayoutContainer` after its components have been moved to a menu
+pub(super) struct LayoutNode {
+    kind: ContainerKind,
+    padding: LayoutPadding,
+    spacing: LayoutSize,
+    children: Vec<(LayoutNodeChild, LayoutConstraints)>,
+}
+
+// A rectangle in pixel coordinates. Floats are used to avoid accumulating rounding errors.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct PixelArea {
+    min_x: f32,
+    min_y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl LayoutNode {
+    /// Computes the domains of all components in this node for a menu with the given size (in
+    /// pixels). The domain of the component with index `i` will be stored in `domains[i]`.
+    pub(super) fn compute_domains(&self, width: u32, height: u32, domains: &mut [ComponentDomain]) {
+        let area = PixelArea {
+            min_x: 0.0,
+            min_y: 0.0,
+            width: width as f32,
+            height: height as f32,
+        };
+        self.place_children(area, width as f32, height as f32, domains);
+    }
+
+    fn place_children(
+        &self,
+        area: PixelArea,
+        menu_width: f32,
+        menu_height: f32,
+        domains: &mut [ComponentDomain],
+    ) {
+        let left = self.padding.left.to_pixels(area.width);
+        let bottom = self.padding.bottom.to_pixels(area.height);
+        let right = self.padding.right.to_pixels(area.width);
+        let top = self.padding.top.to_pixels(area.height);
+        let inner = PixelArea {
+            min_x: area.min_x + left,
+            min_y: area.min_y + bottom,
+            width: (area.width - left - right).max(0.0),
+            height: (area.height - bottom - top).max(0.0),
+        };
+
+        let child_areas = match self.kind {
+            ContainerKind::Row => self.place_along_main_axis(inner, true),
+            ContainerKind::Column => self.place_along_main_axis(inner, false),
+            ContainerKind::Stack => self
+                .children
+                .iter()
+                .map(|(_, constraints)| {
+                    let (min_x, width) = place_across(
+                        inner.min_x,
+                        inner.width,
+                        constraints.min_width,
+                        constraints.max_width,
+                    );
+                    let (min_y, height) = place_across(
+                        inner.min_y,
+                        inner.height,
+                        constraints.min_height,
+                        constraints.max_height,
+                    );
+                    PixelArea {
+                        min_x,
+                        min_y,
+                        width,
+                        height,
+                    }
+                })
+                .collect(),
+        };
+
+        for ((child, _), child_area) in self.children.iter().zip(child_areas) {
+            match child {
+                LayoutNodeChild::Component(index) => {
+                    domains[*index] = ComponentDomain::with_size(
+                        child_area.min_x / menu_width,
+                        child_area.min_y / menu_height,
+                        child_area.width / menu_width,
+                        child_area.height / menu_height,
+                    );
+                }
+                LayoutNodeChild::Container(node) => {
+                    node.place_children(child_area, menu_width, menu_height, domains)
+                }
+            }
+        }
+    }
+
+    fn place_along_main_axis(&self, inner: PixelArea, horizontal: bool) -> Vec<PixelArea> {
+        let (main_size, cross_size) = match horizontal {
+            true => (inner.width, inner.height),
+            false => (inner.height, inner.width),
+        };
+
+        let num_spaces = self.children.len().max(1) - 1;
+        let spacing = self.spacing.to_pixels(main_size);
+        let available = main_size - spacing * num_spaces as f32;
+
+        let size_bounds: Vec<(f32, f32, f32)> = self
+            .children
+            .iter()
+            .map(|(_, constraints)| {
+                let (min_size, max_size) = match horizontal {
+                    true => (constraints.min_width, constraints.max_width),
+                    false => (constraints.min_height, constraints.max_height),
+                };
+                let min_size = min_size.to_pixels(main_size);
+                let max_size = match max_size {
+                    Some(max_size) => max_size.to_pixels(main_size).max(min_size),
+                    None => f32::INFINITY,
+                };
+                (min_size, max_size, constraints.weight)
+            })
+            .collect();
+        let main_sizes = distribute(available, &size_bounds);
+
+        let mut children_areas = Vec::with_capacity(self.children.len());
+
+        // Rows start at the left and columns start at the top
+        let mut main_position = match horizontal {
+            true => inner.min_x,
+            false => inner.min_y + inner.height,
+        };
+        for ((_, constraints), main_child_size) in self.children.iter().zip(main_sizes) {
+            let (cross_min, cross_max, cross_start) = match horizontal {
+                true => (constraints.min_height, constraints.max_height, inner.min_y),
+                false => (constraints.min_width, constraints.max_width, inner.min_x),
+            };
+            let (cross_position, cross_child_size) =
+                place_across(cross_start, cross_size, cross_min, cross_max);
+
+            let child_area = match horizontal {
+                true => {
+                    let area = PixelArea {
+                        min_x: main_position,
+                        min_y: cross_position,
+                        width: main_child_size,
+                        height: cross_child_size,
+                    };
+                    main_position += main_child_size + spacing;
+                    area
+                }
+                false => {
+                    main_position -= main_child_size;
+                    let area = PixelArea {
+                        min_x: cross_position,
+                        min_y: main_position,
+                        width: cross_child_size,
+                        height: main_child_size,
+                    };
+                    main_position -= spacing;
+                    area
+                }
+            };
+            children_areas.push(child_area);
+        }
+
+        children_areas
+    }
+}
+
+/// Determines the position and size of a child along an axis where it should be as large as its
+/// constraints allow, and centered in the available space. Returns (position, size).
+fn place_across(
+    start: f32,
+    available: f32,
+    min_size: LayoutSize,
+    max_size: Option<LayoutSize>,
+) -> (f32, f32) {
+    let min_size = min_size.to_pixels(available);
+    let size = match max_size {
+        Some(max_size) => available.min(max_size.to_pixels(available)).max(min_size),
+        None => available.max(min_size),
+    };
+    (start + 0.5 * (available - size), size)
+}
+
+/// Distributes the `available` space over children with the given (min size, max size, weight)
+/// bounds, and returns the size of each child.
+fn distribute(available: f32, bounds: &[(f32, f32, f32)]) -> Vec<f32> {
+    let mut sizes: Vec<f32> = bounds.iter().map(|(min_size, _, _)| *min_size).collect();
+    let mut remaining = available - sizes.iter().sum::<f32>();
+    let mut growing: Vec<bool> = bounds
+        .iter()
+        .map(|(min_size, max_size, weight)| *weight > 0.0 && max_size > min_size)
+        .collect();
+
+    // Every iteration either distributes all remaining space, or stops at least 1 child from
+    // growing, so this loop will terminate
+    while remaining > 0.0 && growing.contains(&true) {
+        let total_weight: f32 = bounds
+            .iter()
+            .zip(&growing)
+            .filter(|(_, is_growing)| **is_growing)
+            .map(|((_, _, weight), _)| weight)
+            .sum();
+
+        let mut used = 0.0;
+        let mut reached_max = false;
+        for (index, (_, max_size, weight)) in bounds.iter().enumerate() {
+            if growing[index] {
+                let desired_size = sizes[index] + remaining * weight / total_weight;
+                if desired_size >= *max_size {
+                    used += max_size - sizes[index];
+                    sizes[index] = *max_size;
+                    growing[index] = false;
+                    reached_max = true;
+                } else {
+                    used += desired_size - sizes[index];
+                    sizes[index] = desired_size;
+                }
+            }
+        }
+
+        remaining -= used;
+        if !reached_max {
+            break;
+        }
+    }
+
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn assert_domain(expected: ComponentDomain, actual: ComponentDomain) {
+        let eps = 0.0001;
+        assert!(
+            (expected.get_min_x() - actual.get_min_x()).abs() < eps
+                && (expected.get_min_y() - actual.get_min_y()).abs() < eps
+                && (expected.get_max_x() - actual.get_max_x()).abs() < eps
+                && (expected.get_max_y() - actual.get_max_y()).abs() < eps,
+            "Expected {:?}, but got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn compute(container: LayoutContainer, width: u32, height: u32) -> Vec<ComponentDomain> {
+        let mut components = Vec::new();
+        let node = container.into_node(&mut components);
+        let mut domains = vec![ComponentDomain::between(0.0, 0.0, 0.0, 0.0); components.len()];
+        node.compute_domains(width, height, &mut domains);
+        domains
+    }
+
+    fn dummy() -> Box<dyn Component> {
+        Box::new(DummyComponent {})
+    }
+
+    #[test]
+    fn test_distribute() {
+        // Without maximum sizes, the remaining space should be divided by weight
+        assert_eq!(
+            vec![25.0, 75.0],
+            distribute(
+                100.0,
+                &[(10.0, f32::INFINITY, 1.0), (30.0, f32::INFINITY, 3.0)]
+            )
+        );
+
+        // Children without weight shouldn't grow
+        assert_eq!(
+            vec![20.0, 10.0],
+            distribute(
+                100.0,
+                &[(20.0, f32::INFINITY, 0.0), (10.0, f32::INFINITY, 0.0)]
+            )
+        );
+
+        // When a child reaches its maximum size, the others should get the rest
+        assert_eq!(
+            vec![20.0, 40.0, 40.0],
+            distribute(
+                100.0,
+                &[
+                    (0.0, 20.0, 1.0),
+                    (0.0, f32::INFINITY, 1.0),
+                    (0.0, f32::INFINITY, 1.0)
+                ]
+            )
+        );
+
+        // When there is not enough space, the children should keep their minimum size
+        assert_eq!(
+            vec![80.0, 40.0],
+            distribute(100.0, &[(80.0, 90.0, 1.0), (40.0, 50.0, 1.0)])
+        );
+    }
+
+    #[test]
+    fn test_row() {
+        let mut row = LayoutContainer::row();
+        row.set_padding(LayoutPadding::uniform(LayoutSize::Pixels(10)));
+        row.set_spacing(LayoutSize::Pixels(20));
+
+        // A sidebar of 50 pixels, and 2 flexible components
+        row.add_component(
+            dummy(),
+            LayoutConstraints::fixed(LayoutSize::Pixels(50), LayoutSize::Fraction(0.5)),
+        );
+        row.add_component(dummy(), LayoutConstraints::flexible(1.0));
+        row.add_component(dummy(), LayoutConstraints::flexible(3.0));
+
+        // Inner size is 180x80, so 90 pixels remain for the flexible components
+        let domains = compute(row, 200, 100);
+        assert_domain(ComponentDomain::between(0.05, 0.3, 0.3, 0.7), domains[0]);
+        assert_domain(ComponentDomain::between(0.4, 0.1, 0.5125, 0.9), domains[1]);
+        assert_domain(ComponentDomain::between(0.6125, 0.1, 0.95, 0.9), domains[2]);
+    }
+
+    #[test]
+    fn test_column() {
+        let mut column = LayoutContainer::column();
+        column.set_spacing(LayoutSize::Fraction(0.1));
+
+        let mut header_constraints = LayoutConstraints::flexible(0.0);
+        header_constraints.min_height = LayoutSize::Pixels(20);
+        header_constraints.max_width = Some(LayoutSize::Fraction(0.5));
+        column.add_component(dummy(), header_constraints);
+
+        let mut body_constraints = LayoutConstraints::flexible(1.0);
+        body_constraints.max_height = Some(LayoutSize::Pixels(50));
+        column.add_component(dummy(), body_constraints);
+
+        // The column should start at the top, and the body shouldn't exceed 50 pixels
+        let domains = compute(column, 100, 100);
+        assert_domain(ComponentDomain::between(0.25, 0.8, 0.75, 1.0), domains[0]);
+        assert_domain(ComponentDomain::between(0.0, 0.2, 1.0, 0.7), domains[1]);
+
+        // The column should adapt to the new size
+        let mut column = LayoutContainer::column();
+        column.add_component(dummy(), header_constraints);
+        column.add_component(dummy(), body_constraints);
+        let domains = compute(column, 200, 40);
+        assert_domain(ComponentDomain::between(0.25, 0.5, 0.75, 1.0), domains[0]);
+        assert_domain(ComponentDomain::between(0.0, 0.0, 1.0, 0.5), domains[1]);
+    }
+
+    #[test]
+    fn test_stack() {
+        let mut stack = LayoutContainer::stack();
+        stack.add_component(dummy(), LayoutConstraints::flexible(1.0));
+
+        let mut dialog_constraints = LayoutConstraints::flexible(1.0);
+        dialog_constraints.max_width = Some(LayoutSize::Pixels(100));
+        dialog_constraints.max_height = Some(LayoutSize::Fraction(0.5));
+        stack.add_component(dummy(), dialog_constraints);
+
+        let domains = compute(stack, 400, 200);
+        assert_domain(ComponentDomain::between(0.0, 0.0, 1.0, 1.0), domains[0]);
+        assert_domain(
+            ComponentDomain::between(0.375, 0.25, 0.625, 0.75),
+            domains[1],
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let mut inner_column = LayoutContainer::column();
+        inner_column.add_component(dummy(), LayoutConstraints::flexible(1.0));
+        inner_column.add_component(dummy(), LayoutConstraints::flexible(1.0));
+
+        let mut row = LayoutContainer::row();
+        row.add_component(dummy(), LayoutConstraints::flexible(1.0));
+        row.add_container(inner_column, LayoutConstraints::flexible(1.0));
+        row.add_component(dummy(), LayoutConstraints::flexible(2.0));
+
+        // The components should be numbered in the order they were added to the row
+        let domains = compute(row, 100, 100);
+        assert_eq!(4, domains.len());
+        assert_domain(ComponentDomain::between(0.0, 0.0, 0.25, 1.0), domains[0]);
+        assert_domain(ComponentDomain::between(0.25, 0.5, 0.5, 1.0), domains[1]);
+        assert_domain(ComponentDomain::between(0.25, 0.0, 0.5, 0.5), domains[2]);
+        assert_domain(ComponentDomain::between(0.5, 0.0, 1.0, 1.0), domains[3]);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::*;
+
+use super::LayoutCache;
+
+mod container;
+
+pub use container::*;
+
+/// A menu component that places its children according to a tree of `LayoutContainer`s rather
+/// than fixed `ComponentDomain`s. This makes it possible to mix sizes in pixels with relative
+/// sizes: for instance a toolbar with a fixed height above a content area that takes all remaining
+/// space.
+///
+/// The domains of the children are computed from the size of this menu, and recomputed whenever
+/// this menu is resized. Internally, this menu uses a `SimpleFlatMenu` to propagate the events
+/// to its children, so the children behave exactly like they would in a `SimpleFlatMenu`.
+///
+/// ### Indices
+/// The components in the layout are numbered in the order in which they were added to their
+/// containers (where the components of a child container are numbered at the moment that child
+/// container was added to its parent). These indices are used by `set_focus_order`.
+pub struct LayoutMenu {
+    flat_menu: SimpleFlatMenu,
+    root: LayoutNode,
+    num_components: usize,
+    layout_cache: LayoutCache,
+}
+
+impl LayoutMenu {
+    pub fn new(background_color: Option<Color>, root: LayoutContainer) -> Self {
+        let mut components = Vec::new();
+        let root = root.into_node(&mut components);
+        let num_components = components.len();
+
+        let mut flat_menu = SimpleFlatMenu::new(background_color);
+        for component in components {
+            // The real domains will be computed as soon as the size of this menu is known. Until
+            // then, the components won't be rendered and won't receive any events.
+            flat_menu.add_component(component, ComponentDomain::between(0.0, 0.0, 0.0, 0.0));
+        }
+
+        Self {
+            flat_menu,
+            root,
+            num_components,
+            layout_cache: LayoutCache::new(),
+        }
+    }
+
+    /// Overrides the order in which the keyboard focus moves through the components of this menu.
+    /// See the docs of the `set_focus_order` method of `SimpleFlatMenu` and the *Indices* section
+    /// of the docs of this struct.
+    pub fn set_focus_order(&mut self, order: Vec<usize>) {
+        self.flat_menu.set_focus_order(order);
+    }
+
+    fn update_layout(&mut self, width: u32, height: u32) {
+        let mut domains = vec![ComponentDomain::between(0.0, 0.0, 0.0, 0.0); self.num_components];
+        self.root.compute_domains(width, height, &mut domains);
+        for (index, domain) in domains.into_iter().enumerate() {
+            self.flat_menu.set_component_domain(index, domain);
+        }
+    }
+}
+
+impl Component for LayoutMenu {
+    forward_component_methods!(
+        flat_menu;
+        on_attach,
+        mouse_events,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+        on_animation_frame,
+        on_detach,
+    );
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((width, height)) = self.layout_cache.resize(event) {
+            self.update_layout(width, height);
+        }
+        self.flat_menu.on_resize(event, buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some((width, height)) = self.layout_cache.before_render(renderer) {
+            self.update_layout(width, height);
+        }
+        self.flat_menu.render(renderer, buddy, force)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct LoggingComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn on_resize(&mut self, event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!(
+                "{} resize {}x{}",
+                self.name,
+                event.get_width(),
+                event.get_height()
+            ));
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.log.borrow_mut().push(format!("{} render", self.name));
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} click", self.name));
+        }
+    }
+
+    #[test]
+    fn test_layout_menu() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let component = |name| {
+            Box::new(LoggingComponent {
+                name,
+                log: Rc::clone(&log),
+            })
+        };
+
+        // A toolbar of 20 pixels above a content area
+        let mut column = LayoutContainer::column();
+        let mut toolbar_constraints = LayoutConstraints::flexible(0.0);
+        toolbar_constraints.min_height = LayoutSize::Pixels(20);
+        column.add_component(component("toolbar"), toolbar_constraints);
+        column.add_component(component("content"), LayoutConstraints::flexible(1.0));
+
+        let mut application = Application::new(Box::new(LayoutMenu::new(None, column)));
+        let click = |application: &mut Application, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(0.5, y),
+                MouseButton::primary(),
+            ));
+        };
+
+        application.fire_resize_event(ResizeEvent::new(100, 100));
+        assert_eq!(
+            vec!["toolbar resize 100x20", "content resize 100x80"],
+            log.replace(Vec::new())
+        );
+
+        application.render(
+            &test_renderer(RenderRegion::with_size(0, 0, 100, 100)),
+            false,
+        );
+        assert_eq!(
+            vec!["toolbar render", "content render"],
+            log.replace(Vec::new())
+        );
+
+        click(&mut application, 0.85);
+        click(&mut application, 0.75);
+        assert_eq!(
+            vec!["toolbar click", "content click"],
+            log.replace(Vec::new())
+        );
+
+        // The toolbar should keep its height in pixels, so it should now be a smaller fraction
+        application.fire_resize_event(ResizeEvent::new(100, 200));
+        assert_eq!(vec!["content resize 100x180"], log.replace(Vec::new()));
+
+        // Both components should be rendered again because the domains changed
+        application.render(
+            &test_renderer(RenderRegion::with_size(0, 0, 100, 200)),
+            false,
+        );
+        assert_eq!(
+            vec!["toolbar render", "content render"],
+            log.replace(Vec::new())
+        );
+
+        click(&mut application, 0.85);
+        click(&mut application, 0.95);
+        assert_eq!(
+            vec!["content click", "toolbar click"],
+            log.replace(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_layout_without_resize() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut row = LayoutContainer::row();
+        let mut sidebar_constraints = LayoutConstraints::flexible(0.0);
+        sidebar_constraints.min_width = LayoutSize::Pixels(10);
+        row.add_component(
+            Box::new(LoggingComponent {
+                name: "sidebar",
+                log: Rc::clone(&log),
+            }),
+            sidebar_constraints,
+        );
+
+        // The layout should be computed from the viewport if no ResizeEvent was fired
+        let mut application = Application::new(Box::new(LayoutMenu::new(None, row)));
+        application.render(&test_renderer(RenderRegion::with_size(0, 0, 40, 20)), false);
+        assert_eq!(vec!["sidebar render"], log.replace(Vec::new()));
+
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.2, 0.5),
+            MouseButton::primary(),
+        ));
+        assert_eq!(vec!["sidebar click"], log.replace(Vec::new()));
+
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.3, 0.5),
+            MouseButton::primary(),
+        ));
+        assert!(log.borrow().is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Remembers the size (in pixels) for which a menu computed the domains of its components most
+/// recently. Menus that compute these domains from their own size (like `LayoutMenu`) use this to
+/// avoid computing them again when their size didn't change.
+pub(super) struct LayoutCache {
+    size: Option<(u32, u32)>,
+}
+
+impl LayoutCache {
+    pub(super) fn new() -> Self {
+        Self { size: None }
+    }
+
+    /// Gets the size for which the domains were computed most recently, if any
+    pub(super) fn get_size(&self) -> Option<(u32, u32)> {
+        self.size
+    }
+
+    /// Forgets the size, so that the domains will be computed again before the next render
+    pub(super) fn invalidate(&mut self) {
+        self.size = None;
+    }
+
+    /// Returns the new size if the domains need to be computed again after the given `event`
+    pub(super) fn resize(&mut self, event: ResizeEvent) -> Option<(u32, u32)> {
+        let size = (event.get_width(), event.get_height());
+        if self.size != Some(size) {
+            self.size = Some(size);
+            Some(size)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the size of the viewport of the `renderer` if the domains were never computed.
+    /// Menus should call this at the start of their `render` method, in case their parent didn't
+    /// give them a `ResizeEvent` (yet).
+    pub(super) fn before_render(&mut self, renderer: &Renderer) -> Option<(u32, u32)> {
+        if self.size.is_none() {
+            let viewport = renderer.get_viewport();
+            let size = (viewport.get_width(), viewport.get_height());
+            self.size = Some(size);
+            Some(size)
+        } else {
+            None
+        }
+    }
+}
@@ -1,3 +1,21 @@
+#[macro_use]
+mod forward;
+
 mod flat;
+mod grid;
+mod layered;
+mod layout;
+mod layout_cache;
+mod modal;
+mod scroll;
+mod transition;
 
 pub use flat::*;
+pub use grid::*;
+pub use layered::*;
+pub use layout::*;
+pub use modal::*;
+pub use scroll::*;
+pub use transition::*;
+
+use layout_cache::LayoutCache;
@@ -0,0 +1,508 @@
+use crate::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A menu that is shown on top of another menu (the *background*). The background will still be
+/// rendered (optionally dimmed), but it won't receive any input events while the modal is open:
+/// all events go to the components of the modal.
+///
+/// ### Opening
+/// Modals are normally opened with the `change_menu` method of a `ComponentBuddy`, which passes the
+/// current menu to its closure. That menu should become the background of the modal:
+/// ```
+/// use knukki::*;
+///
+/// fn open_modal(buddy: &mut dyn ComponentBuddy, create_dialog: fn(ModalHandle) -> Box<dyn Component>) {
+///     buddy.change_menu(Box::new(move |background| {
+///         let mut modal = ModalMenu::new(background, Some(Color::rgba(0, 0, 0, 150)));
+///         let dialog = create_dialog(modal.get_handle());
+///         modal.add_component(dialog, ComponentDomain::between(0.2, 0.3, 0.8, 0.7));
+///         Box::new(modal)
+///     }));
+/// }
+/// ```
+///
+/// ### Closing
+/// The components of the modal can close it with the `close` method of a `ModalHandle` (which can
+/// be obtained with the `get_handle` method). This will change the menu back to the background.
+/// Popups (like context menus and dropdown lists) will usually want to close as soon as the user
+/// clicks somewhere else, which can be accomplished with `set_close_on_click_outside`.
+///
+/// ### Lifecycle
+/// The background is detached when the modal opens and attached again by the modal, but with a
+/// buddy that doesn't know any mouse or keyboard. (Its timers will still work though.) When the
+/// modal closes, the background is detached again, after which it will be attached as the new root
+/// component.
+pub struct ModalMenu {
+    background: Rc<RefCell<Option<Box<dyn Component>>>>,
+    background_buddy: RootComponentBuddy,
+    #[allow(dead_code)] // Only used when golem rendering is enabled
+    dim_color: Option<Color>,
+
+    // The timers of the background, as pairs (own timer id, timer id of background)
+    background_timers: Vec<(u32, u32)>,
+    next_background_timer_id: u32,
+
+    flat_menu: SimpleFlatMenu,
+    domains: Vec<ComponentDomain>,
+    close_on_click_outside: bool,
+
+    force_next_render: bool,
+}
+
+// The timers of the background use ids with this bit, so that they can be distinguished from the
+// timers of the flat menu
+const BACKGROUND_TIMER_BIT: u32 = 1 << 31;
+
+/// A handle that can be used by the components of a `ModalMenu` to close it
+#[derive(Clone)]
+pub struct ModalHandle {
+    background: Rc<RefCell<Option<Box<dyn Component>>>>,
+}
+
+impl ModalHandle {
+    /// Closes the modal that created this handle by changing the menu back to its background.
+    /// The `buddy` should be the buddy of a component of the modal (or the modal itself).
+    pub fn close(&self, buddy: &mut dyn ComponentBuddy) {
+        let background = Rc::clone(&self.background);
+        buddy.change_menu(Box::new(move |_modal| {
+            background
+                .borrow_mut()
+                .take()
+                .expect("The modal should be closed only once")
+        }));
+    }
+}
+
+/// The side of the anchor at which a popup of a `ModalMenu` is placed. When there is not enough
+/// space at that side, the popup will be placed at the opposite side instead.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PopupSide {
+    Below,
+    Above,
+    Left,
+    Right,
+}
+
+impl ModalMenu {
+    /// Constructs a new `ModalMenu` on top of the given `background`. If `dim_color` is not `None`,
+    /// it will be blended over the background (so it should be partially transparent).
+    pub fn new(background: Box<dyn Component>, dim_color: Option<Color>) -> Self {
+        Self {
+            background: Rc::new(RefCell::new(Some(background))),
+            background_buddy: RootComponentBuddy::new(),
+            dim_color,
+
+            background_timers: Vec::new(),
+            next_background_timer_id: 0,
+
+            flat_menu: SimpleFlatMenu::new(None),
+            domains: Vec::new(),
+            close_on_click_outside: false,
+
+            force_next_render: true,
+        }
+    }
+
+    /// Gets a `ModalHandle` that can be used to close this modal
+    pub fn get_handle(&self) -> ModalHandle {
+        ModalHandle {
+            background: Rc::clone(&self.background),
+        }
+    }
+
+    /// When `close_on_click_outside` is true, this modal will be closed when the user clicks
+    /// outside the domains of its components. This is false by default.
+    pub fn set_close_on_click_outside(&mut self, close_on_click_outside: bool) {
+        self.close_on_click_outside = close_on_click_outside;
+    }
+
+    pub fn add_component(&mut self, component: Box<dyn Component>, domain: ComponentDomain) {
+        self.flat_menu.add_component(component, domain);
+        self.domains.push(domain);
+    }
+
+    /// Adds a popup component of the given `width` and `height` next to the given `anchor`, at the
+    /// given `side` of the anchor. The popup is aligned with the left side of the anchor (when
+    /// placed below or above it) or the top side of the anchor (when placed left or right of it),
+    /// and it will be moved such that it stays within this menu.
+    ///
+    /// The `anchor` should be expressed in the coordinates of this menu (which normally covers the
+    /// entire application). Dropdowns will typically use their own domain as anchor.
+    pub fn add_popup(
+        &mut self,
+        component: Box<dyn Component>,
+        anchor: ComponentDomain,
+        width: f32,
+        height: f32,
+        side: PopupSide,
+    ) {
+        self.add_component(component, place_popup(anchor, width, height, side));
+    }
+
+    fn check_background(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if self.background_buddy.did_request_render() {
+            self.force_next_render = true;
+            if self.background_buddy.did_request_animation_frame() {
+                buddy.request_animation_frame();
+            } else {
+                buddy.request_render();
+            }
+        }
+
+        for (delay, background_id) in self.background_buddy.take_timer_requests() {
+            let own_id = self.next_background_timer_id | BACKGROUND_TIMER_BIT;
+            self.next_background_timer_id =
+                (self.next_background_timer_id + 1) % BACKGROUND_TIMER_BIT;
+            self.background_timers.push((own_id, background_id));
+            buddy.schedule_timer(delay, own_id);
+        }
+    }
+}
+
+/// Computes the domain of a popup of the given size next to the given anchor
+fn place_popup(
+    anchor: ComponentDomain,
+    width: f32,
+    height: f32,
+    side: PopupSide,
+) -> ComponentDomain {
+    let (min_x, min_y) = match side {
+        PopupSide::Below | PopupSide::Above => {
+            let below = anchor.get_min_y() - height;
+            let above = anchor.get_max_y();
+            let fits_below = below >= 0.0;
+            let fits_above = above + height <= 1.0;
+            let min_y = match side {
+                PopupSide::Below if fits_below || !fits_above => below,
+                PopupSide::Above if !fits_above && fits_below => below,
+                _ => above,
+            };
+            (anchor.get_min_x(), min_y)
+        }
+        PopupSide::Left | PopupSide::Right => {
+            let left = anchor.get_min_x() - width;
+            let right = anchor.get_max_x();
+            let fits_left = left >= 0.0;
+            let fits_right = right + width <= 1.0;
+            let min_x = match side {
+                PopupSide::Right if fits_right || !fits_left => right,
+                PopupSide::Left if !fits_left && fits_right => right,
+                _ => left,
+            };
+            (min_x, anchor.get_max_y() - height)
+        }
+    };
+
+    // Keep the popup inside the menu
+    let min_x = min_x.min(1.0 - width).max(0.0);
+    let min_y = min_y.min(1.0 - height).max(0.0);
+    ComponentDomain::with_size(min_x, min_y, width, height)
+}
+
+impl Component for ModalMenu {
+    forward_component_methods!(
+        flat_menu;
+        on_mouse_click_out,
+        on_mouse_press,
+        on_mouse_release,
+        on_mouse_move,
+        on_mouse_enter,
+        on_mouse_leave,
+        on_mouse_scroll,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+    );
+
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        // The background gets a buddy without any mouse or keyboard, which blocks its input
+        self.background_buddy = RootComponentBuddy::new();
+        self.background_buddy
+            .set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+        self.background_buddy
+            .set_keyboard_store(Rc::new(RefCell::new(KeyboardStore::new())));
+        self.background_buddy
+            .set_current_time(buddy.get_current_time());
+        if let Some(background) = self.background.borrow_mut().as_mut() {
+            background.on_attach(&mut self.background_buddy);
+        }
+        self.check_background(buddy);
+
+        self.flat_menu.on_attach(buddy);
+    }
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        self.background_buddy
+            .set_current_time(buddy.get_current_time());
+        if let Some(background) = self.background.borrow_mut().as_mut() {
+            background.on_resize(event, &mut self.background_buddy);
+        }
+        self.check_background(buddy);
+
+        self.flat_menu.on_resize(event, buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        // The background and dim color are drawn behind the components, so the components need to
+        // be drawn again whenever the background is drawn
+        let force = force || self.force_next_render;
+        self.force_next_render = false;
+
+        if force {
+            // A partial render of the background would be dimmed twice, so always redraw it all
+            self.background_buddy.clear_render_request();
+            self.background_buddy
+                .set_current_time(buddy.get_current_time());
+            if let Some(background) = self.background.borrow_mut().as_mut() {
+                let result = background.render(renderer, &mut self.background_buddy, true)?;
+                self.background_buddy.set_last_render_result(result);
+            }
+
+            #[cfg(feature = "golem_rendering")]
+            {
+                if let Some(dim_color) = self.dim_color {
+                    renderer.blend_color(dim_color)?;
+                }
+            }
+        }
+
+        let result = self.flat_menu.render(renderer, buddy, force);
+        self.check_background(buddy);
+
+        // The modal blocks the input of the background, so it covers everything
+        match force {
+            true => {
+                result?;
+                entire_render_result()
+            }
+            false => result,
+        }
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        let point = event.get_point();
+        if self.close_on_click_outside && !self.domains.iter().any(|domain| domain.is_inside(point))
+        {
+            self.get_handle().close(buddy);
+        } else {
+            self.flat_menu.on_mouse_click(event, buddy);
+        }
+    }
+
+    fn on_timer(&mut self, event: TimerEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_id() & BACKGROUND_TIMER_BIT == 0 {
+            self.flat_menu.on_timer(event, buddy);
+            return;
+        }
+
+        let maybe_index = self
+            .background_timers
+            .iter()
+            .position(|(own_id, _)| *own_id == event.get_id());
+        if let Some(index) = maybe_index {
+            let (_, background_id) = self.background_timers.remove(index);
+            self.background_buddy
+                .set_current_time(buddy.get_current_time());
+            if let Some(background) = self.background.borrow_mut().as_mut() {
+                background.on_timer(TimerEvent::new(background_id), &mut self.background_buddy);
+            }
+            self.check_background(buddy);
+        }
+    }
+
+    fn on_animation_frame(&mut self, event: AnimationFrameEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.background_buddy.take_animation_frame_request() {
+            self.background_buddy
+                .set_current_time(buddy.get_current_time());
+            if let Some(background) = self.background.borrow_mut().as_mut() {
+                background.on_animation_frame(event, &mut self.background_buddy);
+            }
+            self.check_background(buddy);
+        }
+        self.flat_menu.on_animation_frame(event, buddy);
+    }
+
+    fn on_detach(&mut self) {
+        self.background_timers.clear();
+        self.flat_menu.on_detach();
+        if let Some(background) = self.background.borrow_mut().as_mut() {
+            background.on_detach();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::place_popup;
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_place_popup() {
+        let anchor = ComponentDomain::between(0.25, 0.5, 0.5, 0.75);
+
+        // There is enough space at these sides
+        assert_eq!(
+            ComponentDomain::between(0.25, 0.25, 0.5, 0.5),
+            place_popup(anchor, 0.25, 0.25, PopupSide::Below)
+        );
+        assert_eq!(
+            ComponentDomain::between(0.25, 0.75, 0.5, 1.0),
+            place_popup(anchor, 0.25, 0.25, PopupSide::Above)
+        );
+        assert_eq!(
+            ComponentDomain::between(0.5, 0.5, 0.75, 0.75),
+            place_popup(anchor, 0.25, 0.25, PopupSide::Right)
+        );
+
+        // There is not enough space at the left side, so it should be placed at the right side
+        assert_eq!(
+            ComponentDomain::between(0.5, 0.5, 0.875, 0.75),
+            place_popup(anchor, 0.375, 0.25, PopupSide::Left)
+        );
+
+        // There is not enough space below, so it should be placed above. It should also be moved
+        // to the left to stay inside the menu.
+        let low_anchor = ComponentDomain::between(0.75, 0.0, 1.0, 0.125);
+        assert_eq!(
+            ComponentDomain::between(0.5, 0.125, 1.0, 0.5),
+            place_popup(low_anchor, 0.5, 0.375, PopupSide::Below)
+        );
+    }
+
+    struct LoggingComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+        close_handle: Option<ModalHandle>,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} attach", self.name));
+            buddy.subscribe_mouse_click();
+        }
+
+        fn render(
+            &mut self,
+            _renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            self.log.borrow_mut().push(format!("{} render", self.name));
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} click", self.name));
+            if let Some(handle) = &self.close_handle {
+                handle.close(buddy);
+            } else {
+                let log = Rc::clone(&self.log);
+                buddy.change_menu(Box::new(move |background| {
+                    let mut modal = ModalMenu::new(background, Some(Color::rgba(0, 0, 0, 100)));
+                    let dialog = LoggingComponent {
+                        name: "dialog",
+                        log,
+                        close_handle: Some(modal.get_handle()),
+                    };
+                    modal.add_component(
+                        Box::new(dialog),
+                        ComponentDomain::between(0.25, 0.25, 0.75, 0.75),
+                    );
+                    Box::new(modal)
+                }));
+            }
+        }
+
+        fn on_detach(&mut self) {
+            self.log.borrow_mut().push(format!("{} detach", self.name));
+        }
+    }
+
+    #[test]
+    fn test_modal_menu() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        // The background should keep its components when it is detached and attached again
+        let mut background = SimpleFlatMenu::new(None);
+        background.add_component(
+            Box::new(LoggingComponent {
+                name: "background",
+                log: Rc::clone(&log),
+                close_handle: None,
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(background));
+        let region = RenderRegion::between(0, 0, 100, 100);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["background attach", "background render"],
+            log.replace(Vec::new())
+        );
+
+        let click = |application: &mut Application, x: f32, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, y),
+                MouseButton::primary(),
+            ));
+        };
+
+        // Clicking the background opens the modal
+        click(&mut application, 0.1, 0.1);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "background click",
+                "background detach",
+                "background attach",
+                "dialog attach",
+                "background render",
+                "dialog render"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // The background shouldn't receive input while the modal is open
+        click(&mut application, 0.1, 0.1);
+        application.render(&test_renderer(region), false);
+        assert!(log.replace(Vec::new()).is_empty());
+
+        // Clicking the dialog closes the modal
+        click(&mut application, 0.5, 0.5);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "dialog click",
+                "dialog detach",
+                "background detach",
+                "background attach",
+                "background render"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // And now the background should receive input again
+        click(&mut application, 0.1, 0.1);
+        assert_eq!(
+            vec![
+                "background click",
+                "background detach",
+                "background attach",
+                "dialog attach"
+            ],
+            log.replace(Vec::new())
+        );
+    }
+}
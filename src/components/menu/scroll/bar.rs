@@ -0,0 +1,141 @@
+use crate::*;
+
+use super::ScrollState;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A scroll bar of a `ScrollMenu`. The thumb can be dragged, and clicking on the track beside
+/// the thumb scrolls a whole page.
+pub(super) struct ScrollBar {
+    state: Rc<RefCell<ScrollState>>,
+    horizontal: bool,
+    track_color: Color,
+    thumb_color: Color,
+
+    // The mouse that is dragging the thumb, the position on the track where it grabbed the thumb,
+    // and the scroll offset at that moment
+    drag: Option<(Mouse, f32, f32)>,
+}
+
+impl ScrollBar {
+    pub(super) fn new(
+        state: Rc<RefCell<ScrollState>>,
+        horizontal: bool,
+        track_color: Color,
+        thumb_color: Color,
+    ) -> Self {
+        Self {
+            state,
+            horizontal,
+            track_color,
+            thumb_color,
+            drag: None,
+        }
+    }
+
+    /// Converts a point on this scroll bar to a position along the track: 0.0 is the left or top
+    /// of the track and 1.0 is the right or bottom of the track.
+    fn track_position(&self, point: Point) -> f32 {
+        match self.horizontal {
+            true => point.get_x(),
+            false => 1.0 - point.get_y(),
+        }
+    }
+
+    fn get_offset(&self) -> f32 {
+        let (offset_x, offset_y) = self.state.borrow().get_offset();
+        match self.horizontal {
+            true => offset_x,
+            false => offset_y,
+        }
+    }
+
+    fn scroll_to(&self, offset: f32) {
+        let mut state = self.state.borrow_mut();
+        let (offset_x, offset_y) = state.get_offset();
+        match self.horizontal {
+            true => state.scroll_to(offset, offset_y),
+            false => state.scroll_to(offset_x, offset),
+        };
+    }
+
+    fn get_content_size(&self) -> f32 {
+        match self.state.borrow().get_content_size() {
+            Some((width, height)) => match self.horizontal {
+                true => width,
+                false => height,
+            },
+            None => 0.0,
+        }
+    }
+}
+
+impl Component for ScrollBar {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_release();
+    }
+
+    #[allow(unused_variables)]
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        renderer.clear(self.track_color);
+
+        let (start, end) = self.state.borrow().get_visible_range(self.horizontal);
+        match self.horizontal {
+            true => {
+                renderer.push_viewport(start, 0.0, end, 1.0, || renderer.clear(self.thumb_color))
+            }
+            false => renderer.push_viewport(0.0, 1.0 - end, 1.0, 1.0 - start, || {
+                renderer.clear(self.thumb_color)
+            }),
+        };
+
+        entire_render_result()
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !event.get_button().is_primary() || self.drag.is_some() {
+            return;
+        }
+
+        let position = self.track_position(event.get_point());
+        let (start, end) = self.state.borrow().get_visible_range(self.horizontal);
+        if position >= start && position <= end {
+            self.drag = Some((event.get_mouse(), position, self.get_offset()));
+            buddy.capture_mouse(event.get_mouse());
+        } else {
+            // Clicking beside the thumb scrolls 1 page towards the mouse
+            let page_size = (end - start) * self.get_content_size();
+            let offset = self.get_offset();
+            match position < start {
+                true => self.scroll_to(offset - page_size),
+                false => self.scroll_to(offset + page_size),
+            };
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, _buddy: &mut dyn ComponentBuddy) {
+        if let Some((mouse, grab_position, grab_offset)) = self.drag {
+            if mouse == event.get_mouse() {
+                let position = self.track_position(event.get_to());
+                self.scroll_to(grab_offset + (position - grab_position) * self.get_content_size());
+            }
+        }
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((mouse, _, _)) = self.drag {
+            if mouse == event.get_mouse() && event.get_button().is_primary() {
+                self.drag = None;
+                buddy.release_mouse_capture(mouse);
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::*;
+
+use super::LayoutCache;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+mod bar;
+mod state;
+mod viewport;
+
+use bar::*;
+use state::*;
+use viewport::*;
+
+/// The scroll bars of a `ScrollMenu`. The vertical scroll bar is placed at the right side of the
+/// menu and the horizontal scroll bar is placed at the bottom of the menu.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScrollBars {
+    pub horizontal: bool,
+    pub vertical: bool,
+    /// The width of the vertical scroll bar and the height of the horizontal scroll bar, in pixels
+    pub thickness: u32,
+    pub track_color: Color,
+    pub thumb_color: Color,
+}
+
+impl ScrollBars {
+    /// Constructs a `ScrollBars` without any scroll bars. The content can still be scrolled with
+    /// the mouse wheel (or touchpad).
+    pub fn none() -> Self {
+        Self {
+            horizontal: false,
+            vertical: false,
+            thickness: 0,
+            track_color: Color::rgb(0, 0, 0),
+            thumb_color: Color::rgb(0, 0, 0),
+        }
+    }
+
+    /// Constructs a `ScrollBars` with only a vertical scroll bar with the given `thickness` (in
+    /// pixels) and a gray color scheme
+    pub fn vertical(thickness: u32) -> Self {
+        Self {
+            horizontal: false,
+            vertical: true,
+            thickness,
+            track_color: Color::rgb(220, 220, 220),
+            thumb_color: Color::rgb(120, 120, 120),
+        }
+    }
+
+    /// Constructs a `ScrollBars` with both a horizontal and a vertical scroll bar with the given
+    /// `thickness` (in pixels) and a gray color scheme
+    pub fn both(thickness: u32) -> Self {
+        Self {
+            horizontal: true,
+            ..Self::vertical(thickness)
+        }
+    }
+}
+
+/// A menu component that shows (a part of) a single content component that can be larger than
+/// this menu. The user can scroll through the content with the mouse wheel (or touchpad) and the
+/// optional scroll bars. The content will *not* receive the `MouseScrollEvent`s.
+///
+/// The content is rendered in a viewport that is shifted by the scroll offset, and clipped to
+/// the area of this menu that is not covered by the scroll bars. The mouse events for the
+/// content are transformed accordingly, so the content doesn't need to know that it is being
+/// scrolled.
+///
+/// ### Content size
+/// The size of the content is given by 2 `LayoutSize`s, where the fractional sizes are relative to
+/// the visible area. For instance, a content width of `LayoutSize::Fraction(1.0)` and a content
+/// height of `LayoutSize::Pixels(2000)` is a typical vertical list. The content is never smaller
+/// than the visible area. The content will receive a `ResizeEvent` with its full size.
+pub struct ScrollMenu {
+    flat_menu: SimpleFlatMenu,
+    state: Rc<RefCell<ScrollState>>,
+    scroll_bars: ScrollBars,
+    layout_cache: LayoutCache,
+    force_next_render: bool,
+}
+
+impl ScrollMenu {
+    pub fn new(
+        content: Box<dyn Component>,
+        content_width: LayoutSize,
+        content_height: LayoutSize,
+        scroll_bars: ScrollBars,
+    ) -> Self {
+        let state = Rc::new(RefCell::new(ScrollState::new(
+            content_width,
+            content_height,
+        )));
+        let mut flat_menu = SimpleFlatMenu::new(None);
+
+        // The real domains will be computed as soon as the size of this menu is known
+        let initial_domain = ComponentDomain::between(0.0, 0.0, 1.0, 1.0);
+        flat_menu.add_component(
+            Box::new(ScrollViewport::new(content, Rc::clone(&state))),
+            initial_domain,
+        );
+        if scroll_bars.vertical {
+            flat_menu.add_component(
+                Box::new(ScrollBar::new(
+                    Rc::clone(&state),
+                    false,
+                    scroll_bars.track_color,
+                    scroll_bars.thumb_color,
+                )),
+                initial_domain,
+            );
+        }
+        if scroll_bars.horizontal {
+            flat_menu.add_component(
+                Box::new(ScrollBar::new(
+                    Rc::clone(&state),
+                    true,
+                    scroll_bars.track_color,
+                    scroll_bars.thumb_color,
+                )),
+                initial_domain,
+            );
+        }
+
+        Self {
+            flat_menu,
+            state,
+            scroll_bars,
+            layout_cache: LayoutCache::new(),
+            force_next_render: false,
+        }
+    }
+
+    /// Sets the number of pixels the content moves when the user scrolls 1 line. The default
+    /// value is 40.
+    pub fn set_pixels_per_line(&mut self, pixels_per_line: f32) {
+        self.state.borrow_mut().set_pixels_per_line(pixels_per_line);
+    }
+
+    fn update_layout(&mut self, width: u32, height: u32) {
+        let mut bar_width = 0.0;
+        let mut bar_height = 0.0;
+        if self.scroll_bars.vertical {
+            bar_width = (self.scroll_bars.thickness as f32 / width as f32).min(1.0);
+        }
+        if self.scroll_bars.horizontal {
+            bar_height = (self.scroll_bars.thickness as f32 / height as f32).min(1.0);
+        }
+
+        let mut index = 0;
+        self.flat_menu.set_component_domain(
+            index,
+            ComponentDomain::between(0.0, bar_height, 1.0 - bar_width, 1.0),
+        );
+        if self.scroll_bars.vertical {
+            index += 1;
+            self.flat_menu.set_component_domain(
+                index,
+                ComponentDomain::between(1.0 - bar_width, bar_height, 1.0, 1.0),
+            );
+        }
+        if self.scroll_bars.horizontal {
+            index += 1;
+            self.flat_menu.set_component_domain(
+                index,
+                ComponentDomain::between(0.0, 0.0, 1.0 - bar_width, bar_height),
+            );
+        }
+    }
+
+    /// When the content was scrolled, the content and the scroll bars need to be redrawn
+    fn check_state(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if self.state.borrow_mut().take_changed() {
+            self.force_next_render = true;
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for ScrollMenu {
+    forward_component_methods!(
+        flat_menu, then check_state;
+        on_attach,
+        mouse_events,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+        on_animation_frame,
+        on_detach,
+    );
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((width, height)) = self.layout_cache.resize(event) {
+            self.update_layout(width, height);
+        }
+        self.flat_menu.on_resize(event, buddy);
+        self.check_state(buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some((width, height)) = self.layout_cache.before_render(renderer) {
+            self.update_layout(width, height);
+        }
+
+        self.check_state(buddy);
+        let force = force || self.force_next_render;
+        self.force_next_render = false;
+        let result = self.flat_menu.render(renderer, buddy, force);
+
+        // The viewport might have learned its size during this render, but the scroll bars are
+        // rendered after the viewport, so they are already up-to-date
+        self.state.borrow_mut().take_changed();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct LoggingComponent {
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+        }
+
+        fn on_resize(&mut self, event: ResizeEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!(
+                "resize {}x{}",
+                event.get_width(),
+                event.get_height()
+            ));
+        }
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            let viewport = renderer.get_viewport();
+            let scissor = renderer.get_scissor();
+            self.log.borrow_mut().push(format!(
+                "render ({}, {}, {}, {}) in ({}, {}, {}, {})",
+                viewport.get_min_x(),
+                viewport.get_min_y(),
+                viewport.get_bound_x(),
+                viewport.get_bound_y(),
+                scissor.get_min_x(),
+                scissor.get_min_y(),
+                scissor.get_bound_x(),
+                scissor.get_bound_y()
+            ));
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            let point = event.get_point();
+            self.log
+                .borrow_mut()
+                .push(format!("click {} {}", point.get_x(), point.get_y()));
+        }
+    }
+
+    fn click(application: &mut Application, x: f32, y: f32) {
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(x, y),
+            MouseButton::primary(),
+        ));
+    }
+
+    fn scroll(application: &mut Application, delta_y: f32) {
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            Mouse::new(0),
+            Point::new(0.5, 0.5),
+            0.0,
+            delta_y,
+        ));
+    }
+
+    #[test]
+    fn test_scroll_menu() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut menu = ScrollMenu::new(
+            Box::new(LoggingComponent {
+                log: Rc::clone(&log),
+            }),
+            LayoutSize::Fraction(1.0),
+            LayoutSize::Pixels(200),
+            ScrollBars::vertical(20),
+        );
+        menu.set_pixels_per_line(20.0);
+
+        let mut application = Application::new(Box::new(menu));
+        let region = RenderRegion::with_size(0, 0, 100, 100);
+        application.fire_resize_event(ResizeEvent::new(100, 100));
+        assert_eq!(vec!["resize 80x200"], log.replace(Vec::new()));
+
+        // The top half of the content should be visible
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["render (0, -100, 80, 100) in (0, 0, 80, 100)"],
+            log.replace(Vec::new())
+        );
+        click(&mut application, 0.4, 0.75);
+        assert_eq!(vec!["click 0.5 0.875"], log.replace(Vec::new()));
+
+        // The content is not visible behind the scroll bar
+        click(&mut application, 0.9, 0.75);
+        assert!(log.borrow().is_empty());
+
+        // Scrolling down 2 lines should move the content up by 40 pixels
+        scroll(&mut application, -2.0);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["render (0, -60, 80, 140) in (0, 0, 80, 100)"],
+            log.replace(Vec::new())
+        );
+        click(&mut application, 0.4, 0.75);
+        assert_eq!(vec!["click 0.5 0.675"], log.replace(Vec::new()));
+
+        // It shouldn't be possible to scroll beyond the bottom of the content
+        scroll(&mut application, -10.0);
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["render (0, 0, 80, 200) in (0, 0, 80, 100)"],
+            log.replace(Vec::new())
+        );
+
+        // Drag the thumb (which is now in the bottom half of the scroll bar) to the top
+        let mouse = Mouse::new(0);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, Point::new(0.9, 0.25)));
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            Point::new(0.9, 0.25),
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            Point::new(0.9, 0.25),
+            Point::new(0.9, 0.5),
+        ));
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["render (0, -50, 80, 150) in (0, 0, 80, 100)"],
+            log.replace(Vec::new())
+        );
+
+        // The scroll bar captured the mouse, so it can be dragged beyond the scroll bar
+        application.fire_mouse_move_event(MouseMoveEvent::new(
+            mouse,
+            Point::new(0.9, 0.5),
+            Point::new(0.3, 1.0),
+        ));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            Point::new(0.3, 1.0),
+            MouseButton::primary(),
+        ));
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["render (0, -100, 80, 100) in (0, 0, 80, 100)"],
+            log.replace(Vec::new())
+        );
+    }
+}
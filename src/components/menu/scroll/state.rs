@@ -0,0 +1,189 @@
+use crate::*;
+
+/// The state that is shared between a `ScrollMenu`, its viewport and its scroll bars
+pub(super) struct ScrollState {
+    content_width: LayoutSize,
+    content_height: LayoutSize,
+    pixels_per_line: f32,
+
+    // The size (in pixels) of the area in which the content is visible
+    view_size: Option<(u32, u32)>,
+
+    // The number of pixels the content is scrolled to the right and down
+    offset_x: f32,
+    offset_y: f32,
+
+    // Whether the offset or size changed since the last call to take_changed
+    changed: bool,
+}
+
+impl ScrollState {
+    pub(super) fn new(content_width: LayoutSize, content_height: LayoutSize) -> Self {
+        Self {
+            content_width,
+            content_height,
+            pixels_per_line: 40.0,
+            view_size: None,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            changed: false,
+        }
+    }
+
+    pub(super) fn set_pixels_per_line(&mut self, pixels_per_line: f32) {
+        self.pixels_per_line = pixels_per_line;
+    }
+
+    pub(super) fn get_pixels_per_line(&self) -> f32 {
+        self.pixels_per_line
+    }
+
+    pub(super) fn set_view_size(&mut self, width: u32, height: u32) {
+        if self.view_size != Some((width, height)) {
+            self.view_size = Some((width, height));
+            self.changed = true;
+            self.clamp_offset();
+        }
+    }
+
+    /// Gets the size of the content (in pixels), which is never smaller than the view size
+    pub(super) fn get_content_size(&self) -> Option<(f32, f32)> {
+        self.view_size.map(|(view_width, view_height)| {
+            let view_width = view_width as f32;
+            let view_height = view_height as f32;
+            (
+                self.content_width.to_pixels(view_width).max(view_width),
+                self.content_height.to_pixels(view_height).max(view_height),
+            )
+        })
+    }
+
+    pub(super) fn get_offset(&self) -> (f32, f32) {
+        (self.offset_x, self.offset_y)
+    }
+
+    /// Scrolls to the given offset (in pixels), but never further than the content allows
+    pub(super) fn scroll_to(&mut self, offset_x: f32, offset_y: f32) {
+        let old_offset = self.get_offset();
+        self.offset_x = offset_x;
+        self.offset_y = offset_y;
+        self.clamp_offset();
+        if self.get_offset() != old_offset {
+            self.changed = true;
+        }
+    }
+
+    pub(super) fn scroll_by(&mut self, delta_x: f32, delta_y: f32) {
+        self.scroll_to(self.offset_x + delta_x, self.offset_y + delta_y);
+    }
+
+    fn clamp_offset(&mut self) {
+        if let (Some((view_width, view_height)), Some((content_width, content_height))) =
+            (self.view_size, self.get_content_size())
+        {
+            let max_x = content_width - view_width as f32;
+            let max_y = content_height - view_height as f32;
+            self.offset_x = self.offset_x.max(0.0).min(max_x);
+            self.offset_y = self.offset_y.max(0.0).min(max_y);
+        }
+    }
+
+    /// Computes the domain of the content, relative to the view. The content will usually extend
+    /// beyond the view.
+    pub(super) fn get_content_domain(&self) -> Option<ComponentDomain> {
+        if let (Some((view_width, view_height)), Some((content_width, content_height))) =
+            (self.view_size, self.get_content_size())
+        {
+            let view_width = view_width as f32;
+            let view_height = view_height as f32;
+
+            // The top of the content is offset_y pixels above the top of the view
+            Some(ComponentDomain::with_size(
+                -self.offset_x / view_width,
+                (view_height + self.offset_y - content_height) / view_height,
+                content_width / view_width,
+                content_height / view_height,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the start and end of the visible part of the content along the horizontal axis (from
+    /// left to right) or vertical axis (from top to bottom), as fractions of the content size
+    pub(super) fn get_visible_range(&self, horizontal: bool) -> (f32, f32) {
+        if let (Some((view_width, view_height)), Some((content_width, content_height))) =
+            (self.view_size, self.get_content_size())
+        {
+            let (offset, view_size, content_size) = match horizontal {
+                true => (self.offset_x, view_width as f32, content_width),
+                false => (self.offset_y, view_height as f32, content_height),
+            };
+            (offset / content_size, (offset + view_size) / content_size)
+        } else {
+            (0.0, 1.0)
+        }
+    }
+
+    pub(super) fn take_changed(&mut self) -> bool {
+        let changed = self.changed;
+        self.changed = false;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_scroll_state() {
+        let mut state = ScrollState::new(LayoutSize::Fraction(1.0), LayoutSize::Pixels(400));
+        assert!(state.get_content_domain().is_none());
+        assert_eq!((0.0, 1.0), state.get_visible_range(false));
+
+        state.set_view_size(50, 100);
+        assert!(state.take_changed());
+        assert_eq!(Some((50.0, 400.0)), state.get_content_size());
+
+        // The top of the content should be aligned with the top of the view
+        assert_eq!(
+            Some(ComponentDomain::between(0.0, -3.0, 1.0, 1.0)),
+            state.get_content_domain()
+        );
+        assert_eq!((0.0, 0.25), state.get_visible_range(false));
+        assert_eq!((0.0, 1.0), state.get_visible_range(true));
+
+        state.scroll_by(10.0, 100.0);
+        assert!(state.take_changed());
+        assert_eq!((0.0, 100.0), state.get_offset());
+        assert_eq!(
+            Some(ComponentDomain::between(0.0, -2.0, 1.0, 2.0)),
+            state.get_content_domain()
+        );
+        assert_eq!((0.25, 0.5), state.get_visible_range(false));
+
+        // It shouldn't be possible to scroll beyond the bottom of the content
+        state.scroll_to(0.0, 1000.0);
+        assert!(state.take_changed());
+        assert_eq!((0.0, 300.0), state.get_offset());
+        assert_eq!(
+            Some(ComponentDomain::between(0.0, 0.0, 1.0, 4.0)),
+            state.get_content_domain()
+        );
+
+        state.scroll_by(0.0, 50.0);
+        assert!(!state.take_changed());
+
+        // When the view grows, the offset should be clamped again
+        state.set_view_size(50, 200);
+        assert!(state.take_changed());
+        assert_eq!((0.0, 200.0), state.get_offset());
+
+        // The content should never be smaller than the view
+        state.set_view_size(50, 500);
+        assert_eq!(Some((50.0, 500.0)), state.get_content_size());
+        assert_eq!((0.0, 0.0), state.get_offset());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+use super::LayoutCache;
+use super::ScrollState;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The part of a `ScrollMenu` in which the content is visible. It uses a `SimpleFlatMenu` with
+/// the content as its only component. The domain of the content is usually larger than this
+/// viewport, and it moves when the user scrolls.
+pub(super) struct ScrollViewport {
+    content_menu: SimpleFlatMenu,
+    state: Rc<RefCell<ScrollState>>,
+    layout_cache: LayoutCache,
+}
+
+impl ScrollViewport {
+    pub(super) fn new(content: Box<dyn Component>, state: Rc<RefCell<ScrollState>>) -> Self {
+        let mut content_menu = SimpleFlatMenu::new(None);
+        // The real domain will be computed as soon as the size of this viewport is known
+        content_menu.add_component(content, ComponentDomain::between(0.0, 0.0, 1.0, 1.0));
+        Self {
+            content_menu,
+            state,
+            layout_cache: LayoutCache::new(),
+        }
+    }
+
+    fn update_content_domain(&mut self) {
+        let maybe_domain = self.state.borrow().get_content_domain();
+        if let Some(domain) = maybe_domain {
+            self.content_menu.set_component_domain(0, domain);
+        }
+    }
+}
+
+impl Component for ScrollViewport {
+    forward_component_methods!(
+        content_menu;
+        on_attach,
+        on_mouse_click,
+        on_mouse_click_out,
+        on_mouse_press,
+        on_mouse_release,
+        on_mouse_move,
+        on_mouse_enter,
+        on_mouse_leave,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+        on_animation_frame,
+        on_detach,
+    );
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((width, height)) = self.layout_cache.resize(event) {
+            self.state.borrow_mut().set_view_size(width, height);
+        }
+        self.update_content_domain();
+        self.content_menu.on_resize(event, buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if let Some((width, height)) = self.layout_cache.before_render(renderer) {
+            self.state.borrow_mut().set_view_size(width, height);
+        }
+        self.update_content_domain();
+
+        // The viewport of the content is larger than this viewport, so make sure that the content
+        // can't draw outside this viewport
+        let content_menu = &mut self.content_menu;
+        renderer
+            .push_scissor(0.0, 0.0, 1.0, 1.0, || {
+                content_menu.render(renderer, buddy, force)
+            })
+            .unwrap_or_else(entire_render_result)
+    }
+
+    // The content doesn't receive the scroll events, because they are used to scroll it
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, _buddy: &mut dyn ComponentBuddy) {
+        let mut state = self.state.borrow_mut();
+        let pixels_per_line = state.get_pixels_per_line();
+
+        // A positive delta_y means that the user scrolled up, which moves the content down
+        state.scroll_by(
+            event.get_delta_x() * pixels_per_line,
+            -event.get_delta_y() * pixels_per_line,
+        );
+        drop(state);
+        self.update_content_domain();
+    }
+}
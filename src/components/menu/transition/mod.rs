@@ -0,0 +1,520 @@
+use crate::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The visual effect of a `TransitionMenu`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransitionEffect {
+    /// The incoming menu slides in from the right while the outgoing menu slides out to the left
+    SlideLeft,
+    /// The incoming menu slides in from the left while the outgoing menu slides out to the right
+    SlideRight,
+    /// The incoming menu slides in from below while the outgoing menu slides out at the top
+    SlideUp,
+    /// The incoming menu slides in from above while the outgoing menu slides out at the bottom
+    SlideDown,
+    /// The outgoing menu fades out to the given color during the first half of the transition, and
+    /// the incoming menu fades in from that color during the second half
+    Fade(Color),
+    /// The incoming menu grows from the center of the outgoing menu until it covers everything
+    Zoom,
+}
+
+/// A component that animates the change from an *outgoing* menu to an *incoming* menu. Both menus
+/// will be rendered (using `push_viewport`) during every frame of the transition. After the
+/// transition, only the incoming menu will be rendered.
+///
+/// ### Usage
+/// Transitions are normally started with the `change_menu` method of a `ComponentBuddy`:
+/// ```
+/// use knukki::*;
+/// use std::time::Duration;
+///
+/// fn open_settings(buddy: &mut dyn ComponentBuddy, settings_menu: Box<dyn Component>) {
+///     buddy.change_menu(Box::new(move |current_menu| {
+///         Box::new(TransitionMenu::new(
+///             current_menu,
+///             settings_menu,
+///             TransitionEffect::SlideLeft,
+///             Duration::from_millis(500),
+///         ))
+///     }));
+/// }
+/// ```
+///
+/// ### Events
+/// The outgoing menu is attached again by the transition, but with a buddy that doesn't know any
+/// mouse or keyboard, and it won't receive any events. It will be detached when the transition
+/// finishes. All events are passed to the incoming menu right away (even during the transition).
+pub struct TransitionMenu {
+    outgoing: Option<Box<dyn Component>>,
+    outgoing_buddy: RootComponentBuddy,
+    incoming: Box<dyn Component>,
+
+    effect: TransitionEffect,
+    duration: Duration,
+    // The time of the first frame of the transition
+    start_time: Option<Duration>,
+}
+
+impl TransitionMenu {
+    /// Constructs a new `TransitionMenu` that will change from `outgoing` to `incoming` using the
+    /// given `effect`. The transition will take the given `duration`, starting at the first frame
+    /// in which it is rendered.
+    pub fn new(
+        outgoing: Box<dyn Component>,
+        incoming: Box<dyn Component>,
+        effect: TransitionEffect,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            outgoing: Some(outgoing),
+            outgoing_buddy: RootComponentBuddy::new(),
+            incoming,
+
+            effect,
+            duration,
+            start_time: None,
+        }
+    }
+
+    /// Checks whether the transition is still going on
+    pub fn is_transitioning(&self) -> bool {
+        self.outgoing.is_some()
+    }
+
+    fn render_transition(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        progress: f32,
+    ) -> Result<(), RenderResult> {
+        // The outgoing menu should never have been removed before the transition is finished
+        let outgoing = self.outgoing.as_mut().unwrap();
+        let outgoing_buddy = &mut self.outgoing_buddy;
+        let incoming = &mut self.incoming;
+
+        let mut render_outgoing = |min_x: f32, min_y: f32, max_x: f32, max_y: f32| {
+            renderer.push_viewport(min_x, min_y, max_x, max_y, || {
+                let result = outgoing.render(renderer, outgoing_buddy, true)?;
+                outgoing_buddy.set_last_render_result(result);
+                Ok(())
+            })
+        };
+        let mut render_incoming = |min_x: f32, min_y: f32, max_x: f32, max_y: f32| {
+            renderer.push_viewport(min_x, min_y, max_x, max_y, || {
+                incoming.render(renderer, buddy, true).map(|_| ())
+            })
+        };
+
+        // The menus might not cover their entire viewport
+        renderer.clear(Color::rgb(0, 0, 0));
+
+        let p = progress;
+        let results = match self.effect {
+            TransitionEffect::SlideLeft => vec![
+                render_outgoing(-p, 0.0, 1.0 - p, 1.0),
+                render_incoming(1.0 - p, 0.0, 2.0 - p, 1.0),
+            ],
+            TransitionEffect::SlideRight => vec![
+                render_outgoing(p, 0.0, 1.0 + p, 1.0),
+                render_incoming(p - 1.0, 0.0, p, 1.0),
+            ],
+            TransitionEffect::SlideUp => vec![
+                render_outgoing(0.0, p, 1.0, 1.0 + p),
+                render_incoming(0.0, p - 1.0, 1.0, p),
+            ],
+            TransitionEffect::SlideDown => vec![
+                render_outgoing(0.0, -p, 1.0, 1.0 - p),
+                render_incoming(0.0, 1.0 - p, 1.0, 2.0 - p),
+            ],
+            #[allow(unused_variables)] // The color is only used when golem rendering is enabled
+            TransitionEffect::Fade(color) => {
+                let (result, opacity) = match p < 0.5 {
+                    true => (render_outgoing(0.0, 0.0, 1.0, 1.0), 2.0 * p),
+                    false => (render_incoming(0.0, 0.0, 1.0, 1.0), 2.0 - 2.0 * p),
+                };
+
+                #[cfg(feature = "golem_rendering")]
+                {
+                    let alpha = (opacity * 255.0) as u8;
+                    let fade_color =
+                        Color::rgba(color.get_red(), color.get_green(), color.get_blue(), alpha);
+                    renderer.blend_color(fade_color).map_err(Err)?;
+                }
+                #[cfg(not(feature = "golem_rendering"))]
+                let _ = opacity;
+
+                vec![result]
+            }
+            TransitionEffect::Zoom => {
+                let half_size = 0.5 * p;
+                vec![
+                    render_outgoing(0.0, 0.0, 1.0, 1.0),
+                    render_incoming(
+                        0.5 - half_size,
+                        0.5 - half_size,
+                        0.5 + half_size,
+                        0.5 + half_size,
+                    ),
+                ]
+            }
+        };
+
+        for result in results {
+            if let Some(Err(error)) = result {
+                return Err(Err(error));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for TransitionMenu {
+    // All events are passed to the incoming menu right away
+    forward_component_methods!(
+        incoming;
+        mouse_events,
+        gesture_events,
+        drag_and_drop_events,
+        keyboard_events,
+        focus_events,
+        on_timer,
+    );
+
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if let Some(outgoing) = &mut self.outgoing {
+            // The outgoing menu gets a buddy without any mouse or keyboard, so it won't get input
+            self.outgoing_buddy = RootComponentBuddy::new();
+            self.outgoing_buddy
+                .set_mouse_store(Rc::new(RefCell::new(MouseStore::new())));
+            self.outgoing_buddy
+                .set_keyboard_store(Rc::new(RefCell::new(KeyboardStore::new())));
+            self.outgoing_buddy
+                .set_current_time(buddy.get_current_time());
+            outgoing.on_attach(&mut self.outgoing_buddy);
+        }
+        self.incoming.on_attach(buddy);
+    }
+
+    fn on_resize(&mut self, event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(outgoing) = &mut self.outgoing {
+            outgoing.on_resize(event, &mut self.outgoing_buddy);
+        }
+        self.incoming.on_resize(event, buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        if self.outgoing.is_some() {
+            let now = buddy.get_current_time();
+            let start_time = *self.start_time.get_or_insert(now);
+            let elapsed_time = now
+                .checked_sub(start_time)
+                .unwrap_or(Duration::from_secs(0));
+            let progress = elapsed_time.as_secs_f32() / self.duration.as_secs_f32();
+
+            if progress < 1.0 {
+                self.outgoing_buddy.set_current_time(now);
+                if let Err(result) = self.render_transition(renderer, buddy, progress) {
+                    return result;
+                }
+
+                // The outgoing menu is about to be detached, so its timers don't matter
+                self.outgoing_buddy.take_timer_requests();
+
+                // Keep rendering until the transition is finished
+                buddy.request_animation_frame();
+                return entire_render_result();
+            }
+
+            // The transition is finished, so the outgoing menu is no longer needed
+            let mut outgoing = self.outgoing.take().unwrap();
+            outgoing.on_detach();
+
+            // The incoming menu might not cover the remains of the transition
+            renderer.clear(Color::rgb(0, 0, 0));
+            return self.incoming.render(renderer, buddy, true);
+        }
+
+        self.incoming.render(renderer, buddy, force)
+    }
+
+    fn on_animation_frame(&mut self, event: AnimationFrameEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(outgoing) = &mut self.outgoing {
+            if self.outgoing_buddy.take_animation_frame_request() {
+                self.outgoing_buddy.set_current_time(event.get_time());
+                outgoing.on_animation_frame(event, &mut self.outgoing_buddy);
+            }
+        }
+        // The incoming menu shares the buddy of this menu, so there is no way to tell whether it
+        // requested this frame
+        self.incoming.on_animation_frame(event, buddy);
+    }
+
+    fn on_detach(&mut self) {
+        if let Some(outgoing) = &mut self.outgoing {
+            outgoing.on_detach();
+        }
+        self.incoming.on_detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    struct LoggingComponent {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Component for LoggingComponent {
+        fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+            buddy.subscribe_mouse_click();
+            self.log.borrow_mut().push(format!("{} attach", self.name));
+        }
+
+        fn render(
+            &mut self,
+            renderer: &Renderer,
+            _buddy: &mut dyn ComponentBuddy,
+            _force: bool,
+        ) -> RenderResult {
+            let viewport = renderer.get_viewport();
+            self.log.borrow_mut().push(format!(
+                "{} render ({}, {})",
+                self.name,
+                viewport.get_min_x(),
+                viewport.get_min_y()
+            ));
+            entire_render_result()
+        }
+
+        fn on_mouse_click(&mut self, _event: MouseClickEvent, _buddy: &mut dyn ComponentBuddy) {
+            self.log.borrow_mut().push(format!("{} click", self.name));
+        }
+
+        fn on_detach(&mut self) {
+            self.log.borrow_mut().push(format!("{} detach", self.name));
+        }
+    }
+
+    #[test]
+    fn test_slide_transition() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let outgoing = Box::new(LoggingComponent {
+            name: "outgoing",
+            log: Rc::clone(&log),
+        });
+        let incoming = Box::new(LoggingComponent {
+            name: "incoming",
+            log: Rc::clone(&log),
+        });
+        let transition = TransitionMenu::new(
+            outgoing,
+            incoming,
+            TransitionEffect::SlideLeft,
+            Duration::from_millis(400),
+        );
+
+        let mut application = Application::new(Box::new(transition));
+        assert_eq!(
+            vec!["outgoing attach", "incoming attach"],
+            log.replace(Vec::new())
+        );
+
+        let region = RenderRegion::between(0, 0, 100, 100);
+        let render_at = |application: &mut Application, millis: u64| {
+            application.tick(Duration::from_millis(millis));
+            application.render(&test_renderer(region), false)
+        };
+
+        // The transition starts at the first frame, when the incoming menu is still out of sight
+        render_at(&mut application, 1000);
+        render_at(&mut application, 1100);
+        render_at(&mut application, 1200);
+        assert_eq!(
+            vec![
+                "outgoing render (0, 0)",
+                "outgoing render (-25, 0)",
+                "incoming render (75, 0)",
+                "outgoing render (-50, 0)",
+                "incoming render (50, 0)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // The outgoing menu shouldn't receive any input during the transition
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.1, 0.5),
+            MouseButton::primary(),
+        ));
+        assert_eq!(vec!["incoming click"], log.replace(Vec::new()));
+
+        render_at(&mut application, 1300);
+        render_at(&mut application, 1400);
+        assert_eq!(
+            vec![
+                "outgoing render (-75, 0)",
+                "incoming render (25, 0)",
+                "outgoing detach",
+                "incoming render (0, 0)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // The transition is finished, so it should stop rendering continuously
+        assert!(!render_at(&mut application, 1500));
+    }
+
+    #[test]
+    fn test_zoom_transition() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let outgoing = Box::new(LoggingComponent {
+            name: "outgoing",
+            log: Rc::clone(&log),
+        });
+        let incoming = Box::new(LoggingComponent {
+            name: "incoming",
+            log: Rc::clone(&log),
+        });
+        let transition = TransitionMenu::new(
+            outgoing,
+            incoming,
+            TransitionEffect::Zoom,
+            Duration::from_millis(200),
+        );
+        let mut application = Application::new(Box::new(transition));
+        log.replace(Vec::new());
+
+        let region = RenderRegion::between(0, 0, 100, 100);
+        application.render(&test_renderer(region), false);
+        application.tick(Duration::from_millis(100));
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec![
+                "outgoing render (0, 0)",
+                "outgoing render (0, 0)",
+                "incoming render (25, 25)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // Frames can be late, but the transition should still end in time
+        application.tick(Duration::from_millis(250));
+        application.render(&test_renderer(region), false);
+        assert_eq!(
+            vec!["outgoing detach", "incoming render (0, 0)"],
+            log.replace(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_transition_from_flat_menu() {
+        struct ChangingComponent {
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Component for ChangingComponent {
+            fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+                buddy.subscribe_mouse_click();
+            }
+
+            fn render(
+                &mut self,
+                renderer: &Renderer,
+                _buddy: &mut dyn ComponentBuddy,
+                _force: bool,
+            ) -> RenderResult {
+                let viewport = renderer.get_viewport();
+                self.log.borrow_mut().push(format!(
+                    "outgoing render ({}, {})",
+                    viewport.get_min_x(),
+                    viewport.get_min_y()
+                ));
+                entire_render_result()
+            }
+
+            fn on_mouse_click(&mut self, _event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+                let incoming = Box::new(LoggingComponent {
+                    name: "incoming",
+                    log: Rc::clone(&self.log),
+                });
+                buddy.change_menu(Box::new(move |current_menu| {
+                    Box::new(TransitionMenu::new(
+                        current_menu,
+                        incoming,
+                        TransitionEffect::SlideLeft,
+                        Duration::from_millis(400),
+                    ))
+                }));
+            }
+
+            fn on_detach(&mut self) {
+                self.log.borrow_mut().push("outgoing detach".to_string());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut outgoing = SimpleFlatMenu::new(None);
+        outgoing.add_component(
+            Box::new(ChangingComponent {
+                log: Rc::clone(&log),
+            }),
+            ComponentDomain::between(0.0, 0.0, 1.0, 1.0),
+        );
+        let mut application = Application::new(Box::new(outgoing));
+
+        let region = RenderRegion::between(0, 0, 100, 100);
+        let render_at = |application: &mut Application, millis: u64| {
+            application.tick(Duration::from_millis(millis));
+            application.render(&test_renderer(region), false)
+        };
+
+        render_at(&mut application, 1000);
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.5, 0.5),
+            MouseButton::primary(),
+        ));
+        assert_eq!(
+            vec![
+                "outgoing render (0, 0)",
+                "outgoing detach",
+                "incoming attach"
+            ],
+            log.replace(Vec::new())
+        );
+
+        // The outgoing menu should still have its component during the transition
+        render_at(&mut application, 1000);
+        render_at(&mut application, 1100);
+        assert_eq!(
+            vec![
+                "outgoing render (0, 0)",
+                "outgoing render (-25, 0)",
+                "incoming render (75, 0)"
+            ],
+            log.replace(Vec::new())
+        );
+
+        render_at(&mut application, 1400);
+        assert_eq!(
+            vec!["outgoing detach", "incoming render (0, 0)"],
+            log.replace(Vec::new())
+        );
+    }
+}
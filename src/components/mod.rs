@@ -1,7 +1,9 @@
 mod color;
 mod menu;
 mod text;
+mod widget;
 
 pub use color::*;
 pub use menu::*;
 pub use text::*;
+pub use widget::*;
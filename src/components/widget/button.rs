@@ -0,0 +1,179 @@
+use super::*;
+
+/// A push button with a text label. When the user clicks on it, its `on_click` callback will be
+/// called. The button fills its entire domain, and its background color depends on whether a
+/// mouse is hovering over it, pressing it, or whether it has been disabled.
+///
+/// ```
+/// use knukki::*;
+///
+/// let mut menu = SimpleFlatMenu::new(None);
+/// menu.add_component(
+///     Box::new(Button::new(
+///         "Start".to_string(),
+///         Box::new(|buddy: &mut dyn ComponentBuddy| {
+///             buddy.change_menu(Box::new(|old_menu| old_menu));
+///         }),
+///     )),
+///     ComponentDomain::between(0.3, 0.4, 0.7, 0.6),
+/// );
+/// ```
+pub struct Button {
+    label: String,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    on_click: ClickHandler,
+
+    input: WidgetInput,
+}
+
+impl Button {
+    /// Constructs a new enabled `Button` with the given `label`, that will call `on_click` each
+    /// time it is clicked. The buddy of the button will be passed to `on_click`.
+    pub fn new(label: String, on_click: ClickHandler) -> Self {
+        Self {
+            label,
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            on_click,
+            input: WidgetInput::new(),
+        }
+    }
+
+    /// Sets the font of the label
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this button. A disabled button is drawn in the disabled color and
+    /// won't call its `on_click` callback. This should be called before the button is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Component for Button {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        _buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        let state = self.input.get_state(self.enabled);
+        draw_bordered_box(
+            renderer,
+            self.colors.border,
+            self.colors.get_background(state),
+        );
+
+        if !self.label.is_empty() {
+            let font = self.font.get(renderer);
+            renderer.get_text_renderer().draw_text(
+                &self.label,
+                font,
+                label_position(0.1, 0.2, 0.9, 0.8, HorizontalTextAlignment::Center),
+                renderer,
+            )?;
+        }
+
+        // The background of the button covers its entire domain
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            (self.on_click)(buddy);
+        }
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn click(application: &mut Application, x: f32, y: f32) {
+        let point = Point::new(x, y);
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            point,
+            MouseButton::primary(),
+        ));
+    }
+
+    #[test]
+    fn test_button() {
+        let counter = Rc::new(Cell::new(0));
+        let button_counter = Rc::clone(&counter);
+        let button = Button::new(
+            "Hello".to_string(),
+            Box::new(move |_buddy| button_counter.set(button_counter.get() + 1)),
+        );
+        let mut application = Application::new(Box::new(button));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 100, 40)), false);
+
+        click(&mut application, 0.5, 0.5);
+        click(&mut application, 0.1, 0.9);
+        assert_eq!(2, counter.get());
+
+        // Other buttons shouldn't trigger the callback
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(0.5, 0.5),
+            MouseButton::new(2),
+        ));
+        assert_eq!(2, counter.get());
+    }
+
+    #[test]
+    fn test_disabled_button() {
+        let counter = Rc::new(Cell::new(0));
+        let button_counter = Rc::clone(&counter);
+        let mut button = Button::new(
+            "Disabled".to_string(),
+            Box::new(move |_buddy| button_counter.set(button_counter.get() + 1)),
+        );
+        button.set_enabled(false);
+        assert!(!button.is_enabled());
+
+        let mut application = Application::new(Box::new(button));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 100, 40)), false);
+        click(&mut application, 0.5, 0.5);
+        assert_eq!(0, counter.get());
+    }
+}
@@ -0,0 +1,191 @@
+use super::*;
+
+/// A square box that can be checked and unchecked by clicking on it, with an optional text label
+/// to its right. Clicking on the label will also toggle the checkbox, but clicking on the empty
+/// space in its domain won't.
+///
+/// Each time the user checks or unchecks it, its `on_change` callback will be called with the new
+/// value.
+pub struct Checkbox {
+    label: String,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    checked: bool,
+    on_change: ChangeHandler<bool>,
+
+    input: WidgetInput,
+}
+
+impl Checkbox {
+    /// Constructs a new enabled `Checkbox` with the given `label` (which can be empty). It will be
+    /// checked initially if `checked` is true.
+    pub fn new(label: String, checked: bool, on_change: ChangeHandler<bool>) -> Self {
+        Self {
+            label,
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            checked,
+            on_change,
+            input: WidgetInput::new(),
+        }
+    }
+
+    /// Sets the font of the label
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this checkbox. The user can't check or uncheck a disabled checkbox.
+    /// This should be called before the checkbox is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+}
+
+impl Component for Checkbox {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The box should stay square, so the drawn region depends on the aspect ratio
+        buddy.request_render();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        _buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        // The box is a square on the left, and its size is 80% of the height of the domain
+        let viewport = renderer.get_viewport();
+        let width = viewport.get_width() as f32;
+        let height = viewport.get_height() as f32;
+        let box_size = (0.8 * height).min(width);
+        let box_width = box_size / width;
+        let box_min_y = 0.5 - 0.5 * box_size / height;
+        let box_max_y = 0.5 + 0.5 * box_size / height;
+
+        let state = self.input.get_state(self.enabled);
+        renderer.push_viewport(0.0, box_min_y, box_width, box_max_y, || {
+            draw_bordered_box(
+                renderer,
+                self.colors.border,
+                self.colors.get_background(state),
+            );
+            if self.checked {
+                renderer.push_viewport(0.25, 0.25, 0.75, 0.75, || {
+                    renderer.clear(self.colors.accent)
+                });
+            }
+        });
+
+        let mut drawn_regions: Vec<Box<dyn DrawnRegion>> = vec![Box::new(
+            RectangularDrawnRegion::new(0.0, box_min_y, box_width, box_max_y),
+        )];
+
+        let label_min_x = 1.3 * box_width;
+        if !self.label.is_empty() && label_min_x < 1.0 {
+            let font = self.font.get(renderer);
+            let drawn_label = renderer.get_text_renderer().draw_text(
+                &self.label,
+                font,
+                label_position(
+                    label_min_x,
+                    box_min_y,
+                    1.0,
+                    box_max_y,
+                    HorizontalTextAlignment::Left,
+                ),
+                renderer,
+            )?;
+            drawn_regions.push(Box::new(label_region(drawn_label)));
+        }
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(CompositeDrawnRegion::new(drawn_regions)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            self.checked = !self.checked;
+            (self.on_change)(self.checked, buddy);
+            buddy.request_render();
+        }
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_checkbox() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let checkbox_changes = Rc::clone(&changes);
+        let checkbox = Checkbox::new(
+            "A".to_string(),
+            false,
+            Box::new(move |checked, _buddy| checkbox_changes.borrow_mut().push(checked)),
+        );
+        let mut application = Application::new(Box::new(checkbox));
+
+        // The box will be 40 by 40 pixels, and the label will be much smaller than the rest
+        application.render(&test_renderer(RenderRegion::between(0, 0, 500, 50)), false);
+        let click = |application: &mut Application, x: f32, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, y),
+                MouseButton::primary(),
+            ));
+        };
+
+        // Click in the box
+        click(&mut application, 0.04, 0.5);
+        assert_eq!(vec![true], *changes.borrow());
+
+        // Click above the box
+        click(&mut application, 0.04, 0.95);
+        assert_eq!(vec![true], *changes.borrow());
+
+        // Click in the empty space after the label
+        click(&mut application, 0.9, 0.5);
+        assert_eq!(vec![true], *changes.borrow());
+
+        // Click on the label
+        click(&mut application, 0.11, 0.5);
+        assert_eq!(vec![true, false], *changes.borrow());
+    }
+}
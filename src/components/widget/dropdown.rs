@@ -0,0 +1,490 @@
+use super::*;
+
+/// A dropdown (or combo box): a header that shows the label of the selected item, which expands
+/// a list of all items below it when it is clicked. Clicking on an item in the list will select
+/// it, after which the `on_change` callback will be called with a clone of the *value* of that
+/// item. The list closes when an item is selected, when the header is clicked again, or when the
+/// user clicks anywhere outside the dropdown (using the `MouseClickOutEvent`).
+///
+/// When the dropdown has the keyboard focus, the Up and Down keys select the previous or next
+/// item while the list is closed. Enter or Space opens the list, after which the Up, Down, Home
+/// and End keys move the highlighted item, Enter or Space selects it, and Escape closes the list.
+///
+/// ### Domain
+/// The domain of the dropdown must be large enough to contain the header *and* the list: the
+/// header takes the top `1 / (n + 1)` of the domain (where `n` is the number of items), and each
+/// item gets a row of the same height below it. While the list is closed, the dropdown only draws
+/// its header, and the rest of its domain is left to the components below it.
+///
+/// To draw the list over its siblings, the dropdown should be added to a `LayeredMenu` with a
+/// larger z-index than the components that the list could cover:
+/// ```
+/// use knukki::*;
+///
+/// #[derive(Copy, Clone)]
+/// enum Difficulty {
+///     Easy,
+///     Hard,
+/// }
+///
+/// let mut menu = LayeredMenu::new(Some(Color::rgb(0, 0, 0)));
+/// menu.add_component(
+///     Box::new(Dropdown::new(
+///         vec![
+///             ("Easy".to_string(), Difficulty::Easy),
+///             ("Hard".to_string(), Difficulty::Hard),
+///         ],
+///         Some(0),
+///         Box::new(|difficulty: Difficulty, _buddy: &mut dyn ComponentBuddy| {
+///             // Use the difficulty
+///         }),
+///     )),
+///     // The header will be the top third of this domain
+///     ComponentDomain::between(0.3, 0.5, 0.7, 0.8),
+///     1,
+/// );
+/// ```
+pub struct Dropdown<T: Clone + 'static> {
+    items: Vec<(String, T)>,
+    selected: Option<usize>,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    on_change: ChangeHandler<T>,
+
+    input: WidgetInput,
+    open: bool,
+    highlighted: Option<usize>,
+}
+
+impl<T: Clone + 'static> Dropdown<T> {
+    /// Constructs a new enabled `Dropdown` with the given `items`, which are pairs of a label and
+    /// a value. The item with index `selected` will be selected initially (if it is not `None`).
+    pub fn new(
+        items: Vec<(String, T)>,
+        selected: Option<usize>,
+        on_change: ChangeHandler<T>,
+    ) -> Self {
+        Self {
+            items,
+            selected,
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            on_change,
+            input: WidgetInput::new(),
+            open: false,
+            highlighted: None,
+        }
+    }
+
+    /// Sets the font of the labels
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this dropdown. The list of a disabled dropdown can't be opened. This
+    /// should be called before the dropdown is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Gets the value of the selected item, or `None` if no item is selected
+    pub fn get_selected_value(&self) -> Option<&T> {
+        self.selected.map(|index| &self.items[index].1)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Gets the height of the header and each row of the list
+    fn get_row_height(&self) -> f32 {
+        1.0 / (self.items.len() + 1) as f32
+    }
+
+    fn is_in_header(&self, point: Point) -> bool {
+        point.get_y() >= 1.0 - self.get_row_height()
+    }
+
+    /// Gets the index of the item whose row contains the given point, or `None` if the point is
+    /// in the header
+    fn get_item_at(&self, point: Point) -> Option<usize> {
+        let row = ((1.0 - point.get_y()) / self.get_row_height()) as usize;
+        match row {
+            0 => None,
+            row => Some(row.min(self.items.len()) - 1),
+        }
+    }
+
+    fn open_list(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if !self.items.is_empty() {
+            self.open = true;
+            self.highlighted = Some(self.selected.unwrap_or(0));
+            buddy.request_render();
+        }
+    }
+
+    fn close_list(&mut self, buddy: &mut dyn ComponentBuddy) {
+        if self.open {
+            self.open = false;
+            self.highlighted = None;
+            buddy.request_render();
+        }
+    }
+
+    fn select(&mut self, index: usize, buddy: &mut dyn ComponentBuddy) {
+        if self.selected != Some(index) {
+            self.selected = Some(index);
+            (self.on_change)(self.items[index].1.clone(), buddy);
+            buddy.request_render();
+        }
+    }
+}
+
+impl<T: Clone + 'static> Component for Dropdown<T> {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+        buddy.subscribe_mouse_click_out();
+
+        // Moving over the list changes which item is highlighted
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_key_press();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        let viewport = renderer.get_viewport();
+        let row_height = self.get_row_height();
+        let header_min_y = 1.0 - row_height;
+        let font = self.font.get(renderer);
+        let draw_label = |label: &str| match label.is_empty() {
+            true => Ok(()),
+            false => renderer
+                .get_text_renderer()
+                .draw_text(
+                    label,
+                    font,
+                    label_position(0.05, 0.2, 0.85, 0.8, HorizontalTextAlignment::Left),
+                    renderer,
+                )
+                .map(|_| ()),
+        };
+
+        let border = match buddy.has_focus() {
+            true => self.colors.accent,
+            false => self.colors.border,
+        };
+        let header_color = self
+            .colors
+            .get_background(self.input.get_state(self.enabled));
+        let selected_label = self
+            .selected
+            .map(|index| self.items[index].0.as_str())
+            .unwrap_or("");
+        renderer
+            .push_viewport(0.0, header_min_y, 1.0, 1.0, || {
+                draw_bordered_box(renderer, border, header_color);
+
+                // A small square on the right side indicates that this is a dropdown
+                let header_height = viewport.get_height() as f32 * row_height;
+                let marker_size = 0.3 * header_height / viewport.get_width() as f32;
+                let marker_max_x = 1.0 - marker_size;
+                renderer.push_viewport(
+                    marker_max_x - marker_size,
+                    0.35,
+                    marker_max_x,
+                    0.65,
+                    || renderer.clear(self.colors.accent),
+                );
+                draw_label(selected_label)
+            })
+            .unwrap_or(Ok(()))?;
+
+        if self.open {
+            let marker_width = 2.0 * BORDER_WIDTH / viewport.get_width() as f32;
+            for (index, (label, _)) in self.items.iter().enumerate() {
+                let max_y = header_min_y - index as f32 * row_height;
+                let background = match self.highlighted == Some(index) {
+                    true => self.colors.hovered,
+                    false => self.colors.normal,
+                };
+                renderer
+                    .push_viewport(0.0, max_y - row_height, 1.0, max_y, || {
+                        draw_bordered_box(renderer, self.colors.border, background);
+                        if self.selected == Some(index) {
+                            renderer.push_viewport(0.0, 0.0, marker_width, 1.0, || {
+                                renderer.clear(self.colors.accent)
+                            });
+                        }
+                        draw_label(label)
+                    })
+                    .unwrap_or(Ok(()))?;
+            }
+        }
+
+        // The list is only drawn while it is open
+        let drawn_min_y = match self.open {
+            true => 0.0,
+            false => header_min_y,
+        };
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(0.0, drawn_min_y, 1.0, 1.0)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if !self.enabled || !event.get_button().is_primary() {
+            return;
+        }
+
+        if self.is_in_header(event.get_point()) {
+            match self.open {
+                true => self.close_list(buddy),
+                false => self.open_list(buddy),
+            }
+        } else if self.open {
+            if let Some(index) = self.get_item_at(event.get_point()) {
+                self.select(index, buddy);
+                self.close_list(buddy);
+            }
+        }
+    }
+
+    fn on_mouse_click_out(&mut self, _event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        self.close_list(buddy);
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            buddy.request_focus();
+        }
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.open {
+            let item = self.get_item_at(event.get_to());
+            if item.is_some() && item != self.highlighted {
+                self.highlighted = item;
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !should_handle_key_press(self.enabled, buddy) || self.items.is_empty() {
+            return;
+        }
+
+        let last = self.items.len() - 1;
+        let current = match self.open {
+            true => self.highlighted,
+            false => self.selected,
+        };
+        let target = match event.get_key() {
+            Key::Up => current.map(|index| index.saturating_sub(1)).unwrap_or(0),
+            Key::Down => current.map(|index| (index + 1).min(last)).unwrap_or(0),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Enter | Key::Space => {
+                match (self.open, self.highlighted) {
+                    (true, Some(highlighted)) => {
+                        self.select(highlighted, buddy);
+                        self.close_list(buddy);
+                    }
+                    (true, None) => self.close_list(buddy),
+                    (false, _) => self.open_list(buddy),
+                }
+                return;
+            }
+            Key::Escape => {
+                self.close_list(buddy);
+                return;
+            }
+            _ => return,
+        };
+
+        // While the list is closed, the arrow keys change the selection directly
+        match self.open {
+            true => {
+                self.highlighted = Some(target);
+                buddy.request_render();
+            }
+            false => self.select(target, buddy),
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+
+    fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.close_list(buddy);
+        buddy.request_render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    fn click(application: &mut Application, x: f32, y: f32) {
+        application.fire_mouse_click_event(MouseClickEvent::new(
+            Mouse::new(0),
+            Point::new(x, y),
+            MouseButton::primary(),
+        ));
+    }
+
+    fn create_items() -> Vec<(String, i32)> {
+        vec![
+            ("one".to_string(), 1),
+            ("two".to_string(), 2),
+            ("three".to_string(), 3),
+        ]
+    }
+
+    #[test]
+    fn test_dropdown_over_siblings() {
+        let button_counter = Rc::new(Cell::new(0));
+        let button_counter_clone = Rc::clone(&button_counter);
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let dropdown_changes = Rc::clone(&changes);
+
+        let mut menu = LayeredMenu::new(Some(Color::rgb(0, 0, 0)));
+        menu.add_component(
+            Box::new(Button::new(
+                "Button".to_string(),
+                Box::new(move |_buddy| button_counter_clone.set(button_counter_clone.get() + 1)),
+            )),
+            ComponentDomain::between(0.0, 0.0, 1.0, 0.6),
+            0,
+        );
+
+        // The header will go from y = 0.84 to y = 1.0, and each item will be 0.16 high
+        menu.add_component(
+            Box::new(Dropdown::new(
+                create_items(),
+                None,
+                Box::new(move |value, _buddy| dropdown_changes.borrow_mut().push(value)),
+            )),
+            ComponentDomain::between(0.0, 0.2, 1.0, 1.0),
+            1,
+        );
+
+        let mut application = Application::new(Box::new(menu));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 200, 500));
+        application.render(&renderer, false);
+
+        // While the dropdown is closed, clicks below its header should reach the button
+        click(&mut application, 0.5, 0.4);
+        assert_eq!(1, button_counter.get());
+
+        // While it is open, the list should cover the button
+        click(&mut application, 0.5, 0.9);
+        application.render(&renderer, false);
+        click(&mut application, 0.5, 0.4);
+        assert_eq!(1, button_counter.get());
+        assert_eq!(vec![3], *changes.borrow());
+
+        // Selecting an item should close the list
+        application.render(&renderer, false);
+        click(&mut application, 0.5, 0.4);
+        assert_eq!(2, button_counter.get());
+
+        // Clicking outside the dropdown should close the list as well
+        click(&mut application, 0.5, 0.9);
+        application.render(&renderer, false);
+        click(&mut application, 0.5, 0.1);
+        assert_eq!(3, button_counter.get());
+        application.render(&renderer, false);
+        click(&mut application, 0.5, 0.4);
+        assert_eq!(4, button_counter.get());
+        assert_eq!(vec![3], *changes.borrow());
+    }
+
+    #[test]
+    fn test_dropdown_keyboard() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let dropdown_changes = Rc::clone(&changes);
+        let dropdown = Dropdown::new(
+            create_items(),
+            None,
+            Box::new(move |value, _buddy| dropdown_changes.borrow_mut().push(value)),
+        );
+        let mut application = Application::new(Box::new(dropdown));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 200, 200)), false);
+
+        // Pressing on the header should give the dropdown the focus
+        let mouse = Mouse::new(0);
+        let header = Point::new(0.5, 0.9);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, header));
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            header,
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            header,
+            MouseButton::primary(),
+        ));
+
+        let press = |application: &mut Application, key: Key| {
+            application.fire_key_press_event(KeyPressEvent::new(key, KeyModifiers::none()));
+        };
+
+        // While the list is closed, the arrow keys change the selection directly
+        press(&mut application, Key::Down);
+        press(&mut application, Key::Down);
+        assert_eq!(vec![1, 2], *changes.borrow());
+
+        // While the list is open, the arrow keys only move the highlighted item
+        press(&mut application, Key::Enter);
+        press(&mut application, Key::Up);
+        press(&mut application, Key::Up);
+        assert_eq!(vec![1, 2], *changes.borrow());
+        press(&mut application, Key::Enter);
+        assert_eq!(vec![1, 2, 1], *changes.borrow());
+
+        // Escape should close the list without selecting the highlighted item
+        press(&mut application, Key::Space);
+        press(&mut application, Key::End);
+        press(&mut application, Key::Escape);
+        press(&mut application, Key::Up);
+        assert_eq!(vec![1, 2, 1], *changes.borrow());
+    }
+}
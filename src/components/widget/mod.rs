@@ -0,0 +1,323 @@
+use crate::*;
+
+mod button;
+mod checkbox;
+mod dropdown;
+mod radio;
+mod slider;
+mod spinner;
+mod text_area;
+mod text_edit;
+mod text_field;
+mod toggle;
+
+pub use button::*;
+pub use checkbox::*;
+pub use dropdown::*;
+pub use radio::*;
+pub use slider::*;
+pub use spinner::*;
+pub use text_area::*;
+pub use text_field::*;
+pub use toggle::*;
+
+/// The callback of a `Button`, which will be called with the buddy of the button each time it
+/// is clicked
+pub type ClickHandler = Box<dyn FnMut(&mut dyn ComponentBuddy)>;
+
+/// The callback of a widget whose value can be changed by the user. It will be called with the
+/// new value and the buddy of the widget each time the user changes the value.
+pub type ChangeHandler<T> = Box<dyn FnMut(T, &mut dyn ComponentBuddy)>;
+
+/// The interaction state of a widget, which determines the color in which it is drawn
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WidgetState {
+    Normal,
+    /// At least 1 mouse is hovering over the widget
+    Hovered,
+    /// The primary button of a mouse was pressed while the mouse was hovering over the widget,
+    /// and it hasn't been released yet
+    Pressed,
+    /// The widget has been disabled, so it won't respond to clicks
+    Disabled,
+}
+
+/// The colors that are used to draw the widgets in this module. Each widget uses one of the
+/// background colors (depending on its `WidgetState`) for its main area, the `border` color for
+/// the outline of boxes and knobs, and the `accent` color to indicate that it is checked,
+/// selected or turned on.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WidgetColors {
+    pub normal: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+    pub disabled: Color,
+    pub border: Color,
+    pub accent: Color,
+}
+
+impl WidgetColors {
+    /// Gets the background color that should be used for the given `state`
+    pub fn get_background(&self, state: WidgetState) -> Color {
+        match state {
+            WidgetState::Normal => self.normal,
+            WidgetState::Hovered => self.hovered,
+            WidgetState::Pressed => self.pressed,
+            WidgetState::Disabled => self.disabled,
+        }
+    }
+}
+
+impl Default for WidgetColors {
+    fn default() -> Self {
+        Self {
+            normal: Color::rgb(210, 210, 210),
+            hovered: Color::rgb(230, 230, 230),
+            pressed: Color::rgb(170, 170, 170),
+            disabled: Color::rgb(140, 140, 140),
+            border: Color::rgb(60, 60, 60),
+            accent: Color::rgb(40, 110, 220),
+        }
+    }
+}
+
+/// The font that a widget uses to draw its text. Until the `set_font` method of the widget is
+/// called, the default font of the text renderer will be used.
+struct WidgetFont {
+    font: Option<FontHandle>,
+}
+
+impl WidgetFont {
+    fn new() -> Self {
+        Self { font: None }
+    }
+
+    fn set(&mut self, font: FontHandle) {
+        self.font = Some(font);
+    }
+
+    /// Gets the font that should be used to draw the text of the widget
+    fn get(&self, renderer: &Renderer) -> FontHandle {
+        self.font
+            .unwrap_or_else(|| renderer.get_text_renderer().get_default_font())
+    }
+}
+
+/// Keeps track of the mouses that are hovering over a widget and the mouse that is pressing it.
+/// All widgets in this module use this to determine their `WidgetState`.
+struct WidgetInput {
+    hovering: Vec<Mouse>,
+    pressing: Option<Mouse>,
+}
+
+impl WidgetInput {
+    fn new() -> Self {
+        Self {
+            hovering: Vec::new(),
+            pressing: None,
+        }
+    }
+
+    /// Subscribes the widget for all mouse events that this struct needs, as well as the
+    /// `MouseClickEvent`
+    fn subscribe(&self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_click();
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_release();
+        buddy.subscribe_mouse_enter();
+        buddy.subscribe_mouse_leave();
+    }
+
+    fn get_state(&self, enabled: bool) -> WidgetState {
+        if !enabled {
+            WidgetState::Disabled
+        } else if self.pressing.is_some() {
+            WidgetState::Pressed
+        } else if !self.hovering.is_empty() {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        }
+    }
+
+    fn mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.hovering.push(event.get_mouse());
+        buddy.request_render();
+    }
+
+    fn mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.hovering.retain(|mouse| *mouse != event.get_mouse());
+
+        // When the mouse leaves before the button is released, it shouldn't look pressed anymore
+        if self.pressing == Some(event.get_mouse()) {
+            self.pressing = None;
+        }
+        buddy.request_render();
+    }
+
+    fn mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() && self.pressing.is_none() {
+            self.pressing = Some(event.get_mouse());
+            buddy.request_render();
+        }
+    }
+
+    fn mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() && self.pressing == Some(event.get_mouse()) {
+            self.pressing = None;
+            buddy.request_render();
+        }
+    }
+}
+
+/// Checks whether a widget should respond to a `KeyPressEvent`. Key press events are sent to all
+/// subscribers rather than only to the focused component, so the widgets in this module ignore
+/// them unless they are enabled and have the keyboard focus.
+fn should_handle_key_press(enabled: bool, buddy: &dyn ComponentBuddy) -> bool {
+    enabled && buddy.has_focus()
+}
+
+/// The width (in pixels) of the border around the boxes of buttons and checkboxes
+const BORDER_WIDTH: f32 = 2.0;
+
+/// Fills the current viewport with the `border` color, and the area inside the border with the
+/// `background` color
+fn draw_bordered_box(renderer: &Renderer, border: Color, background: Color) {
+    renderer.clear(border);
+
+    let viewport = renderer.get_viewport();
+    let inset_x = BORDER_WIDTH / viewport.get_width() as f32;
+    let inset_y = BORDER_WIDTH / viewport.get_height() as f32;
+    renderer.push_viewport(inset_x, inset_y, 1.0 - inset_x, 1.0 - inset_y, || {
+        renderer.clear(background)
+    });
+}
+
+/// Fills the largest oval in the current viewport with the `border` color, and a slightly
+/// smaller oval with the `inner` color
+#[cfg(feature = "golem_rendering")]
+fn draw_bordered_oval(
+    renderer: &Renderer,
+    border: Color,
+    inner: Color,
+) -> Result<(), golem::GolemError> {
+    renderer.fill_oval(border)?;
+    renderer
+        .push_viewport(0.15, 0.15, 0.85, 0.85, || renderer.fill_oval(inner))
+        .unwrap_or(Ok(()))
+}
+
+/// Converts the given area of a widget (in the coordinate system of components) to a
+/// `TextDrawPosition` for its label. The label will be centered vertically.
+fn label_position(
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+    horizontal_alignment: HorizontalTextAlignment,
+) -> TextDrawPosition {
+    // The text renderer uses the OpenGL coordinate system, which goes from -1.0 to 1.0
+    TextDrawPosition {
+        min_x: 2.0 * min_x - 1.0,
+        min_y: 2.0 * min_y - 1.0,
+        max_x: 2.0 * max_x - 1.0,
+        max_y: 2.0 * max_y - 1.0,
+        horizontal_alignment,
+        vertical_alignment: VerticalTextAlignment::Center,
+    }
+}
+
+/// Converts the position where the text renderer drew a label back to the coordinate system of
+/// components
+fn label_region(position: DrawnTextPosition) -> RectangularDrawnRegion {
+    RectangularDrawnRegion::new(
+        (position.min_x + 1.0) / 2.0,
+        (position.min_y + 1.0) / 2.0,
+        (position.max_x + 1.0) / 2.0,
+        (position.max_y + 1.0) / 2.0,
+    )
+}
+
+/// Clamps `value` between `min` and `max`, and rounds it to the nearest multiple of `step` above
+/// `min`. When `step` is not positive, the value will only be clamped.
+fn snap_value(value: f32, min: f32, max: f32, step: f32) -> f32 {
+    let value = match step > 0.0 {
+        true => min + ((value - min) / step).round() * step,
+        false => value,
+    };
+    value.max(min).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_widget_input() {
+        let mut buddy = RootComponentBuddy::new();
+        let mut input = WidgetInput::new();
+        let mouse1 = Mouse::new(1);
+        let mouse2 = Mouse::new(2);
+        let point = Point::new(0.5, 0.5);
+        assert_eq!(WidgetState::Normal, input.get_state(true));
+        assert_eq!(WidgetState::Disabled, input.get_state(false));
+
+        input.mouse_enter(MouseEnterEvent::new(mouse1, point), &mut buddy);
+        input.mouse_enter(MouseEnterEvent::new(mouse2, point), &mut buddy);
+        assert_eq!(WidgetState::Hovered, input.get_state(true));
+
+        // Only the primary button should press the widget
+        input.mouse_press(
+            MousePressEvent::new(mouse1, point, MouseButton::new(1)),
+            &mut buddy,
+        );
+        assert_eq!(WidgetState::Hovered, input.get_state(true));
+        input.mouse_press(
+            MousePressEvent::new(mouse1, point, MouseButton::primary()),
+            &mut buddy,
+        );
+        assert_eq!(WidgetState::Pressed, input.get_state(true));
+        assert_eq!(WidgetState::Disabled, input.get_state(false));
+
+        // Releasing another mouse shouldn't matter
+        input.mouse_release(
+            MouseReleaseEvent::new(mouse2, point, MouseButton::primary()),
+            &mut buddy,
+        );
+        assert_eq!(WidgetState::Pressed, input.get_state(true));
+
+        // Leaving with the pressing mouse should cancel the press
+        input.mouse_leave(MouseLeaveEvent::new(mouse1, point), &mut buddy);
+        assert_eq!(WidgetState::Hovered, input.get_state(true));
+        input.mouse_leave(MouseLeaveEvent::new(mouse2, point), &mut buddy);
+        assert_eq!(WidgetState::Normal, input.get_state(true));
+    }
+
+    #[test]
+    fn test_should_handle_key_press() {
+        let mut buddy = RootComponentBuddy::new();
+        assert!(!should_handle_key_press(true, &buddy));
+
+        buddy.set_focus(true);
+        assert!(should_handle_key_press(true, &buddy));
+        assert!(!should_handle_key_press(false, &buddy));
+    }
+
+    #[test]
+    fn test_snap_value() {
+        assert_eq!(30.0, snap_value(33.0, 0.0, 100.0, 10.0));
+        assert_eq!(40.0, snap_value(36.0, 0.0, 100.0, 10.0));
+        assert_eq!(100.0, snap_value(130.0, 0.0, 100.0, 10.0));
+        assert_eq!(0.0, snap_value(-20.0, 0.0, 100.0, 10.0));
+
+        // The steps start at the minimum value
+        assert_eq!(7.0, snap_value(6.0, 2.0, 20.0, 5.0));
+
+        // When the last step would exceed the maximum, the maximum should be used instead
+        assert_eq!(20.0, snap_value(19.5, 2.0, 20.0, 5.0));
+
+        // Without step, the value should only be clamped
+        assert_eq!(33.3, snap_value(33.3, 0.0, 100.0, 0.0));
+    }
+}
@@ -0,0 +1,250 @@
+use super::*;
+
+/// A group of radio buttons: a list of options of which at most 1 can be selected. The options
+/// are placed below each other, and each option is drawn as a circle with a text label to its
+/// right. When the user clicks on the circle or label of an option, that option will be selected
+/// and the `on_change` callback will be called with its index.
+pub struct RadioGroup {
+    options: Vec<String>,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    selected: Option<usize>,
+    on_change: ChangeHandler<usize>,
+
+    input: WidgetInput,
+}
+
+impl RadioGroup {
+    /// Constructs a new enabled `RadioGroup` with the given `options`. The option with index
+    /// `selected` will be selected initially (if it is not `None`).
+    pub fn new(
+        options: Vec<String>,
+        selected: Option<usize>,
+        on_change: ChangeHandler<usize>,
+    ) -> Self {
+        Self {
+            options,
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            selected,
+            on_change,
+            input: WidgetInput::new(),
+        }
+    }
+
+    /// Sets the font of the labels
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this radio group. The user can't change the selected option of a
+    /// disabled radio group. This should be called before the radio group is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Gets the index of the option in the row that contains the given point
+    fn get_row(&self, point: Point) -> usize {
+        let row = ((1.0 - point.get_y()) * self.options.len() as f32) as usize;
+        row.min(self.options.len().max(1) - 1)
+    }
+
+    #[cfg(feature = "golem_rendering")]
+    fn get_row_state(&self, row: usize, buddy: &dyn ComponentBuddy) -> WidgetState {
+        let is_in_row = |mouse: Mouse| {
+            buddy
+                .get_mouse_position(mouse)
+                .map(|position| self.get_row(position) == row)
+                .unwrap_or(false)
+        };
+
+        if !self.enabled {
+            WidgetState::Disabled
+        } else if self.input.pressing.map(is_in_row).unwrap_or(false) {
+            WidgetState::Pressed
+        } else if self.input.hovering.iter().any(|mouse| is_in_row(*mouse)) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        }
+    }
+}
+
+impl Component for RadioGroup {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+
+        // Moving from 1 option to another changes which option is hovered
+        buddy.subscribe_mouse_move();
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The circles should stay round, so the drawn region depends on the aspect ratio
+        buddy.request_render();
+    }
+
+    #[cfg_attr(not(feature = "golem_rendering"), allow(unused_variables))]
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        let viewport = renderer.get_viewport();
+        let width = viewport.get_width() as f32;
+        let row_height = 1.0 / self.options.len() as f32;
+        let row_pixels = row_height * viewport.get_height() as f32;
+
+        // Like the box of a checkbox, the size of the circles is 80% of the height of a row
+        let circle_size = (0.8 * row_pixels).min(width);
+        let circle_width = circle_size / width;
+        let circle_height = row_height * circle_size / row_pixels;
+        let label_min_x = 1.3 * circle_width;
+
+        let font = self.font.get(renderer);
+
+        let mut drawn_regions: Vec<Box<dyn DrawnRegion>> = Vec::new();
+        for (row, option) in self.options.iter().enumerate() {
+            let center_y = 1.0 - (row as f32 + 0.5) * row_height;
+            let min_y = center_y - 0.5 * circle_height;
+            let max_y = center_y + 0.5 * circle_height;
+
+            #[cfg(feature = "golem_rendering")]
+            {
+                let state = self.get_row_state(row, buddy);
+                let background = self.colors.get_background(state);
+                let selected = self.selected == Some(row);
+                renderer
+                    .push_viewport(0.0, min_y, circle_width, max_y, || {
+                        draw_bordered_oval(renderer, self.colors.border, background)?;
+                        match selected {
+                            true => renderer
+                                .push_viewport(0.3, 0.3, 0.7, 0.7, || {
+                                    renderer.fill_oval(self.colors.accent)
+                                })
+                                .unwrap_or(Ok(())),
+                            false => Ok(()),
+                        }
+                    })
+                    .unwrap_or(Ok(()))?;
+            }
+
+            drawn_regions.push(Box::new(OvalDrawnRegion::new(
+                Point::new(0.5 * circle_width, center_y),
+                0.5 * circle_width,
+                0.5 * circle_height,
+            )));
+
+            if !option.is_empty() && label_min_x < 1.0 {
+                let drawn_label = renderer.get_text_renderer().draw_text(
+                    option,
+                    font,
+                    label_position(
+                        label_min_x,
+                        min_y,
+                        1.0,
+                        max_y,
+                        HorizontalTextAlignment::Left,
+                    ),
+                    renderer,
+                )?;
+                drawn_regions.push(Box::new(label_region(drawn_label)));
+            }
+        }
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(CompositeDrawnRegion::new(drawn_regions)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() && !self.options.is_empty() {
+            let row = self.get_row(event.get_point());
+            if self.selected != Some(row) {
+                self.selected = Some(row);
+                (self.on_change)(row, buddy);
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.get_row(event.get_from()) != self.get_row(event.get_to()) {
+            buddy.request_render();
+        }
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_radio_group() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let radio_changes = Rc::clone(&changes);
+        let radio_group = RadioGroup::new(
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            Some(0),
+            Box::new(move |selected, _buddy| radio_changes.borrow_mut().push(selected)),
+        );
+        let mut application = Application::new(Box::new(radio_group));
+
+        // Each row will be 500 by 50 pixels, and each circle will be 40 by 40 pixels
+        application.render(&test_renderer(RenderRegion::between(0, 0, 500, 150)), false);
+        let click = |application: &mut Application, x: f32, y: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, y),
+                MouseButton::primary(),
+            ));
+        };
+
+        // Click on the circle of the last option
+        click(&mut application, 0.04, 1.0 / 6.0);
+        assert_eq!(vec![2], *changes.borrow());
+
+        // Clicking on the selected option again shouldn't fire an event
+        click(&mut application, 0.04, 1.0 / 6.0);
+        assert_eq!(vec![2], *changes.borrow());
+
+        // Clicking in the corner of the box around the circle should be ignored
+        click(&mut application, 0.002, 0.5 + 0.4 / 3.0);
+        click(&mut application, 0.9, 0.5);
+        assert_eq!(vec![2], *changes.borrow());
+
+        // Click on the label of the middle option
+        click(&mut application, 0.11, 0.5);
+        assert_eq!(vec![2, 1], *changes.borrow());
+    }
+}
@@ -0,0 +1,431 @@
+use super::*;
+
+/// The direction in which the thumb of a `Slider` moves
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SliderOrientation {
+    /// The minimum value is on the left and the maximum value is on the right
+    Horizontal,
+    /// The minimum value is at the bottom and the maximum value is at the top
+    Vertical,
+}
+
+/// The number of steps that Page Up and Page Down move the thumb of a `Slider`
+const PAGE_STEPS: f32 = 10.0;
+
+/// A slider: a track with a round thumb that the user can drag to choose a value between a
+/// minimum and a maximum value. Pressing on the track outside the thumb will move the thumb to
+/// that position, after which it can be dragged further.
+///
+/// When the slider has the keyboard focus, the value can also be nudged with the arrow keys (1
+/// step), Page Up and Page Down (10 steps), and Home and End (the minimum and maximum value).
+///
+/// If a *step* is set, the value will always be snapped to a multiple of the step above the
+/// minimum value. Each time the user changes the value, the `on_change` callback will be called
+/// with the new value.
+pub struct Slider {
+    orientation: SliderOrientation,
+    min: f32,
+    max: f32,
+    step: f32,
+    value: f32,
+    colors: WidgetColors,
+    enabled: bool,
+    on_change: ChangeHandler<f32>,
+
+    input: WidgetInput,
+    // The mouse that is dragging the thumb, and the distance from the mouse to the thumb center
+    dragging: Option<(Mouse, f32)>,
+    // Half of the size of the thumb (along the track) during the last render
+    thumb_radius: f32,
+}
+
+impl Slider {
+    /// Constructs a new enabled `Slider` without step. The initial `value` will be clamped
+    /// between `min` and `max`.
+    pub fn new(
+        orientation: SliderOrientation,
+        min: f32,
+        max: f32,
+        value: f32,
+        on_change: ChangeHandler<f32>,
+    ) -> Self {
+        Self {
+            orientation,
+            min,
+            max,
+            step: 0.0,
+            value: snap_value(value, min, max, 0.0),
+            colors: WidgetColors::default(),
+            enabled: true,
+            on_change,
+            input: WidgetInput::new(),
+            dragging: None,
+            thumb_radius: 0.0,
+        }
+    }
+
+    /// Sets the step of this slider, and snaps the current value to it. A step of 0 means that
+    /// the value can be anything between the minimum and maximum value.
+    pub fn set_step(&mut self, step: f32) {
+        self.step = step;
+        self.value = snap_value(self.value, self.min, self.max, step);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this slider. The user can't change the value of a disabled slider.
+    /// This should be called before the slider is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value
+    }
+
+    /// Gets the coordinate of `point` along the track
+    fn get_axis(&self, point: Point) -> f32 {
+        match self.orientation {
+            SliderOrientation::Horizontal => point.get_x(),
+            SliderOrientation::Vertical => point.get_y(),
+        }
+    }
+
+    /// Gets the coordinate of the center of the thumb along the track
+    fn get_thumb_center(&self) -> f32 {
+        let fraction = match self.max > self.min {
+            true => (self.value - self.min) / (self.max - self.min),
+            false => 0.0,
+        };
+        self.thumb_radius + fraction * (1.0 - 2.0 * self.thumb_radius)
+    }
+
+    /// Gets the value that corresponds to the given coordinate along the track
+    fn get_value_at(&self, axis: f32) -> f32 {
+        let track_length = 1.0 - 2.0 * self.thumb_radius;
+        let fraction = match track_length > 0.0 {
+            true => ((axis - self.thumb_radius) / track_length).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        self.min + fraction * (self.max - self.min)
+    }
+
+    /// Converts a region with the given coordinates along and across the track to the
+    /// coordinate system of components
+    fn to_region(
+        &self,
+        min_along: f32,
+        min_across: f32,
+        max_along: f32,
+        max_across: f32,
+    ) -> [f32; 4] {
+        match self.orientation {
+            SliderOrientation::Horizontal => [min_along, min_across, max_along, max_across],
+            SliderOrientation::Vertical => [min_across, min_along, max_across, max_along],
+        }
+    }
+
+    fn change_value(&mut self, value: f32, buddy: &mut dyn ComponentBuddy) {
+        let value = snap_value(value, self.min, self.max, self.step);
+        if value != self.value {
+            self.value = value;
+            (self.on_change)(value, buddy);
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for Slider {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_release();
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_enter();
+        buddy.subscribe_mouse_leave();
+        buddy.subscribe_key_press();
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The thumb should stay round, so its size depends on the aspect ratio
+        buddy.request_render();
+    }
+
+    #[cfg_attr(not(feature = "golem_rendering"), allow(unused_variables))]
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        let viewport = renderer.get_viewport();
+        let (along_pixels, across_pixels) = match self.orientation {
+            SliderOrientation::Horizontal => (viewport.get_width(), viewport.get_height()),
+            SliderOrientation::Vertical => (viewport.get_height(), viewport.get_width()),
+        };
+
+        // The diameter of the thumb is the width or height of the slider (whichever is smaller)
+        let thumb_size = along_pixels.min(across_pixels) as f32;
+        self.thumb_radius = 0.5 * thumb_size / along_pixels as f32;
+        let thumb_center = self.get_thumb_center();
+        let across_radius = 0.5 * thumb_size / across_pixels as f32;
+        let track_radius = across_radius / 3.0;
+
+        // The part of the track before the thumb is filled with the accent color
+        let filled_color = match self.enabled {
+            true => self.colors.accent,
+            false => self.colors.disabled,
+        };
+        let [min_x, min_y, max_x, max_y] = self.to_region(
+            self.thumb_radius,
+            0.5 - track_radius,
+            thumb_center,
+            0.5 + track_radius,
+        );
+        renderer.push_viewport(min_x, min_y, max_x, max_y, || renderer.clear(filled_color));
+        let [min_x, min_y, max_x, max_y] = self.to_region(
+            thumb_center,
+            0.5 - track_radius,
+            1.0 - self.thumb_radius,
+            0.5 + track_radius,
+        );
+        renderer.push_viewport(min_x, min_y, max_x, max_y, || {
+            renderer.clear(self.colors.border)
+        });
+
+        #[cfg(feature = "golem_rendering")]
+        {
+            let thumb_border = match buddy.has_focus() {
+                true => self.colors.accent,
+                false => self.colors.border,
+            };
+            let thumb_color = self
+                .colors
+                .get_background(self.input.get_state(self.enabled));
+            let [min_x, min_y, max_x, max_y] = self.to_region(
+                thumb_center - self.thumb_radius,
+                0.5 - across_radius,
+                thumb_center + self.thumb_radius,
+                0.5 + across_radius,
+            );
+            renderer
+                .push_viewport(min_x, min_y, max_x, max_y, || {
+                    draw_bordered_oval(renderer, thumb_border, thumb_color)
+                })
+                .unwrap_or(Ok(()))?;
+        }
+
+        // The track and thumb span the entire length of the slider
+        let [min_x, min_y, max_x, max_y] =
+            self.to_region(0.0, 0.5 - across_radius, 1.0, 0.5 + across_radius);
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(min_x, min_y, max_x, max_y)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !self.enabled || !event.get_button().is_primary() || self.dragging.is_some() {
+            return;
+        }
+
+        buddy.request_focus();
+        self.input.mouse_press(event, buddy);
+
+        // When the user presses on the thumb, it shouldn't jump to the mouse position
+        let axis = self.get_axis(event.get_point());
+        let thumb_offset = axis - self.get_thumb_center();
+        let offset = match thumb_offset.abs() <= self.thumb_radius {
+            true => thumb_offset,
+            false => {
+                self.change_value(self.get_value_at(axis), buddy);
+                0.0
+            }
+        };
+        self.dragging = Some((event.get_mouse(), offset));
+        buddy.capture_mouse(event.get_mouse());
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some((mouse, offset)) = self.dragging {
+            if mouse == event.get_mouse() {
+                let axis = self.get_axis(event.get_to()) - offset;
+                self.change_value(self.get_value_at(axis), buddy);
+            }
+        }
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+        if let Some((mouse, _)) = self.dragging {
+            if mouse == event.get_mouse() && event.get_button().is_primary() {
+                self.dragging = None;
+                buddy.release_mouse_capture(mouse);
+            }
+        }
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !should_handle_key_press(self.enabled, buddy) {
+            return;
+        }
+
+        // Without step, each key press moves the thumb 1% of the track
+        let step = match self.step > 0.0 {
+            true => self.step,
+            false => (self.max - self.min) / 100.0,
+        };
+        let new_value = match event.get_key() {
+            Key::Left | Key::Down => self.value - step,
+            Key::Right | Key::Up => self.value + step,
+            Key::PageDown => self.value - PAGE_STEPS * step,
+            Key::PageUp => self.value + PAGE_STEPS * step,
+            Key::Home => self.min,
+            Key::End => self.max,
+            _ => return,
+        };
+        self.change_value(new_value, buddy);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+
+    fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn drag(application: &mut Application, from: Point, to: Point) {
+        let mouse = Mouse::new(0);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, from));
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            from,
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, from, to));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            to,
+            MouseButton::primary(),
+        ));
+    }
+
+    #[test]
+    fn test_horizontal_slider() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let slider_changes = Rc::clone(&changes);
+        let mut slider = Slider::new(
+            SliderOrientation::Horizontal,
+            0.0,
+            100.0,
+            0.0,
+            Box::new(move |value, _buddy| slider_changes.borrow_mut().push(value)),
+        );
+        slider.set_step(10.0);
+        let mut application = Application::new(Box::new(slider));
+
+        // The thumb will be 20 by 20 pixels, so the track goes from x = 0.05 to x = 0.95
+        application.render(&test_renderer(RenderRegion::between(0, 0, 200, 20)), false);
+
+        // Pressing on the track should move the thumb, and dragging it should move it further
+        drag(
+            &mut application,
+            Point::new(0.5, 0.5),
+            Point::new(0.68, 0.5),
+        );
+        assert_eq!(vec![50.0, 70.0], *changes.borrow());
+
+        // Dragging the thumb shouldn't make it jump to the mouse position, and it can't go past
+        // the end of the track
+        drag(&mut application, Point::new(0.7, 0.5), Point::new(1.5, 0.5));
+        assert_eq!(vec![50.0, 70.0, 100.0], *changes.borrow());
+
+        // The slider got the focus, so it can be nudged with the keyboard
+        let none = KeyModifiers::none();
+        application.fire_key_press_event(KeyPressEvent::new(Key::Left, none));
+        application.fire_key_press_event(KeyPressEvent::new(Key::Up, none));
+        application.fire_key_press_event(KeyPressEvent::new(Key::Up, none));
+        application.fire_key_press_event(KeyPressEvent::new(Key::Home, none));
+        assert_eq!(vec![50.0, 70.0, 100.0, 90.0, 100.0, 0.0], *changes.borrow());
+    }
+
+    #[test]
+    fn test_vertical_slider() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let slider_changes = Rc::clone(&changes);
+        let slider = Slider::new(
+            SliderOrientation::Vertical,
+            -1.0,
+            1.0,
+            5.0,
+            Box::new(move |value, _buddy| slider_changes.borrow_mut().push(value)),
+        );
+        assert_eq!(1.0, slider.get_value());
+        let mut application = Application::new(Box::new(slider));
+
+        // The thumb will be 20 by 20 pixels, so the track goes from y = 0.05 to y = 0.95
+        application.render(&test_renderer(RenderRegion::between(0, 0, 20, 200)), false);
+
+        // Without step, the value can be anything between the minimum and the maximum
+        drag(
+            &mut application,
+            Point::new(0.5, 0.95),
+            Point::new(0.5, 0.5),
+        );
+        drag(
+            &mut application,
+            Point::new(0.5, 0.5),
+            Point::new(0.5, 0.14),
+        );
+        let changes = changes.borrow();
+        assert_eq!(2, changes.len());
+        assert!(changes[0].abs() < 0.001);
+        assert!((changes[1] + 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_disabled_slider() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let slider_changes = Rc::clone(&changes);
+        let mut slider = Slider::new(
+            SliderOrientation::Horizontal,
+            0.0,
+            1.0,
+            0.0,
+            Box::new(move |value, _buddy| slider_changes.borrow_mut().push(value)),
+        );
+        slider.set_enabled(false);
+        let mut application = Application::new(Box::new(slider));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 200, 20)), false);
+
+        drag(
+            &mut application,
+            Point::new(0.05, 0.5),
+            Point::new(0.9, 0.5),
+        );
+        application.fire_key_press_event(KeyPressEvent::new(Key::End, KeyModifiers::none()));
+        assert!(changes.borrow().is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+use super::*;
+
+/// The number of steps that Page Up and Page Down change the value of a `Spinner`
+const PAGE_STEPS: f32 = 10.0;
+
+/// The parts of a `Spinner` that the user can point at
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum SpinnerPart {
+    Decrement,
+    Value,
+    Increment,
+}
+
+/// A numeric spinner: a text showing a number between a decrement button (on the left) and an
+/// increment button (on the right). Clicking on the buttons will decrease or increase the value
+/// by 1 step, but never below the minimum value or above the maximum value.
+///
+/// The value can also be changed by scrolling over the spinner, and with the arrow keys (1 step),
+/// Page Up and Page Down (10 steps), and Home and End (the minimum and maximum value) when the
+/// spinner has the keyboard focus.
+///
+/// Each time the user changes the value, the `on_change` callback will be called with the new
+/// value.
+pub struct Spinner {
+    min: f32,
+    max: f32,
+    step: f32,
+    value: f32,
+    decimals: usize,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    on_change: ChangeHandler<f32>,
+
+    input: WidgetInput,
+    // The width of each button during the last render
+    button_width: f32,
+}
+
+/// Gets the number of decimals that is needed to show all multiples of `step` (at most 6)
+fn get_decimals(step: f32) -> usize {
+    (0..6)
+        .find(|decimals| {
+            let scaled = step * 10f32.powi(*decimals as i32);
+            (scaled - scaled.round()).abs() < 0.001
+        })
+        .unwrap_or(6)
+}
+
+impl Spinner {
+    /// Constructs a new enabled `Spinner` whose value will go from `min` to `max` in steps of
+    /// `step`. The initial `value` will be snapped to a multiple of the step above `min`.
+    ///
+    /// The number of decimals that is shown will be derived from the `step`, but it can be
+    /// changed with the `set_decimals` method.
+    pub fn new(min: f32, max: f32, step: f32, value: f32, on_change: ChangeHandler<f32>) -> Self {
+        Self {
+            min,
+            max,
+            step,
+            value: snap_value(value, min, max, step),
+            decimals: get_decimals(step),
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            on_change,
+            input: WidgetInput::new(),
+            button_width: 0.0,
+        }
+    }
+
+    /// Sets the number of decimals that will be shown after the decimal point
+    pub fn set_decimals(&mut self, decimals: usize) {
+        self.decimals = decimals;
+    }
+
+    /// Sets the font of the value and buttons
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this spinner. The user can't change the value of a disabled spinner.
+    /// This should be called before the spinner is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value
+    }
+
+    fn get_part(&self, point: Point) -> SpinnerPart {
+        if point.get_x() < self.button_width {
+            SpinnerPart::Decrement
+        } else if point.get_x() > 1.0 - self.button_width {
+            SpinnerPart::Increment
+        } else {
+            SpinnerPart::Value
+        }
+    }
+
+    fn get_part_state(&self, part: SpinnerPart, buddy: &dyn ComponentBuddy) -> WidgetState {
+        let is_in_part = |mouse: Mouse| {
+            buddy
+                .get_mouse_position(mouse)
+                .map(|position| self.get_part(position) == part)
+                .unwrap_or(false)
+        };
+
+        if !self.enabled {
+            WidgetState::Disabled
+        } else if self.input.pressing.map(is_in_part).unwrap_or(false) {
+            WidgetState::Pressed
+        } else if self.input.hovering.iter().any(|mouse| is_in_part(*mouse)) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        }
+    }
+
+    fn change_value(&mut self, value: f32, buddy: &mut dyn ComponentBuddy) {
+        let value = snap_value(value, self.min, self.max, self.step);
+        if value != self.value {
+            self.value = value;
+            (self.on_change)(value, buddy);
+            buddy.request_render();
+        }
+    }
+}
+
+impl Component for Spinner {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+
+        // Moving from 1 button to the other changes which button is hovered
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_scroll();
+        buddy.subscribe_key_press();
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The buttons should stay square, so their width depends on the aspect ratio
+        buddy.request_render();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        // The buttons are square, but each of them can take at most 1 third of the width
+        let viewport = renderer.get_viewport();
+        let width = viewport.get_width() as f32;
+        let height = viewport.get_height() as f32;
+        self.button_width = height.min(width / 3.0) / width;
+
+        let state = match self.enabled {
+            true => WidgetState::Normal,
+            false => WidgetState::Disabled,
+        };
+        let border = match buddy.has_focus() {
+            true => self.colors.accent,
+            false => self.colors.border,
+        };
+        draw_bordered_box(renderer, border, self.colors.get_background(state));
+
+        let font = self.font.get(renderer);
+        let buttons = [
+            (SpinnerPart::Decrement, 0.0, self.button_width, "-"),
+            (SpinnerPart::Increment, 1.0 - self.button_width, 1.0, "+"),
+        ];
+        for (part, min_x, max_x, label) in buttons.iter() {
+            let background = self
+                .colors
+                .get_background(self.get_part_state(*part, buddy));
+            renderer
+                .push_viewport(*min_x, 0.0, *max_x, 1.0, || {
+                    draw_bordered_box(renderer, self.colors.border, background);
+                    renderer
+                        .get_text_renderer()
+                        .draw_text(
+                            label,
+                            font,
+                            label_position(0.2, 0.2, 0.8, 0.8, HorizontalTextAlignment::Center),
+                            renderer,
+                        )
+                        .map(|_| ())
+                })
+                .unwrap_or(Ok(()))?;
+        }
+
+        renderer.get_text_renderer().draw_text(
+            &format!("{:.*}", self.decimals, self.value),
+            font,
+            label_position(
+                self.button_width,
+                0.2,
+                1.0 - self.button_width,
+                0.8,
+                HorizontalTextAlignment::Center,
+            ),
+            renderer,
+        )?;
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            match self.get_part(event.get_point()) {
+                SpinnerPart::Decrement => self.change_value(self.value - self.step, buddy),
+                SpinnerPart::Increment => self.change_value(self.value + self.step, buddy),
+                SpinnerPart::Value => {}
+            }
+        }
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            buddy.request_focus();
+        }
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.get_part(event.get_from()) != self.get_part(event.get_to()) {
+            buddy.request_render();
+        }
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        // Scrolling up increases the value
+        if self.enabled && event.get_delta_y() != 0.0 {
+            let steps = event.get_delta_y().signum();
+            self.change_value(self.value + steps * self.step, buddy);
+        }
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !should_handle_key_press(self.enabled, buddy) {
+            return;
+        }
+
+        let new_value = match event.get_key() {
+            Key::Left | Key::Down => self.value - self.step,
+            Key::Right | Key::Up => self.value + self.step,
+            Key::PageDown => self.value - PAGE_STEPS * self.step,
+            Key::PageUp => self.value + PAGE_STEPS * self.step,
+            Key::Home => self.min,
+            Key::End => self.max,
+            _ => return,
+        };
+        self.change_value(new_value, buddy);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+
+    fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::get_decimals;
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn click(application: &mut Application, x: f32) {
+        let mouse = Mouse::new(0);
+        let point = Point::new(x, 0.5);
+        let button = MouseButton::primary();
+        application.fire_mouse_press_event(MousePressEvent::new(mouse, point, button));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(mouse, point, button));
+        application.fire_mouse_click_event(MouseClickEvent::new(mouse, point, button));
+    }
+
+    #[test]
+    fn test_spinner() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let spinner_changes = Rc::clone(&changes);
+        let spinner = Spinner::new(
+            0.0,
+            1.0,
+            0.25,
+            0.4,
+            Box::new(move |value, _buddy| spinner_changes.borrow_mut().push(value)),
+        );
+        assert_eq!(0.5, spinner.get_value());
+        let mut application = Application::new(Box::new(spinner));
+
+        // Each button will be 100 by 100 pixels
+        application.render(&test_renderer(RenderRegion::between(0, 0, 300, 100)), false);
+        application
+            .fire_mouse_enter_event(MouseEnterEvent::new(Mouse::new(0), Point::new(0.5, 0.5)));
+
+        click(&mut application, 0.9);
+        click(&mut application, 0.9);
+
+        // The value can't exceed the maximum, and clicking on the value shouldn't do anything
+        click(&mut application, 0.9);
+        click(&mut application, 0.5);
+        click(&mut application, 0.1);
+        assert_eq!(vec![0.75, 1.0, 0.75], *changes.borrow());
+
+        // The spinner got the focus when it was pressed
+        let none = KeyModifiers::none();
+        application.fire_key_press_event(KeyPressEvent::new(Key::Down, none));
+        application.fire_key_press_event(KeyPressEvent::new(Key::End, none));
+        application.fire_mouse_scroll_event(MouseScrollEvent::new(
+            Mouse::new(0),
+            Point::new(0.5, 0.5),
+            0.0,
+            -3.0,
+        ));
+        assert_eq!(vec![0.75, 1.0, 0.75, 0.5, 1.0, 0.75], *changes.borrow());
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(0, get_decimals(1.0));
+        assert_eq!(0, get_decimals(5.0));
+        assert_eq!(1, get_decimals(0.1));
+        assert_eq!(1, get_decimals(0.5));
+        assert_eq!(2, get_decimals(0.25));
+    }
+}
@@ -0,0 +1,865 @@
+use super::text_edit::TextEdit;
+use super::*;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The space (in pixels) between the border of a `TextArea` and its text
+const PADDING: f32 = 5.0;
+
+/// The width (in pixels) of the cursor of a `TextArea`
+const CURSOR_WIDTH: f32 = 2.0;
+
+/// The default height (in pixels) of each row of a `TextArea`
+const DEFAULT_LINE_HEIGHT: f32 = 20.0;
+
+/// The fraction of the line height that is left empty above and below the text of each row
+const LINE_MARGIN: f32 = 0.1;
+
+/// A multi-line text area that the user can type in. Like the `TextField`, clicking on it will
+/// give it the keyboard focus and move its cursor, and dragging the mouse will select text.
+///
+/// Lines that are wider than the text area will be *soft-wrapped*: they will be split into
+/// multiple rows, preferably after a whitespace. Pressing Enter inserts a newline, and the cursor
+/// can be moved between rows with the Up and Down keys (or Page Up and Page Down). Home and End
+/// move the cursor to the start or end of its row, or to the start or end of the entire text
+/// when control is held. Pressing Escape will release the focus.
+///
+/// When there are more rows than fit in the text area, the user can scroll vertically with the
+/// mouse wheel, and the text area will automatically scroll to the cursor when it is moved.
+///
+/// Each time the user changes the text, the `on_change` callback will be called with the new
+/// text. Like the `TextField`, it will fall back to the `request_text_input` method of its buddy
+/// when there is no keyboard.
+///
+/// ### Partial rendering
+/// The text area remembers what it drew in each row during the previous render. When it needs
+/// to render again (and is not forced to redraw everything), it will use `push_scissor` to
+/// redraw only the rows that changed. Typing in a long text will thus typically only redraw the
+/// row that contains the cursor.
+pub struct TextArea {
+    edit: TextEdit,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    line_height: f32,
+    on_change: ChangeHandler<String>,
+
+    keyboard_available: bool,
+    dragging: Option<Mouse>,
+
+    // The rows of the text (after wrapping) during the last render
+    rows: Vec<Row>,
+    // The size of the viewport (in pixels) during the last render
+    size: (f32, f32),
+    // How far the text is scrolled down (in pixels)
+    scroll_y: f32,
+    // When true, the next render will scroll to the row of the cursor
+    reveal_cursor: bool,
+    // The x-coordinate that Up and Down try to keep, in pixels from the start of the row
+    preferred_x: Option<f32>,
+    last_frame: Option<Frame>,
+}
+
+/// A row of a `TextArea`: (a part of) a line of the text that fits in the width of the text area
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    /// The grapheme index of the first grapheme in this row
+    start: usize,
+    /// The text in this row, without the newline
+    text: String,
+    /// The x-coordinates of the boundaries between the graphemes of this row, in pixels relative
+    /// to the start of the row. This has 1 more element than the number of graphemes.
+    positions: Vec<f32>,
+    /// True if this row is the last row of its line, and false if the line was wrapped here
+    ends_line: bool,
+}
+
+impl Row {
+    fn new(
+        line_start: usize,
+        graphemes: &[&str],
+        positions: &[f32],
+        start: usize,
+        end: usize,
+        ends_line: bool,
+    ) -> Self {
+        Self {
+            start: line_start + start,
+            text: graphemes[start..end].concat(),
+            positions: positions[start..=end]
+                .iter()
+                .map(|position| position - positions[start])
+                .collect(),
+            ends_line,
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.positions.len() - 1
+    }
+
+    /// Gets the largest grapheme index of the cursor that would be shown in this row. The end of
+    /// a wrapped row is shown at the start of the next row instead.
+    fn last_cursor(&self) -> usize {
+        match self.ends_line || self.end() == self.start {
+            true => self.end(),
+            false => self.end() - 1,
+        }
+    }
+
+    /// Gets the x-coordinate (in pixels relative to the start of the row) of the given grapheme
+    /// index, which should be in this row
+    fn get_position(&self, index: usize) -> f32 {
+        let local_index = index.saturating_sub(self.start);
+        self.positions[local_index.min(self.positions.len() - 1)]
+    }
+
+    /// Gets the grapheme index in this row whose position is closest to `x`
+    fn get_index(&self, x: f32) -> usize {
+        let mut best_index = self.start;
+        let mut best_distance = f32::INFINITY;
+        for (local_index, position) in self.positions.iter().enumerate() {
+            let distance = (position - x).abs();
+            if distance < best_distance {
+                best_index = self.start + local_index;
+                best_distance = distance;
+            }
+        }
+        best_index.min(self.last_cursor())
+    }
+}
+
+/// Splits `text` into rows whose width is at most `max_width`. The `measure` function should
+/// compute the x-coordinates of the grapheme boundaries of a (non-empty) line, in the same unit
+/// as `max_width`.
+///
+/// Lines are wrapped after the last whitespace that fits in the row. Whitespace at the end of a
+/// row may exceed the `max_width`. Words that are too long to fit in a row on their own will be
+/// split over multiple rows.
+fn compute_rows(text: &str, max_width: f32, mut measure: impl FnMut(&str) -> Vec<f32>) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        let graphemes: Vec<&str> = line.graphemes(true).collect();
+        let positions = match line.is_empty() {
+            true => vec![0.0],
+            false => measure(line),
+        };
+
+        let mut row_start = 0;
+        let mut wrap_point = None;
+        for (index, grapheme) in graphemes.iter().enumerate() {
+            let is_whitespace = grapheme.chars().all(char::is_whitespace);
+            let too_wide = positions[index + 1] - positions[row_start] > max_width;
+            if !is_whitespace && too_wide && index > row_start {
+                let row_end = match wrap_point {
+                    Some(wrap_point) if wrap_point > row_start => wrap_point,
+                    _ => index,
+                };
+                rows.push(Row::new(
+                    line_start, &graphemes, &positions, row_start, row_end, false,
+                ));
+                row_start = row_end;
+            }
+            if is_whitespace {
+                wrap_point = Some(index + 1);
+            }
+        }
+        rows.push(Row::new(
+            line_start,
+            &graphemes,
+            &positions,
+            row_start,
+            graphemes.len(),
+            true,
+        ));
+
+        // The newline counts as a grapheme
+        line_start += graphemes.len() + 1;
+    }
+    rows
+}
+
+/// Gets the index of the row in which the cursor would be shown if it were at the given grapheme
+/// index
+fn find_row(rows: &[Row], index: usize) -> usize {
+    rows.iter()
+        .position(|row| index >= row.start && index <= row.last_cursor())
+        .unwrap_or_else(|| rows.len().saturating_sub(1))
+}
+
+/// What a `TextArea` drew in a row during a render. If the appearance of a row didn't change, it
+/// doesn't need to be redrawn.
+#[derive(Clone, Debug, PartialEq)]
+struct RowAppearance {
+    text: String,
+    // The selected range of graphemes, relative to the start of the row
+    selection: Option<(usize, usize)>,
+    // The position of the cursor, relative to the start of the row
+    cursor: Option<usize>,
+}
+
+/// What a `TextArea` drew during a render. When any of the fields other than `rows` changes, the
+/// entire text area needs to be redrawn.
+#[derive(Clone, Debug, PartialEq)]
+struct Frame {
+    size: (f32, f32),
+    scroll_y: f32,
+    focused: bool,
+    first_row: usize,
+    rows: Vec<RowAppearance>,
+}
+
+impl Frame {
+    /// Gets the indices (relative to the first visible row) of the rows that need to be redrawn
+    /// when going from this frame to the `next` frame. Returns `None` if everything needs to be
+    /// redrawn.
+    fn get_changed_rows(&self, next: &Frame) -> Option<Vec<usize>> {
+        let same_layout = self.size == next.size
+            && self.scroll_y == next.scroll_y
+            && self.focused == next.focused
+            && self.first_row == next.first_row;
+        match same_layout {
+            true => Some(
+                (0..self.rows.len().max(next.rows.len()))
+                    .filter(|index| self.rows.get(*index) != next.rows.get(*index))
+                    .collect(),
+            ),
+            false => None,
+        }
+    }
+}
+
+impl TextArea {
+    /// Constructs a new enabled `TextArea` with the given initial `text`
+    pub fn new(text: String, on_change: ChangeHandler<String>) -> Self {
+        Self {
+            edit: TextEdit::new(text.replace("\r\n", "\n")),
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            line_height: DEFAULT_LINE_HEIGHT,
+            on_change,
+            keyboard_available: true,
+            dragging: None,
+            rows: Vec::new(),
+            size: (1.0, 1.0),
+            scroll_y: 0.0,
+            reveal_cursor: false,
+            preferred_x: None,
+            last_frame: None,
+        }
+    }
+
+    /// Sets the font of the text
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Sets the height of each row, in pixels. The size of the text depends on it.
+    pub fn set_line_height(&mut self, line_height: f32) {
+        self.line_height = line_height;
+    }
+
+    /// Enables or disables this text area. The user can't change the text of a disabled text
+    /// area, but can still scroll through it. This should be called before the text area is
+    /// attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_text(&self) -> &str {
+        self.edit.get_text()
+    }
+
+    /// Gets the grapheme index that is closest to the given point, using the rows of the last
+    /// render
+    fn get_grapheme_index(&self, point: Point) -> usize {
+        if self.rows.is_empty() {
+            return 0;
+        }
+        let (width, height) = self.size;
+        let y = (1.0 - point.get_y()) * height - PADDING + self.scroll_y;
+        let row = ((y / self.line_height).max(0.0) as usize).min(self.rows.len() - 1);
+        self.rows[row].get_index(point.get_x() * width - PADDING)
+    }
+
+    /// Moves the cursor `delta` rows down (or up when `delta` is negative), while trying to keep
+    /// its x-coordinate
+    fn move_rows(&mut self, delta: isize, selecting: bool) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let cursor = self.edit.get_cursor();
+        let row_index = find_row(&self.rows, cursor);
+        let row = &self.rows[row_index];
+        let x = *self
+            .preferred_x
+            .get_or_insert_with(|| row.get_position(cursor));
+
+        let target_row = row_index as isize + delta;
+        let new_cursor = if target_row < 0 {
+            0
+        } else if target_row as usize >= self.rows.len() {
+            self.edit.get_num_graphemes()
+        } else {
+            self.rows[target_row as usize].get_index(x)
+        };
+        self.edit.move_cursor(new_cursor, selecting);
+    }
+
+    /// Gets the number of rows that fit in the text area (at least 1)
+    fn get_visible_rows(&self) -> usize {
+        let inner_height = self.size.1 - 2.0 * PADDING;
+        ((inner_height / self.line_height) as usize).max(1)
+    }
+
+    fn notify_change(&mut self, buddy: &mut dyn ComponentBuddy) {
+        (self.on_change)(self.edit.get_text().to_string(), buddy);
+        self.reveal_cursor = true;
+        buddy.request_render();
+    }
+}
+
+impl Component for TextArea {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_release();
+        buddy.subscribe_mouse_scroll();
+        buddy.subscribe_mouse_click_out();
+
+        self.keyboard_available = buddy.subscribe_char_type().is_ok();
+        if self.keyboard_available {
+            buddy.subscribe_key_press();
+        }
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The width of the text area determines where the lines are wrapped
+        buddy.request_render();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        force: bool,
+    ) -> RenderResult {
+        let viewport = renderer.get_viewport();
+        let width = viewport.get_width() as f32;
+        let height = viewport.get_height() as f32;
+        let padding_x = PADDING / width;
+        let padding_y = PADDING / height;
+        let row_height = self.line_height / height;
+        let font = self.font.get(renderer);
+
+        // Like the text field, the height of the rows should be the only limit on the text size
+        let text_position = |min_y: f32| {
+            label_position(
+                padding_x,
+                min_y + LINE_MARGIN * row_height,
+                padding_x + 1000.0,
+                min_y + (1.0 - LINE_MARGIN) * row_height,
+                HorizontalTextAlignment::Left,
+            )
+        };
+
+        let measure = |line: &str| {
+            match renderer.get_text_renderer().get_grapheme_offsets(
+                line,
+                font,
+                text_position(0.0),
+                renderer,
+            ) {
+                Some(offsets) => offsets
+                    .iter()
+                    .map(|offset| 0.5 * (offset - offsets[0]) * width)
+                    .collect(),
+                // The text can't be drawn either, and draw_text will return the error below
+                None => vec![0.0; line.graphemes(true).count() + 1],
+            }
+        };
+        self.rows = compute_rows(self.edit.get_text(), width - 2.0 * PADDING, measure);
+        self.size = (width, height);
+
+        let cursor = self.edit.get_cursor();
+        let cursor_row = find_row(&self.rows, cursor);
+        let inner_height = height - 2.0 * PADDING;
+        if self.reveal_cursor {
+            let cursor_top = cursor_row as f32 * self.line_height;
+            self.scroll_y = self
+                .scroll_y
+                .min(cursor_top)
+                .max(cursor_top + self.line_height - inner_height);
+            self.reveal_cursor = false;
+        }
+        let content_height = self.rows.len() as f32 * self.line_height;
+        self.scroll_y = self
+            .scroll_y
+            .min((content_height - inner_height).max(0.0))
+            .max(0.0);
+
+        // Determine what each visible row should look like
+        let focused = buddy.has_focus();
+        let show_cursor = focused && self.enabled && self.keyboard_available;
+        let selection = self.edit.get_selection();
+        let first_row = (self.scroll_y / self.line_height) as usize;
+        let bound_row = (((self.scroll_y + inner_height) / self.line_height).ceil() as usize)
+            .min(self.rows.len());
+        let appearances = (first_row..bound_row.max(first_row))
+            .map(|row_index| {
+                let row = &self.rows[row_index];
+                let row_selection = selection.and_then(|(start, end)| {
+                    let start = start.max(row.start);
+                    let end = end.min(row.end());
+                    match start < end {
+                        true => Some((start - row.start, end - row.start)),
+                        false => None,
+                    }
+                });
+                RowAppearance {
+                    text: row.text.clone(),
+                    selection: row_selection,
+                    cursor: match show_cursor && row_index == cursor_row {
+                        true => Some(cursor - row.start),
+                        false => None,
+                    },
+                }
+            })
+            .collect();
+        let frame = Frame {
+            size: self.size,
+            scroll_y: self.scroll_y,
+            focused,
+            first_row,
+            rows: appearances,
+        };
+
+        // Only the rows whose appearance changed need to be redrawn, unless something changed
+        // that affects all rows
+        let changed_rows = match &self.last_frame {
+            Some(last_frame) if !force => last_frame.get_changed_rows(&frame),
+            _ => None,
+        };
+
+        let state = match self.enabled {
+            true => WidgetState::Normal,
+            false => WidgetState::Disabled,
+        };
+        let background = self.colors.get_background(state);
+        if changed_rows.is_none() {
+            let border = match focused {
+                true => self.colors.accent,
+                false => self.colors.border,
+            };
+            draw_bordered_box(renderer, border, background);
+        }
+
+        let cursor_width = CURSOR_WIDTH / width;
+        let get_row_bounds = |screen_index: usize| {
+            let row_top = PADDING + (first_row + screen_index) as f32 * self.line_height;
+            let max_y = 1.0 - (row_top - self.scroll_y) / height;
+            (max_y - row_height, max_y)
+        };
+        let draw_row = |screen_index: usize| {
+            let (min_y, max_y) = get_row_bounds(screen_index);
+            let appearance = match frame.rows.get(screen_index) {
+                Some(appearance) => appearance,
+                None => return Ok(()),
+            };
+            let row = &self.rows[first_row + screen_index];
+
+            if let Some((start, end)) = appearance.selection {
+                let min_x = padding_x + row.positions[start] / width;
+                let max_x = padding_x + row.positions[end] / width;
+                renderer.push_viewport(min_x, min_y, max_x, max_y, || {
+                    renderer.clear(self.colors.hovered)
+                });
+            }
+
+            let result = match row.text.is_empty() {
+                true => Ok(()),
+                false => renderer
+                    .get_text_renderer()
+                    .draw_text(&row.text, font, text_position(min_y), renderer)
+                    .map(|_| ()),
+            };
+
+            if let Some(cursor) = appearance.cursor {
+                let cursor_x = padding_x + row.positions[cursor] / width;
+                renderer.push_viewport(
+                    cursor_x - 0.5 * cursor_width,
+                    min_y,
+                    cursor_x + 0.5 * cursor_width,
+                    max_y,
+                    || renderer.clear(self.colors.border),
+                );
+            }
+            result
+        };
+
+        renderer
+            .push_scissor(
+                padding_x,
+                padding_y,
+                1.0 - padding_x,
+                1.0 - padding_y,
+                || match &changed_rows {
+                    Some(changed_rows) => changed_rows.iter().try_for_each(|screen_index| {
+                        let (min_y, max_y) = get_row_bounds(*screen_index);
+                        renderer
+                            .push_scissor(0.0, min_y, 1.0, max_y, || {
+                                renderer.clear(background);
+                                draw_row(*screen_index)
+                            })
+                            .unwrap_or(Ok(()))
+                    }),
+                    None => (0..frame.rows.len()).try_for_each(&draw_row),
+                },
+            )
+            .transpose()?;
+        self.last_frame = Some(frame);
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !self.enabled || !event.get_button().is_primary() {
+            return;
+        }
+
+        if !self.keyboard_available {
+            buddy.request_text_input(self.edit.get_text().to_string());
+            return;
+        }
+
+        buddy.request_focus();
+        let index = self.get_grapheme_index(event.get_point());
+        self.edit.move_cursor(index, buddy.is_key_down(Key::Shift));
+        self.preferred_x = None;
+        if self.dragging.is_none() {
+            self.dragging = Some(event.get_mouse());
+            buddy.capture_mouse(event.get_mouse());
+        }
+        buddy.request_render();
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.dragging == Some(event.get_mouse()) {
+            let index = self.get_grapheme_index(event.get_to());
+            if index != self.edit.get_cursor() {
+                self.edit.move_cursor(index, true);
+                self.reveal_cursor = true;
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() && self.dragging == Some(event.get_mouse()) {
+            self.dragging = None;
+            buddy.release_mouse_capture(event.get_mouse());
+        }
+    }
+
+    fn on_mouse_scroll(&mut self, event: MouseScrollEvent, buddy: &mut dyn ComponentBuddy) {
+        // A positive delta_y means that the user scrolled up, so the text should move down
+        self.scroll_y -= event.get_delta_y() * self.line_height;
+        buddy.request_render();
+    }
+
+    fn on_mouse_click_out(&mut self, _event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        buddy.release_focus();
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        // Newlines are inserted by the Enter key instead
+        let text = event.get_text();
+        if self.enabled && buddy.has_focus() && !text.chars().any(char::is_control) {
+            self.edit.insert(text);
+            self.preferred_x = None;
+            self.notify_change(buddy);
+        }
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !should_handle_key_press(self.enabled, buddy) {
+            return;
+        }
+
+        let modifiers = event.get_modifiers();
+        let selecting = modifiers.is_shift_down();
+        let control = modifiers.is_control_down() || modifiers.is_meta_down();
+        let cursor_row = find_row(&self.rows, self.edit.get_cursor());
+        match event.get_key() {
+            Key::Up => self.move_rows(-1, selecting),
+            Key::Down => self.move_rows(1, selecting),
+            Key::PageUp => self.move_rows(-(self.get_visible_rows() as isize), selecting),
+            Key::PageDown => self.move_rows(self.get_visible_rows() as isize, selecting),
+            key => {
+                self.preferred_x = None;
+                match key {
+                    Key::Left => self.edit.move_left(selecting),
+                    Key::Right => self.edit.move_right(selecting),
+                    Key::Home if control => self.edit.move_cursor(0, selecting),
+                    Key::End if control => self
+                        .edit
+                        .move_cursor(self.edit.get_num_graphemes(), selecting),
+                    Key::Home => match self.rows.get(cursor_row) {
+                        Some(row) => self.edit.move_cursor(row.start, selecting),
+                        None => self.edit.move_cursor(0, selecting),
+                    },
+                    Key::End => match self.rows.get(cursor_row) {
+                        Some(row) => self.edit.move_cursor(row.last_cursor(), selecting),
+                        None => self
+                            .edit
+                            .move_cursor(self.edit.get_num_graphemes(), selecting),
+                    },
+                    Key::Letter('A') if control => self.edit.select_all(),
+                    Key::Enter => {
+                        self.edit.insert("\n");
+                        self.notify_change(buddy);
+                    }
+                    Key::Backspace => {
+                        if self.edit.backspace() {
+                            self.notify_change(buddy);
+                        }
+                    }
+                    Key::Delete => {
+                        if self.edit.delete() {
+                            self.notify_change(buddy);
+                        }
+                    }
+                    Key::Escape => buddy.release_focus(),
+                    _ => return,
+                }
+            }
+        };
+        self.reveal_cursor = true;
+        buddy.request_render();
+    }
+
+    fn on_text_input(&mut self, event: &TextInputEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(text) = event.get_text() {
+            self.edit.set_text(text.replace("\r\n", "\n"));
+            self.preferred_x = None;
+            self.notify_change(buddy);
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled && self.keyboard_available
+    }
+
+    fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+
+    fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Measures each grapheme as 10 units wide
+    fn measure(line: &str) -> Vec<f32> {
+        let num_graphemes = line.graphemes(true).count();
+        (0..=num_graphemes)
+            .map(|index| 10.0 * index as f32)
+            .collect()
+    }
+
+    fn row_texts(rows: &[Row]) -> Vec<&str> {
+        rows.iter().map(|row| row.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_compute_rows() {
+        let rows = compute_rows("hello world foo\n\nab", 60.0, measure);
+        assert_eq!(vec!["hello ", "world ", "foo", "", "ab"], row_texts(&rows));
+        assert_eq!(
+            vec![0, 6, 12, 16, 17],
+            rows.iter().map(|row| row.start).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![false, false, true, true, true],
+            rows.iter().map(|row| row.ends_line).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0.0, 10.0, 20.0, 30.0], rows[2].positions);
+
+        // The end of a wrapped row should be shown at the start of the next row
+        assert_eq!(0, find_row(&rows, 5));
+        assert_eq!(1, find_row(&rows, 6));
+        assert_eq!(2, find_row(&rows, 15));
+        assert_eq!(3, find_row(&rows, 16));
+        assert_eq!(4, find_row(&rows, 19));
+
+        // Clicking after the end of a wrapped row should put the cursor before the whitespace
+        assert_eq!(5, rows[0].get_index(100.0));
+        assert_eq!(15, rows[2].get_index(100.0));
+        assert_eq!(13, rows[2].get_index(8.0));
+
+        // Words that are too long should be split
+        let rows = compute_rows("abcdefghij", 35.0, measure);
+        assert_eq!(vec!["abc", "def", "ghi", "j"], row_texts(&rows));
+
+        // Whitespace at the end of a row is allowed to exceed the max width
+        let rows = compute_rows("abc   d", 30.0, measure);
+        assert_eq!(vec!["abc   ", "d"], row_texts(&rows));
+    }
+
+    #[test]
+    fn test_changed_rows() {
+        let appearance = |text: &str, cursor: Option<usize>| RowAppearance {
+            text: text.to_string(),
+            selection: None,
+            cursor,
+        };
+        let frame = Frame {
+            size: (100.0, 100.0),
+            scroll_y: 0.0,
+            focused: true,
+            first_row: 0,
+            rows: vec![appearance("ab", Some(2)), appearance("cd", None)],
+        };
+
+        // Typing in the first row should only change the first row
+        let mut next_frame = frame.clone();
+        next_frame.rows[0] = appearance("abc", Some(3));
+        assert_eq!(Some(vec![0]), frame.get_changed_rows(&next_frame));
+
+        // Adding a row should only change the new row
+        next_frame.rows.push(appearance("", None));
+        assert_eq!(Some(vec![0, 2]), frame.get_changed_rows(&next_frame));
+        assert_eq!(Some(vec![]), frame.get_changed_rows(&frame));
+
+        // Scrolling affects all rows
+        next_frame = frame.clone();
+        next_frame.scroll_y = 10.0;
+        assert_eq!(None, frame.get_changed_rows(&next_frame));
+    }
+
+    fn type_text(application: &mut Application, text: &str) {
+        for character in text.chars() {
+            application.fire_char_type_event(CharTypeEvent::new(character.to_string()));
+        }
+    }
+
+    fn press_key(application: &mut Application, key: Key, modifiers: KeyModifiers) {
+        application.fire_key_press_event(KeyPressEvent::new(key, modifiers));
+        application.fire_key_release_event(KeyReleaseEvent::new(key, modifiers));
+    }
+
+    #[test]
+    fn test_text_area() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let area_changes = Rc::clone(&changes);
+        let text_area = TextArea::new(
+            String::new(),
+            Box::new(move |text, _buddy| area_changes.borrow_mut().push(text)),
+        );
+        let mut application = Application::new(Box::new(text_area));
+        let renderer = test_renderer(RenderRegion::between(0, 0, 400, 200));
+        application.render(&renderer, false);
+
+        let mouse = Mouse::new(0);
+        let point = Point::new(0.5, 0.5);
+        application.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            point,
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            point,
+            MouseButton::primary(),
+        ));
+
+        let none = KeyModifiers::none();
+        type_text(&mut application, "ab");
+        press_key(&mut application, Key::Enter, none);
+        type_text(&mut application, "ab");
+        assert_eq!("ab\nab", changes.borrow().last().unwrap());
+        application.render(&renderer, false);
+
+        // The cursor should keep its x-coordinate when moving to the previous row
+        press_key(&mut application, Key::Up, none);
+        type_text(&mut application, "X");
+        assert_eq!("abX\nab", changes.borrow().last().unwrap());
+        application.render(&renderer, false);
+
+        // Moving down from the last row should move the cursor to the end of the text
+        press_key(&mut application, Key::Down, none);
+        press_key(&mut application, Key::Down, none);
+        type_text(&mut application, "Y");
+        assert_eq!("abX\nabY", changes.borrow().last().unwrap());
+        application.render(&renderer, false);
+
+        // Backspace at the start of a row should join it with the previous row
+        press_key(&mut application, Key::Home, none);
+        press_key(&mut application, Key::Backspace, none);
+        assert_eq!("abXabY", changes.borrow().last().unwrap());
+
+        let control = KeyModifiers::new(false, true, false, false);
+        press_key(&mut application, Key::Home, control);
+        type_text(&mut application, "Z");
+        assert_eq!("ZabXabY", changes.borrow().last().unwrap());
+    }
+
+    #[test]
+    fn test_text_area_scrolling() {
+        let text = (0..10)
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut text_area = TextArea::new(text, Box::new(|_text, _buddy| {}));
+        let mut buddy = RootComponentBuddy::new();
+        buddy.set_keyboard_store(Rc::new(RefCell::new(KeyboardStore::new())));
+        text_area.on_attach(&mut buddy);
+        buddy.set_focus(true);
+
+        // The inner height is 90 pixels and the content height is 200 pixels
+        let renderer = test_renderer(RenderRegion::between(0, 0, 200, 100));
+        text_area.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!(0.0, text_area.scroll_y);
+        assert_eq!(5, text_area.last_frame.as_ref().unwrap().rows.len());
+
+        // Scrolling can't go past the end of the text
+        let scroll = |text_area: &mut TextArea, buddy: &mut RootComponentBuddy, delta_y: f32| {
+            let event = MouseScrollEvent::new(Mouse::new(0), Point::new(0.5, 0.5), 0.0, delta_y);
+            text_area.on_mouse_scroll(event, buddy);
+            text_area.render(&renderer, buddy, false).unwrap();
+        };
+        scroll(&mut text_area, &mut buddy, -2.0);
+        assert_eq!(40.0, text_area.scroll_y);
+        scroll(&mut text_area, &mut buddy, -10.0);
+        assert_eq!(110.0, text_area.scroll_y);
+        scroll(&mut text_area, &mut buddy, 1.0);
+        assert_eq!(90.0, text_area.scroll_y);
+
+        // Moving the cursor should scroll it into view
+        let control = KeyModifiers::new(false, true, false, false);
+        text_area.on_key_press(KeyPressEvent::new(Key::Home, control), &mut buddy);
+        text_area.render(&renderer, &mut buddy, false).unwrap();
+        assert_eq!(0.0, text_area.scroll_y);
+        assert_eq!(
+            Some(0),
+            text_area.last_frame.as_ref().unwrap().rows[0].cursor
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The editable text of a text field, together with its cursor and selection. All positions are
+/// *grapheme* indices: position 0 is before the first grapheme and position `n` is after the
+/// `n`-th grapheme. This ensures that the cursor can never end up in the middle of a grapheme
+/// cluster (like a letter with a combining accent).
+///
+/// The selection is the range between the *anchor* and the cursor. When there is no anchor, or
+/// the anchor is equal to the cursor, nothing is selected.
+pub(super) struct TextEdit {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+}
+
+impl TextEdit {
+    /// Constructs a new `TextEdit` with the given initial `text`, and the cursor at the end of it
+    pub(super) fn new(text: String) -> Self {
+        let cursor = text.graphemes(true).count();
+        Self {
+            text,
+            cursor,
+            anchor: None,
+        }
+    }
+
+    pub(super) fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the entire text, and moves the cursor to the end of the new text
+    pub(super) fn set_text(&mut self, text: String) {
+        *self = Self::new(text);
+    }
+
+    pub(super) fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub(super) fn get_num_graphemes(&self) -> usize {
+        self.text.graphemes(true).count()
+    }
+
+    /// Gets the selected range (the start is always smaller than the end), or `None` if nothing
+    /// is selected
+    pub(super) fn get_selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor < self.cursor => Some((anchor, self.cursor)),
+            Some(anchor) if anchor > self.cursor => Some((self.cursor, anchor)),
+            _ => None,
+        }
+    }
+
+    /// Converts a grapheme index to the corresponding byte index in the text
+    pub(super) fn byte_index(&self, grapheme_index: usize) -> usize {
+        self.text
+            .grapheme_indices(true)
+            .nth(grapheme_index)
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or_else(|| self.text.len())
+    }
+
+    /// Moves the cursor to the given grapheme index (which will be clamped to the length of the
+    /// text). If `selecting` is true, the selection will be extended to the new cursor position.
+    /// Otherwise, the selection will be cleared.
+    pub(super) fn move_cursor(&mut self, position: usize, selecting: bool) {
+        if selecting {
+            if self.anchor.is_none() {
+                self.anchor = Some(self.cursor);
+            }
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position.min(self.get_num_graphemes());
+    }
+
+    /// Moves the cursor 1 grapheme to the left. If something is selected and `selecting` is
+    /// false, the cursor will move to the start of the selection instead.
+    pub(super) fn move_left(&mut self, selecting: bool) {
+        match (self.get_selection(), selecting) {
+            (Some((start, _)), false) => self.move_cursor(start, false),
+            _ => self.move_cursor(self.cursor.saturating_sub(1), selecting),
+        }
+    }
+
+    /// Moves the cursor 1 grapheme to the right. If something is selected and `selecting` is
+    /// false, the cursor will move to the end of the selection instead.
+    pub(super) fn move_right(&mut self, selecting: bool) {
+        match (self.get_selection(), selecting) {
+            (Some((_, end)), false) => self.move_cursor(end, false),
+            _ => self.move_cursor(self.cursor + 1, selecting),
+        }
+    }
+
+    pub(super) fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.get_num_graphemes();
+    }
+
+    /// Removes the selected text, and returns true if anything was selected
+    fn delete_selection(&mut self) -> bool {
+        if let Some((start, end)) = self.get_selection() {
+            let byte_start = self.byte_index(start);
+            let byte_end = self.byte_index(end);
+            self.text.replace_range(byte_start..byte_end, "");
+            self.cursor = start;
+            self.anchor = None;
+            true
+        } else {
+            self.anchor = None;
+            false
+        }
+    }
+
+    /// Inserts the given `text` at the cursor position (replacing the selection, if any), and
+    /// moves the cursor to the end of the inserted text
+    pub(super) fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let byte_index = self.byte_index(self.cursor);
+        self.text.insert_str(byte_index, text);
+
+        // Inserting a combining character could merge it with the grapheme before the cursor, so
+        // the new cursor position needs to be computed from the byte index
+        let end_byte_index = byte_index + text.len();
+        self.cursor = self
+            .text
+            .grapheme_indices(true)
+            .take_while(|(index, _)| *index < end_byte_index)
+            .count();
+    }
+
+    /// Removes the selection, or the grapheme before the cursor if nothing is selected. Returns
+    /// true if the text changed.
+    pub(super) fn backspace(&mut self) -> bool {
+        if self.delete_selection() {
+            true
+        } else if self.cursor > 0 {
+            self.anchor = Some(self.cursor - 1);
+            self.delete_selection()
+        } else {
+            false
+        }
+    }
+
+    /// Removes the selection, or the grapheme after the cursor if nothing is selected. Returns
+    /// true if the text changed.
+    pub(super) fn delete(&mut self) -> bool {
+        if self.delete_selection() {
+            true
+        } else if self.cursor < self.get_num_graphemes() {
+            self.anchor = Some(self.cursor + 1);
+            self.delete_selection()
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_insert_and_delete() {
+        let mut edit = TextEdit::new("hllo".to_string());
+        assert_eq!(4, edit.get_cursor());
+
+        edit.move_cursor(1, false);
+        edit.insert("e");
+        assert_eq!("hello", edit.get_text());
+        assert_eq!(2, edit.get_cursor());
+
+        assert!(edit.backspace());
+        assert_eq!("hllo", edit.get_text());
+        assert_eq!(1, edit.get_cursor());
+
+        assert!(edit.delete());
+        assert_eq!("hlo", edit.get_text());
+        assert_eq!(1, edit.get_cursor());
+
+        edit.move_cursor(0, false);
+        assert!(!edit.backspace());
+        edit.move_cursor(3, false);
+        assert!(!edit.delete());
+        assert_eq!("hlo", edit.get_text());
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // The e with the combining accent is 1 grapheme, but 2 chars
+        let mut edit = TextEdit::new("caf\u{65}\u{301}!".to_string());
+        assert_eq!(5, edit.get_num_graphemes());
+        assert_eq!(5, edit.get_cursor());
+
+        edit.move_left(false);
+        edit.move_left(false);
+        assert_eq!(3, edit.get_cursor());
+        assert_eq!(3, edit.byte_index(3));
+        assert_eq!(6, edit.byte_index(4));
+
+        // The entire grapheme should be deleted at once
+        assert!(edit.delete());
+        assert_eq!("caf!", edit.get_text());
+
+        // Typing a combining accent should merge it with the previous grapheme
+        edit.insert("e");
+        edit.insert("\u{301}");
+        assert_eq!("caf\u{65}\u{301}!", edit.get_text());
+        assert_eq!(4, edit.get_cursor());
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut edit = TextEdit::new("hello world".to_string());
+        assert_eq!(None, edit.get_selection());
+
+        edit.move_cursor(6, false);
+        edit.move_right(true);
+        edit.move_right(true);
+        assert_eq!(Some((6, 8)), edit.get_selection());
+
+        // Moving left without selecting should collapse the selection to its start
+        edit.move_left(false);
+        assert_eq!(None, edit.get_selection());
+        assert_eq!(6, edit.get_cursor());
+
+        // Selecting backwards
+        edit.move_cursor(5, true);
+        edit.move_cursor(0, true);
+        assert_eq!(Some((0, 6)), edit.get_selection());
+        edit.insert("goodbye ");
+        assert_eq!("goodbye world", edit.get_text());
+        assert_eq!(8, edit.get_cursor());
+        assert_eq!(None, edit.get_selection());
+
+        edit.select_all();
+        assert_eq!(Some((0, 13)), edit.get_selection());
+        assert!(edit.backspace());
+        assert_eq!("", edit.get_text());
+        assert_eq!(0, edit.get_cursor());
+    }
+}
@@ -0,0 +1,485 @@
+use super::text_edit::TextEdit;
+use super::*;
+
+/// The space (in pixels) between the border of a `TextField` and its text
+const PADDING: f32 = 5.0;
+
+/// The width (in pixels) of the cursor of a `TextField`
+const CURSOR_WIDTH: f32 = 2.0;
+
+/// A single-line text field that the user can type in. Clicking on it will give it the keyboard
+/// focus and move its cursor to the clicked position, and dragging the mouse will select text.
+/// The cursor can also be moved with the arrow keys (hold shift to select), Home and End, and
+/// Ctrl+A selects all text. Pressing Enter or Escape will release the focus.
+///
+/// The cursor and selection always stay on *grapheme* boundaries, so letters with combining
+/// accents (and other grapheme clusters) are treated as a single character. When the text is
+/// wider than the field, it will scroll horizontally to keep the cursor visible.
+///
+/// Each time the user changes the text, the `on_change` callback will be called with the new
+/// text.
+///
+/// ### Without keyboard
+/// When the *wrapper* reports that there is no keyboard (for instance on a phone), the text field
+/// can't receive `CharTypeEvent`s. In that case, clicking on it will call the `request_text_input`
+/// method of its buddy instead, and the text field will take over the answer.
+pub struct TextField {
+    edit: TextEdit,
+    font: WidgetFont,
+    colors: WidgetColors,
+    enabled: bool,
+    on_change: ChangeHandler<String>,
+
+    keyboard_available: bool,
+    dragging: Option<Mouse>,
+
+    // The x-coordinates of the grapheme boundaries during the last render
+    grapheme_positions: Vec<f32>,
+    // How far the text is scrolled to the left (in the coordinate system of components)
+    scroll_x: f32,
+}
+
+impl TextField {
+    /// Constructs a new enabled `TextField` with the given initial `text`
+    pub fn new(text: String, on_change: ChangeHandler<String>) -> Self {
+        Self {
+            edit: TextEdit::new(text),
+            font: WidgetFont::new(),
+            colors: WidgetColors::default(),
+            enabled: true,
+            on_change,
+            keyboard_available: true,
+            dragging: None,
+            grapheme_positions: Vec::new(),
+            scroll_x: 0.0,
+        }
+    }
+
+    /// Sets the font of the text
+    pub fn set_font(&mut self, font: FontHandle) {
+        self.font.set(font);
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this text field. The user can't change the text of a disabled text
+    /// field. This should be called before the text field is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn get_text(&self) -> &str {
+        self.edit.get_text()
+    }
+
+    /// Gets the grapheme boundary that is closest to the given x-coordinate
+    fn get_grapheme_index(&self, x: f32) -> usize {
+        let mut best_index = 0;
+        let mut best_distance = f32::INFINITY;
+        for (index, position) in self.grapheme_positions.iter().enumerate() {
+            let distance = (position - x).abs();
+            if distance < best_distance {
+                best_index = index;
+                best_distance = distance;
+            }
+        }
+        best_index
+    }
+
+    fn notify_change(&mut self, buddy: &mut dyn ComponentBuddy) {
+        (self.on_change)(self.edit.get_text().to_string(), buddy);
+        buddy.request_render();
+    }
+}
+
+impl Component for TextField {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.subscribe_mouse_press();
+        buddy.subscribe_mouse_move();
+        buddy.subscribe_mouse_release();
+        buddy.subscribe_mouse_click_out();
+
+        self.keyboard_available = buddy.subscribe_char_type().is_ok();
+        if self.keyboard_available {
+            buddy.subscribe_key_press();
+        }
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The padding is in pixels, so the positions of the graphemes will change
+        buddy.request_render();
+    }
+
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        let focused = buddy.has_focus();
+        let state = match self.enabled {
+            true => WidgetState::Normal,
+            false => WidgetState::Disabled,
+        };
+        let border = match focused {
+            true => self.colors.accent,
+            false => self.colors.border,
+        };
+        draw_bordered_box(renderer, border, self.colors.get_background(state));
+
+        let viewport = renderer.get_viewport();
+        let padding_x = PADDING / viewport.get_width() as f32;
+        let padding_y = PADDING / viewport.get_height() as f32;
+        let font = self.font.get(renderer);
+
+        // The text should never be squeezed horizontally, so the height of the text field should
+        // be the only limit on the size of the text
+        let text_position = |min_x: f32| {
+            label_position(
+                min_x,
+                padding_y,
+                min_x + 1000.0,
+                1.0 - padding_y,
+                HorizontalTextAlignment::Left,
+            )
+        };
+
+        let text = self.edit.get_text();
+        let positions = match text.is_empty() {
+            true => vec![padding_x],
+            false => match renderer.get_text_renderer().get_grapheme_offsets(
+                text,
+                font,
+                text_position(padding_x),
+                renderer,
+            ) {
+                Some(offsets) => offsets.into_iter().map(|x| (x + 1.0) / 2.0).collect(),
+                // The text can't be drawn either, and draw_text will return the error below
+                None => vec![padding_x; self.edit.get_num_graphemes() + 1],
+            },
+        };
+
+        // Scroll as little as possible to keep the cursor visible, without leaving empty space
+        // behind the end of the text
+        let cursor_position = positions[self.edit.get_cursor()];
+        let end_position = positions[positions.len() - 1];
+        self.scroll_x = self
+            .scroll_x
+            .min((end_position - 1.0 + padding_x).max(0.0))
+            .max(cursor_position - 1.0 + padding_x)
+            .min(cursor_position - padding_x)
+            .max(0.0);
+        self.grapheme_positions = positions.into_iter().map(|x| x - self.scroll_x).collect();
+
+        let selection = self.edit.get_selection();
+        let cursor_width = CURSOR_WIDTH / viewport.get_width() as f32;
+        let show_cursor = focused && self.enabled && self.keyboard_available;
+        renderer
+            .push_scissor(
+                padding_x,
+                padding_y,
+                1.0 - padding_x,
+                1.0 - padding_y,
+                || {
+                    if let Some((start, end)) = selection {
+                        let min_x = self.grapheme_positions[start].max(0.0);
+                        let max_x = self.grapheme_positions[end].min(1.0);
+                        renderer.push_viewport(min_x, padding_y, max_x, 1.0 - padding_y, || {
+                            renderer.clear(self.colors.hovered)
+                        });
+                    }
+
+                    let result = match text.is_empty() {
+                        true => Ok(()),
+                        false => renderer
+                            .get_text_renderer()
+                            .draw_text(
+                                text,
+                                font,
+                                text_position(padding_x - self.scroll_x),
+                                renderer,
+                            )
+                            .map(|_| ()),
+                    };
+
+                    if show_cursor {
+                        let cursor_x = self.grapheme_positions[self.edit.get_cursor()];
+                        renderer.push_viewport(
+                            cursor_x - 0.5 * cursor_width,
+                            padding_y,
+                            cursor_x + 0.5 * cursor_width,
+                            1.0 - padding_y,
+                            || renderer.clear(self.colors.border),
+                        );
+                    }
+                    result
+                },
+            )
+            .transpose()?;
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(0.0, 0.0, 1.0, 1.0)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !self.enabled || !event.get_button().is_primary() {
+            return;
+        }
+
+        if !self.keyboard_available {
+            buddy.request_text_input(self.edit.get_text().to_string());
+            return;
+        }
+
+        buddy.request_focus();
+        let index = self.get_grapheme_index(event.get_point().get_x());
+        self.edit.move_cursor(index, buddy.is_key_down(Key::Shift));
+        if self.dragging.is_none() {
+            self.dragging = Some(event.get_mouse());
+            buddy.capture_mouse(event.get_mouse());
+        }
+        buddy.request_render();
+    }
+
+    fn on_mouse_move(&mut self, event: MouseMoveEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.dragging == Some(event.get_mouse()) {
+            let index = self.get_grapheme_index(event.get_to().get_x());
+            if index != self.edit.get_cursor() {
+                self.edit.move_cursor(index, true);
+                buddy.request_render();
+            }
+        }
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        if event.get_button().is_primary() && self.dragging == Some(event.get_mouse()) {
+            self.dragging = None;
+            buddy.release_mouse_capture(event.get_mouse());
+        }
+    }
+
+    fn on_mouse_click_out(&mut self, _event: MouseClickOutEvent, buddy: &mut dyn ComponentBuddy) {
+        buddy.release_focus();
+    }
+
+    fn on_char_type(&mut self, event: &CharTypeEvent, buddy: &mut dyn ComponentBuddy) {
+        // This is a single-line text field, so newlines (and other control characters) are ignored
+        let text = event.get_text();
+        if self.enabled && buddy.has_focus() && !text.chars().any(char::is_control) {
+            self.edit.insert(text);
+            self.notify_change(buddy);
+        }
+    }
+
+    fn on_key_press(&mut self, event: KeyPressEvent, buddy: &mut dyn ComponentBuddy) {
+        if !should_handle_key_press(self.enabled, buddy) {
+            return;
+        }
+
+        let modifiers = event.get_modifiers();
+        let selecting = modifiers.is_shift_down();
+        match event.get_key() {
+            Key::Left => self.edit.move_left(selecting),
+            Key::Right => self.edit.move_right(selecting),
+            Key::Home => self.edit.move_cursor(0, selecting),
+            Key::End => self
+                .edit
+                .move_cursor(self.edit.get_num_graphemes(), selecting),
+            Key::Letter('A') if modifiers.is_control_down() || modifiers.is_meta_down() => {
+                self.edit.select_all()
+            }
+            Key::Backspace => {
+                if self.edit.backspace() {
+                    self.notify_change(buddy);
+                }
+            }
+            Key::Delete => {
+                if self.edit.delete() {
+                    self.notify_change(buddy);
+                }
+            }
+            Key::Enter | Key::Escape => buddy.release_focus(),
+            _ => return,
+        };
+        buddy.request_render();
+    }
+
+    fn on_text_input(&mut self, event: &TextInputEvent, buddy: &mut dyn ComponentBuddy) {
+        if let Some(text) = event.get_text() {
+            self.edit.set_text(text.replace('\n', " "));
+            self.notify_change(buddy);
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled && self.keyboard_available
+    }
+
+    fn on_focus_gained(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+
+    fn on_focus_lost(&mut self, buddy: &mut dyn ComponentBuddy) {
+        buddy.request_render();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn enter(application: &mut Application) {
+        application
+            .fire_mouse_enter_event(MouseEnterEvent::new(Mouse::new(0), Point::new(0.0, 0.5)));
+    }
+
+    fn press(application: &mut Application, x: f32) {
+        application.fire_mouse_press_event(MousePressEvent::new(
+            Mouse::new(0),
+            Point::new(x, 0.5),
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            Mouse::new(0),
+            Point::new(x, 0.5),
+            MouseButton::primary(),
+        ));
+    }
+
+    fn type_text(application: &mut Application, text: &str) {
+        for character in text.chars() {
+            application.fire_char_type_event(CharTypeEvent::new(character.to_string()));
+        }
+    }
+
+    fn press_key(application: &mut Application, key: Key, modifiers: KeyModifiers) {
+        application.fire_key_press_event(KeyPressEvent::new(key, modifiers));
+        application.fire_key_release_event(KeyReleaseEvent::new(key, modifiers));
+    }
+
+    #[test]
+    fn test_text_field() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let field_changes = Rc::clone(&changes);
+        let text_field = TextField::new(
+            "ac".to_string(),
+            Box::new(move |text, _buddy| field_changes.borrow_mut().push(text)),
+        );
+        let mut application = Application::new(Box::new(text_field));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 400, 40)), false);
+        enter(&mut application);
+
+        // The text field shouldn't respond to typing before it has the focus
+        type_text(&mut application, "x");
+        assert!(changes.borrow().is_empty());
+
+        // Clicking far to the right should move the cursor to the end of the text
+        press(&mut application, 0.9);
+        type_text(&mut application, "d");
+        assert_eq!(vec!["acd".to_string()], *changes.borrow());
+
+        let none = KeyModifiers::none();
+        press_key(&mut application, Key::Left, none);
+        press_key(&mut application, Key::Left, none);
+        type_text(&mut application, "b");
+        assert_eq!("abcd", changes.borrow().last().unwrap());
+
+        // Newlines are not allowed in a single-line text field
+        type_text(&mut application, "\n");
+        assert_eq!(2, changes.borrow().len());
+
+        press_key(&mut application, Key::Backspace, none);
+        press_key(&mut application, Key::Delete, none);
+        assert_eq!("ad", changes.borrow().last().unwrap());
+
+        // Typing while everything is selected should replace the selection, and combining
+        // accents should be merged with the previous character
+        let shift = KeyModifiers::new(true, false, false, false);
+        press_key(&mut application, Key::End, none);
+        press_key(&mut application, Key::Home, shift);
+        type_text(&mut application, "e\u{301}");
+        assert_eq!("e\u{301}", changes.borrow().last().unwrap());
+
+        // After releasing the focus, typing should be ignored again
+        let num_changes = changes.borrow().len();
+        press_key(&mut application, Key::Escape, none);
+        type_text(&mut application, "x");
+        press_key(&mut application, Key::Backspace, none);
+        assert_eq!(num_changes, changes.borrow().len());
+    }
+
+    #[test]
+    fn test_text_field_mouse_selection() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let field_changes = Rc::clone(&changes);
+        let text_field = TextField::new(
+            "hello".to_string(),
+            Box::new(move |text, _buddy| field_changes.borrow_mut().push(text)),
+        );
+        let mut application = Application::new(Box::new(text_field));
+        application.render(&test_renderer(RenderRegion::between(0, 0, 400, 40)), false);
+        enter(&mut application);
+
+        // Drag from the end of the text to the start of the text to select everything
+        let mouse = Mouse::new(0);
+        let start = Point::new(0.9, 0.5);
+        let end = Point::new(0.0, 0.5);
+        application.fire_mouse_press_event(MousePressEvent::new(
+            mouse,
+            start,
+            MouseButton::primary(),
+        ));
+        application.fire_mouse_move_event(MouseMoveEvent::new(mouse, start, end));
+        application.fire_mouse_release_event(MouseReleaseEvent::new(
+            mouse,
+            end,
+            MouseButton::primary(),
+        ));
+
+        type_text(&mut application, "x");
+        assert_eq!(vec!["x".to_string()], *changes.borrow());
+    }
+
+    #[test]
+    fn test_text_field_without_keyboard() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let field_changes = Rc::clone(&changes);
+        let mut text_field = TextField::new(
+            "hello".to_string(),
+            Box::new(move |text, _buddy| field_changes.borrow_mut().push(text)),
+        );
+
+        let mut buddy = RootComponentBuddy::new();
+        let mut keyboard_store = KeyboardStore::new();
+        keyboard_store.set_keyboard_available(false);
+        buddy.set_keyboard_store(Rc::new(RefCell::new(keyboard_store)));
+        text_field.on_attach(&mut buddy);
+        assert!(!text_field.is_focusable());
+
+        // Since it can't receive char type events, it should request text input instead
+        text_field.on_mouse_press(
+            MousePressEvent::new(Mouse::new(0), Point::new(0.5, 0.5), MouseButton::primary()),
+            &mut buddy,
+        );
+        assert_eq!(Some("hello".to_string()), buddy.take_text_input_request());
+        assert_eq!(None, buddy.take_focus_request());
+
+        text_field.on_text_input(
+            &TextInputEvent::new(Some("hello\nworld".to_string())),
+            &mut buddy,
+        );
+        assert_eq!("hello world", text_field.get_text());
+        assert_eq!(vec!["hello world".to_string()], *changes.borrow());
+
+        // Cancelling the text input shouldn't change anything
+        text_field.on_text_input(&TextInputEvent::new(None), &mut buddy);
+        assert_eq!(1, changes.borrow().len());
+    }
+}
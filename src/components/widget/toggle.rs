@@ -0,0 +1,176 @@
+use super::*;
+
+use std::time::Duration;
+
+/// The time it takes for the knob of a `Toggle` to move to the other side
+const KNOB_DURATION: Duration = Duration::from_millis(120);
+
+/// A toggle switch: a track with a round knob that is on the left side when the switch is off and
+/// on the right side when it is on. Clicking on it switches it on or off, after which the knob
+/// slides to the other side and the `on_change` callback is called with the new value.
+///
+/// The track is twice as wide as it is high, and it is centered in the domain of the toggle.
+pub struct Toggle {
+    colors: WidgetColors,
+    enabled: bool,
+    on: bool,
+    on_change: ChangeHandler<bool>,
+
+    // 0.0 when the knob is on the left side and 1.0 when it is on the right side
+    knob_position: Animated<f32>,
+    input: WidgetInput,
+}
+
+impl Toggle {
+    /// Constructs a new enabled `Toggle` that is initially on if `on` is true
+    pub fn new(on: bool, on_change: ChangeHandler<bool>) -> Self {
+        Self {
+            colors: WidgetColors::default(),
+            enabled: true,
+            on,
+            on_change,
+            knob_position: Animated::new(if on { 1.0 } else { 0.0 }),
+            input: WidgetInput::new(),
+        }
+    }
+
+    pub fn set_colors(&mut self, colors: WidgetColors) {
+        self.colors = colors;
+    }
+
+    /// Enables or disables this toggle. The user can't switch a disabled toggle on or off. This
+    /// should be called before the toggle is attached.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+}
+
+impl Component for Toggle {
+    fn on_attach(&mut self, buddy: &mut dyn ComponentBuddy) {
+        self.input.subscribe(buddy);
+    }
+
+    fn on_resize(&mut self, _event: ResizeEvent, buddy: &mut dyn ComponentBuddy) {
+        // The drawn region depends on the aspect ratio, so it needs to be recomputed
+        buddy.request_render();
+    }
+
+    #[cfg_attr(not(feature = "golem_rendering"), allow(unused_variables))]
+    fn render(
+        &mut self,
+        renderer: &Renderer,
+        buddy: &mut dyn ComponentBuddy,
+        _force: bool,
+    ) -> RenderResult {
+        // Use the largest area in the middle whose width is twice its height
+        let ar = renderer.get_viewport().get_aspect_ratio();
+        let used_width = (2.0 / ar).min(1.0);
+        let used_height = (0.5 * ar).min(1.0);
+        let min_x = 0.5 - 0.5 * used_width;
+        let min_y = 0.5 - 0.5 * used_height;
+        let max_x = 0.5 + 0.5 * used_width;
+        let max_y = 0.5 + 0.5 * used_height;
+
+        #[cfg(feature = "golem_rendering")]
+        {
+            let track_color = if !self.enabled {
+                self.colors.disabled
+            } else if self.on {
+                self.colors.accent
+            } else {
+                self.colors.border
+            };
+            let knob_color = self
+                .colors
+                .get_background(self.input.get_state(self.enabled));
+            let knob_position = self.knob_position.get(buddy);
+
+            renderer
+                .push_viewport(min_x, min_y, max_x, max_y, || {
+                    renderer.clear(track_color);
+
+                    // The knob is a circle whose diameter is the height of the track
+                    let knob_min_x = 0.5 * knob_position;
+                    renderer
+                        .push_viewport(knob_min_x, 0.0, knob_min_x + 0.5, 1.0, || {
+                            draw_bordered_oval(renderer, self.colors.border, knob_color)
+                        })
+                        .unwrap_or(Ok(()))
+                })
+                .unwrap_or(Ok(()))?;
+        }
+
+        Ok(RenderResultStruct {
+            drawn_region: Box::new(RectangularDrawnRegion::new(min_x, min_y, max_x, max_y)),
+            filter_mouse_actions: true,
+        })
+    }
+
+    fn on_mouse_click(&mut self, event: MouseClickEvent, buddy: &mut dyn ComponentBuddy) {
+        if self.enabled && event.get_button().is_primary() {
+            self.on = !self.on;
+            let target = if self.on { 1.0 } else { 0.0 };
+            self.knob_position
+                .animate_to(target, KNOB_DURATION, Easing::QuadOut, buddy);
+            (self.on_change)(self.on, buddy);
+        }
+    }
+
+    fn on_mouse_press(&mut self, event: MousePressEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_press(event, buddy);
+    }
+
+    fn on_mouse_release(&mut self, event: MouseReleaseEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_release(event, buddy);
+    }
+
+    fn on_mouse_enter(&mut self, event: MouseEnterEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_enter(event, buddy);
+    }
+
+    fn on_mouse_leave(&mut self, event: MouseLeaveEvent, buddy: &mut dyn ComponentBuddy) {
+        self.input.mouse_leave(event, buddy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_toggle() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let toggle_changes = Rc::clone(&changes);
+        let toggle = Toggle::new(
+            false,
+            Box::new(move |on, _buddy| toggle_changes.borrow_mut().push(on)),
+        );
+        let mut application = Application::new(Box::new(toggle));
+
+        // The track will be 100 by 50 pixels in the middle
+        application.render(&test_renderer(RenderRegion::between(0, 0, 200, 50)), false);
+        let click = |application: &mut Application, x: f32| {
+            application.fire_mouse_click_event(MouseClickEvent::new(
+                Mouse::new(0),
+                Point::new(x, 0.5),
+                MouseButton::primary(),
+            ));
+        };
+
+        click(&mut application, 0.2);
+        click(&mut application, 0.8);
+        assert!(changes.borrow().is_empty());
+
+        click(&mut application, 0.3);
+        assert_eq!(vec![true], *changes.borrow());
+        click(&mut application, 0.7);
+        assert_eq!(vec![true, false], *changes.borrow());
+    }
+}
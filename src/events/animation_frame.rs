@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// This event is for the `on_animation_frame` method of `Component`. It indicates that the frame
+/// that the component requested with the `request_animation_frame` method of its buddy is about to
+/// be rendered.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AnimationFrameEvent {
+    time: Duration,
+}
+
+impl AnimationFrameEvent {
+    /// Constructs a new `AnimationFrameEvent` for the frame at the given `time`. This function
+    /// should normally only be used by the `Application` and by menu components.
+    pub fn new(time: Duration) -> Self {
+        Self { time }
+    }
+
+    /// Gets the timestamp of the frame. Animations should use this to compute their state, since
+    /// the time between 2 frames can vary.
+    pub fn get_time(&self) -> Duration {
+        self.time
+    }
+}
@@ -0,0 +1,181 @@
+use crate::*;
+
+use std::any::Any;
+use std::rc::Rc;
+
+/// The event for the `on_drag_enter` method of `Component`. It indicates that the mouse of an
+/// ongoing drag-and-drop operation just moved *onto* the component.
+///
+/// Drag-and-drop operations are started with the `start_drag_and_drop` method of
+/// `ComponentBuddy`. This event will always be followed by a `DragOverEvent` at the same point.
+#[derive(Clone)]
+pub struct DragEnterEvent {
+    mouse: Mouse,
+    point: Point,
+    payload: Rc<dyn Any>,
+}
+
+impl DragEnterEvent {
+    /// Constructs a new `DragEnterEvent` with the given mouse, relative mouse position (point)
+    /// and payload
+    pub fn new(mouse: Mouse, point: Point, payload: Rc<dyn Any>) -> Self {
+        Self {
+            mouse,
+            point,
+            payload,
+        }
+    }
+
+    /// Gets the `Mouse` that is dragging the payload
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position where the mouse entered the component, relative to the component
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the payload that is being dragged, if it has type `T`. This will return `None` if
+    /// the payload has another type, which typically means that the component can't accept it.
+    pub fn get_payload<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Gets the payload that is being dragged, without knowing its type
+    pub fn get_raw_payload(&self) -> &Rc<dyn Any> {
+        &self.payload
+    }
+}
+
+/// The event for the `on_drag_over` method of `Component`. It indicates that the mouse of an
+/// ongoing drag-and-drop operation moved while it was *on* the component.
+#[derive(Clone)]
+pub struct DragOverEvent {
+    mouse: Mouse,
+    point: Point,
+    payload: Rc<dyn Any>,
+}
+
+impl DragOverEvent {
+    /// Constructs a new `DragOverEvent` with the given mouse, relative mouse position (point)
+    /// and payload
+    pub fn new(mouse: Mouse, point: Point, payload: Rc<dyn Any>) -> Self {
+        Self {
+            mouse,
+            point,
+            payload,
+        }
+    }
+
+    /// Gets the `Mouse` that is dragging the payload
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the current position of the mouse, relative to the component
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the payload that is being dragged, if it has type `T`
+    pub fn get_payload<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Gets the payload that is being dragged, without knowing its type
+    pub fn get_raw_payload(&self) -> &Rc<dyn Any> {
+        &self.payload
+    }
+}
+
+/// The event for the `on_drag_leave` method of `Component`. It indicates that the mouse of an
+/// ongoing drag-and-drop operation moved *off* the component, or that the drag-and-drop operation
+/// was cancelled (for instance because the mouse left the application).
+#[derive(Clone)]
+pub struct DragLeaveEvent {
+    mouse: Mouse,
+    point: Point,
+    payload: Rc<dyn Any>,
+}
+
+impl DragLeaveEvent {
+    /// Constructs a new `DragLeaveEvent` with the given mouse, relative mouse position (point)
+    /// and payload
+    pub fn new(mouse: Mouse, point: Point, payload: Rc<dyn Any>) -> Self {
+        Self {
+            mouse,
+            point,
+            payload,
+        }
+    }
+
+    /// Gets the `Mouse` that is dragging the payload
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position of the mouse when it left, relative to the component. Note that this
+    /// point can be outside the component.
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the payload that is being dragged, if it has type `T`
+    pub fn get_payload<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Gets the payload that is being dragged, without knowing its type
+    pub fn get_raw_payload(&self) -> &Rc<dyn Any> {
+        &self.payload
+    }
+}
+
+/// The event for the `on_drop` method of `Component`. It indicates that the user released the
+/// mouse of a drag-and-drop operation *on* the component. This ends the drag-and-drop operation.
+///
+/// The component that receives this event won't receive a `DragLeaveEvent` afterwards.
+///
+/// If the component that started the drag-and-drop operation needs to know whether the payload
+/// was dropped (for instance to remove the dragged item from its own list), it can put an
+/// `Rc<Cell<bool>>` (or something similar) in the payload and let the receiving component
+/// modify it.
+#[derive(Clone)]
+pub struct DropEvent {
+    mouse: Mouse,
+    point: Point,
+    payload: Rc<dyn Any>,
+}
+
+impl DropEvent {
+    /// Constructs a new `DropEvent` with the given mouse, relative mouse position (point) and
+    /// payload
+    pub fn new(mouse: Mouse, point: Point, payload: Rc<dyn Any>) -> Self {
+        Self {
+            mouse,
+            point,
+            payload,
+        }
+    }
+
+    /// Gets the `Mouse` that dropped the payload
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position where the payload was dropped, relative to the component
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the payload that was dropped, if it has type `T`
+    pub fn get_payload<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// Gets the payload that was dropped, without knowing its type
+    pub fn get_raw_payload(&self) -> &Rc<dyn Any> {
+        &self.payload
+    }
+}
@@ -0,0 +1,390 @@
+use crate::*;
+
+mod recognizer;
+
+pub use recognizer::*;
+
+/// The event for the `on_double_click` method of `Component`. It indicates that the user clicked
+/// twice *on* the component with the same mouse button, quickly after each other.
+///
+/// The `MouseClickEvent` for the second click will be fired before this event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DoubleClickEvent {
+    mouse: Mouse,
+    point: Point,
+    button: MouseButton,
+}
+
+impl DoubleClickEvent {
+    /// Constructs a new `DoubleClickEvent` with the given mouse, relative mouse cursor position
+    /// (point) of the second click, and the given button
+    pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self {
+            mouse,
+            point,
+            button,
+        }
+    }
+
+    /// Gets the `Mouse` that was double-clicked
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position of the mouse cursor during the second click, relative to the component
+    /// that listens to this event
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the mouse button that was double-clicked
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+}
+
+/// The event for the `on_long_press` method of `Component`. It indicates that the user has been
+/// pressing a mouse button *on* the component for a while, without moving the mouse (much).
+///
+/// This event will be fired at most once per mouse press, and it will be fired while the button
+/// is still pressed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LongPressEvent {
+    mouse: Mouse,
+    point: Point,
+    button: MouseButton,
+}
+
+impl LongPressEvent {
+    /// Constructs a new `LongPressEvent` with the given mouse, relative mouse cursor position
+    /// (point) and the given button
+    pub fn new(mouse: Mouse, point: Point, button: MouseButton) -> Self {
+        Self {
+            mouse,
+            point,
+            button,
+        }
+    }
+
+    /// Gets the `Mouse` that is being pressed
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position of the mouse cursor, relative to the component that listens to this event
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the mouse button that is being pressed
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+}
+
+/// The event for the `on_drag_start` method of `Component`. It indicates that the user pressed a
+/// mouse button *on* the component and moved the mouse some distance while holding the button.
+///
+/// This event will be followed by a `DragUpdateEvent` for every mouse movement, and finally by a
+/// `DragEndEvent`. These will all be fired at the component where the drag started, even when the
+/// mouse leaves that component.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DragStartEvent {
+    mouse: Mouse,
+    button: MouseButton,
+    start_point: Point,
+}
+
+impl DragStartEvent {
+    /// Constructs a new `DragStartEvent` for the given mouse and button, where `start_point` is
+    /// the (relative) position where the button was pressed
+    pub fn new(mouse: Mouse, button: MouseButton, start_point: Point) -> Self {
+        Self {
+            mouse,
+            button,
+            start_point,
+        }
+    }
+
+    /// Gets the `Mouse` that is dragging
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the mouse button that is being held during the drag
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+
+    /// Gets the position where the mouse button was pressed, relative to the component that
+    /// listens to this event
+    pub fn get_start_point(&self) -> Point {
+        self.start_point
+    }
+}
+
+/// The event for the `on_drag_update` method of `Component`. It indicates that the mouse moved
+/// during a drag that started at the component.
+///
+/// Unlike most mouse events, the positions of this event can be *outside* the component: the
+/// component will keep receiving these events until the drag ends, even if the mouse leaves it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DragUpdateEvent {
+    mouse: Mouse,
+    button: MouseButton,
+    start_point: Point,
+    from: Point,
+    to: Point,
+}
+
+impl DragUpdateEvent {
+    /// Constructs a new `DragUpdateEvent` indicating that `mouse` moved from `from` to `to`
+    /// during a drag (with `button`) that started at `start_point`
+    pub fn new(
+        mouse: Mouse,
+        button: MouseButton,
+        start_point: Point,
+        from: Point,
+        to: Point,
+    ) -> Self {
+        Self {
+            mouse,
+            button,
+            start_point,
+            from,
+            to,
+        }
+    }
+
+    /// Gets the `Mouse` that is dragging
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the mouse button that is being held during the drag
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+
+    /// Gets the position where the drag started (where the mouse button was pressed)
+    pub fn get_start_point(&self) -> Point {
+        self.start_point
+    }
+
+    /// Gets the position the mouse came from
+    pub fn get_from(&self) -> Point {
+        self.from
+    }
+
+    /// Gets the position the mouse moved to
+    pub fn get_to(&self) -> Point {
+        self.to
+    }
+}
+
+/// The event for the `on_drag_end` method of `Component`. It indicates that a drag that started
+/// at the component ended, either because the mouse button was released, or because the mouse
+/// left the application (or is now part of a pinch).
+///
+/// Just like the `DragUpdateEvent`, the end point can be *outside* the component.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DragEndEvent {
+    mouse: Mouse,
+    button: MouseButton,
+    start_point: Point,
+    end_point: Point,
+}
+
+impl DragEndEvent {
+    /// Constructs a new `DragEndEvent` for a drag (with `button`) of `mouse` that started at
+    /// `start_point` and ended at `end_point`
+    pub fn new(mouse: Mouse, button: MouseButton, start_point: Point, end_point: Point) -> Self {
+        Self {
+            mouse,
+            button,
+            start_point,
+            end_point,
+        }
+    }
+
+    /// Gets the `Mouse` that was dragging
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the mouse button that was held during the drag
+    pub fn get_button(&self) -> MouseButton {
+        self.button
+    }
+
+    /// Gets the position where the drag started (where the mouse button was pressed)
+    pub fn get_start_point(&self) -> Point {
+        self.start_point
+    }
+
+    /// Gets the position where the drag ended
+    pub fn get_end_point(&self) -> Point {
+        self.end_point
+    }
+}
+
+/// The event for the `on_pinch` method of `Component`. It indicates that the user is pressing
+/// 2 `Mouse`s (typically 2 fingers on a touchscreen) and moved at least 1 of them.
+///
+/// The scale factor and rotation are *relative to the previous* `PinchEvent` (or the start of the
+/// pinch), so components should multiply/add them to their current zoom/rotation.
+///
+/// A pinch is always fired at the component where it started (the component under the center of
+/// the 2 mouses when the second one was pressed), even if the center moves outside it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PinchEvent {
+    mouses: (Mouse, Mouse),
+    start_center: Point,
+    center: Point,
+    scale_factor: f32,
+    rotation: f32,
+}
+
+impl PinchEvent {
+    /// Constructs a new `PinchEvent` for the given pair of mouses
+    pub fn new(
+        mouses: (Mouse, Mouse),
+        start_center: Point,
+        center: Point,
+        scale_factor: f32,
+        rotation: f32,
+    ) -> Self {
+        Self {
+            mouses,
+            start_center,
+            center,
+            scale_factor,
+            rotation,
+        }
+    }
+
+    /// Gets the 2 `Mouse`s that form this pinch
+    pub fn get_mouses(&self) -> (Mouse, Mouse) {
+        self.mouses
+    }
+
+    /// Gets the point halfway between the 2 mouses at the moment the pinch started
+    pub fn get_start_center(&self) -> Point {
+        self.start_center
+    }
+
+    /// Gets the point halfway between the 2 mouses, relative to the component that listens to
+    /// this event
+    pub fn get_center(&self) -> Point {
+        self.center
+    }
+
+    /// Gets the ratio between the current distance between the 2 mouses and their distance during
+    /// the previous `PinchEvent`. Values larger than 1 mean that the mouses moved apart (zooming
+    /// in) and values smaller than 1 mean that the mouses moved closer together (zooming out).
+    ///
+    /// This ratio is computed in the coordinate space of the `Application`, so it doesn't depend
+    /// on the size of the component.
+    pub fn get_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Gets the angle (in radians) that the line between the 2 mouses rotated since the previous
+    /// `PinchEvent`. Positive values mean counter-clockwise rotation. The angle will always be
+    /// between -PI and PI.
+    ///
+    /// Just like the scale factor, this is computed in the coordinate space of the `Application`.
+    pub fn get_rotation(&self) -> f32 {
+        self.rotation
+    }
+}
+
+/// Any of the higher-level events that can be produced by the `GestureRecognizer`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GestureEvent {
+    DoubleClick(DoubleClickEvent),
+    LongPress(LongPressEvent),
+    DragStart(DragStartEvent),
+    DragUpdate(DragUpdateEvent),
+    DragEnd(DragEndEvent),
+    Pinch(PinchEvent),
+}
+
+impl GestureEvent {
+    /// Gets the point that determines which component should receive this event. For drags and
+    /// pinches, this is the point where they started, so that the component where they started
+    /// will keep receiving them until they end.
+    pub(crate) fn get_routing_point(&self) -> Point {
+        match self {
+            Self::DoubleClick(event) => event.get_point(),
+            Self::LongPress(event) => event.get_point(),
+            Self::DragStart(event) => event.get_start_point(),
+            Self::DragUpdate(event) => event.get_start_point(),
+            Self::DragEnd(event) => event.get_start_point(),
+            Self::Pinch(event) => event.get_start_center(),
+        }
+    }
+
+    pub(crate) fn is_subscribed(&self, subscriptions: &ComponentSubscriptions) -> bool {
+        match self {
+            Self::DoubleClick(_) => subscriptions.double_click,
+            Self::LongPress(_) => subscriptions.long_press,
+            Self::DragStart(_) | Self::DragUpdate(_) | Self::DragEnd(_) => subscriptions.drag,
+            Self::Pinch(_) => subscriptions.pinch,
+        }
+    }
+
+    /// Creates a copy of this event where all points are transformed with `transform`. The scale
+    /// factor and rotation of pinch events won't be transformed.
+    pub(crate) fn transform(&self, transform: impl Fn(Point) -> Point) -> Self {
+        match self {
+            Self::DoubleClick(event) => Self::DoubleClick(DoubleClickEvent::new(
+                event.get_mouse(),
+                transform(event.get_point()),
+                event.get_button(),
+            )),
+            Self::LongPress(event) => Self::LongPress(LongPressEvent::new(
+                event.get_mouse(),
+                transform(event.get_point()),
+                event.get_button(),
+            )),
+            Self::DragStart(event) => Self::DragStart(DragStartEvent::new(
+                event.get_mouse(),
+                event.get_button(),
+                transform(event.get_start_point()),
+            )),
+            Self::DragUpdate(event) => Self::DragUpdate(DragUpdateEvent::new(
+                event.get_mouse(),
+                event.get_button(),
+                transform(event.get_start_point()),
+                transform(event.get_from()),
+                transform(event.get_to()),
+            )),
+            Self::DragEnd(event) => Self::DragEnd(DragEndEvent::new(
+                event.get_mouse(),
+                event.get_button(),
+                transform(event.get_start_point()),
+                transform(event.get_end_point()),
+            )),
+            Self::Pinch(event) => Self::Pinch(PinchEvent::new(
+                event.get_mouses(),
+                transform(event.get_start_center()),
+                transform(event.get_center()),
+                event.get_scale_factor(),
+                event.get_rotation(),
+            )),
+        }
+    }
+
+    /// Calls the corresponding event handler method of `component`
+    pub(crate) fn fire(self, component: &mut dyn Component, buddy: &mut dyn ComponentBuddy) {
+        match self {
+            Self::DoubleClick(event) => component.on_double_click(event, buddy),
+            Self::LongPress(event) => component.on_long_press(event, buddy),
+            Self::DragStart(event) => component.on_drag_start(event, buddy),
+            Self::DragUpdate(event) => component.on_drag_update(event, buddy),
+            Self::DragEnd(event) => component.on_drag_end(event, buddy),
+            Self::Pinch(event) => component.on_pinch(event, buddy),
+        }
+    }
+}
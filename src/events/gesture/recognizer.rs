@@ -0,0 +1,539 @@
+use crate::*;
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// The maximum time between 2 clicks of a double-click
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The maximum distance between the positions of 2 clicks of a double-click
+pub const DOUBLE_CLICK_DISTANCE: f32 = 0.05;
+
+/// The time a mouse button needs to be pressed before a `LongPressEvent` is fired
+pub const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// The distance a mouse needs to move while a button is pressed before it is considered a drag
+pub const DRAG_THRESHOLD: f32 = 0.02;
+
+/// Turns the regular mouse events of *all* `Mouse`s into `GestureEvent`s. The `Application` has a
+/// `GestureRecognizer` and feeds it all mouse events it receives.
+///
+/// The recognizer needs to know the current time to recognize double-clicks and long presses, so
+/// its `tick` method should be called regularly. Until the first call to `tick`, it won't produce
+/// any time-based gestures.
+pub struct GestureRecognizer {
+    current_time: Option<Duration>,
+
+    pressed_buttons: Vec<PressedButton>,
+    last_click: Option<ClickRecord>,
+    pinch: Option<Pinch>,
+}
+
+struct PressedButton {
+    mouse: Mouse,
+    button: MouseButton,
+
+    press_point: Point,
+    current_point: Point,
+    press_time: Option<Duration>,
+
+    dragging: bool,
+    long_pressed: bool,
+    // Presses that took part in a pinch won't become drags or long presses anymore
+    pinched: bool,
+}
+
+struct ClickRecord {
+    mouse: Mouse,
+    button: MouseButton,
+    point: Point,
+    time: Duration,
+}
+
+struct Pinch {
+    mouses: (Mouse, Mouse),
+    points: (Point, Point),
+    start_center: Point,
+}
+
+impl Pinch {
+    fn get_center(&self) -> Point {
+        center(self.points.0, self.points.1)
+    }
+}
+
+fn center(a: Point, b: Point) -> Point {
+    Point::new((a.get_x() + b.get_x()) / 2.0, (a.get_y() + b.get_y()) / 2.0)
+}
+
+fn angle(from: Point, to: Point) -> f32 {
+    (to.get_y() - from.get_y()).atan2(to.get_x() - from.get_x())
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self {
+            current_time: None,
+            pressed_buttons: Vec::new(),
+            last_click: None,
+            pinch: None,
+        }
+    }
+
+    /// Informs the recognizer about the current time, and checks whether any mouse buttons have
+    /// been pressed long enough to fire a `LongPressEvent`
+    pub fn tick(&mut self, now: Duration) -> Vec<GestureEvent> {
+        self.current_time = Some(now);
+
+        let mut events = Vec::new();
+        for pressed in &mut self.pressed_buttons {
+            if let Some(press_time) = pressed.press_time {
+                if !pressed.dragging
+                    && !pressed.long_pressed
+                    && !pressed.pinched
+                    && now >= press_time + LONG_PRESS_DURATION
+                {
+                    pressed.long_pressed = true;
+                    events.push(GestureEvent::LongPress(LongPressEvent::new(
+                        pressed.mouse,
+                        pressed.current_point,
+                        pressed.button,
+                    )));
+                }
+            }
+        }
+        events
+    }
+
+    pub fn mouse_press(&mut self, event: MousePressEvent) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+        let mouse = event.get_mouse();
+
+        // Pressing the same button twice shouldn't happen, but let's not create duplicates
+        self.pressed_buttons
+            .retain(|pressed| pressed.mouse != mouse || pressed.button != event.get_button());
+        self.pressed_buttons.push(PressedButton {
+            mouse,
+            button: event.get_button(),
+            press_point: event.get_point(),
+            current_point: event.get_point(),
+            press_time: self.current_time,
+            dragging: false,
+            long_pressed: false,
+            pinched: false,
+        });
+
+        // A pinch starts when a second mouse presses its primary button
+        if self.pinch.is_none() && event.get_button().is_primary() {
+            let other_press = self.pressed_buttons.iter().find(|pressed| {
+                pressed.mouse != mouse && pressed.button.is_primary() && !pressed.pinched
+            });
+            if let Some(other_press) = other_press {
+                let other_mouse = other_press.mouse;
+                let points = (other_press.current_point, event.get_point());
+                self.pinch = Some(Pinch {
+                    mouses: (other_mouse, mouse),
+                    points,
+                    start_center: center(points.0, points.1),
+                });
+
+                // The mouses of the pinch shouldn't continue dragging
+                for pressed in &mut self.pressed_buttons {
+                    if pressed.mouse == mouse || pressed.mouse == other_mouse {
+                        if pressed.dragging {
+                            pressed.dragging = false;
+                            events.push(GestureEvent::DragEnd(DragEndEvent::new(
+                                pressed.mouse,
+                                pressed.button,
+                                pressed.press_point,
+                                pressed.current_point,
+                            )));
+                        }
+                        pressed.pinched = true;
+                    }
+                }
+            }
+        }
+
+        events
+    }
+
+    pub fn mouse_release(&mut self, event: MouseReleaseEvent) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+        let mouse = event.get_mouse();
+        let button = event.get_button();
+
+        if let Some(index) = self
+            .pressed_buttons
+            .iter()
+            .position(|pressed| pressed.mouse == mouse && pressed.button == button)
+        {
+            let pressed = self.pressed_buttons.remove(index);
+            if pressed.dragging {
+                events.push(GestureEvent::DragEnd(DragEndEvent::new(
+                    mouse,
+                    button,
+                    pressed.press_point,
+                    event.get_point(),
+                )));
+            }
+        }
+
+        if button.is_primary() {
+            self.end_pinch_of(mouse);
+        }
+
+        events
+    }
+
+    pub fn mouse_click(&mut self, event: MouseClickEvent) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+
+        if let Some(now) = self.current_time {
+            let is_double_click = match &self.last_click {
+                Some(last_click) => {
+                    last_click.mouse == event.get_mouse()
+                        && last_click.button == event.get_button()
+                        && last_click.point.distance_to(event.get_point()) <= DOUBLE_CLICK_DISTANCE
+                        && now <= last_click.time + DOUBLE_CLICK_INTERVAL
+                }
+                None => false,
+            };
+
+            if is_double_click {
+                // A third click shouldn't form another double-click with the second click
+                self.last_click = None;
+                events.push(GestureEvent::DoubleClick(DoubleClickEvent::new(
+                    event.get_mouse(),
+                    event.get_point(),
+                    event.get_button(),
+                )));
+            } else {
+                self.last_click = Some(ClickRecord {
+                    mouse: event.get_mouse(),
+                    button: event.get_button(),
+                    point: event.get_point(),
+                    time: now,
+                });
+            }
+        }
+
+        events
+    }
+
+    pub fn mouse_move(&mut self, event: MouseMoveEvent) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+        let mouse = event.get_mouse();
+        let to = event.get_to();
+
+        for pressed in &mut self.pressed_buttons {
+            if pressed.mouse == mouse {
+                let from = pressed.current_point;
+                pressed.current_point = to;
+
+                if pressed.dragging {
+                    events.push(GestureEvent::DragUpdate(DragUpdateEvent::new(
+                        mouse,
+                        pressed.button,
+                        pressed.press_point,
+                        from,
+                        to,
+                    )));
+                } else if !pressed.pinched && pressed.press_point.distance_to(to) > DRAG_THRESHOLD {
+                    pressed.dragging = true;
+                    events.push(GestureEvent::DragStart(DragStartEvent::new(
+                        mouse,
+                        pressed.button,
+                        pressed.press_point,
+                    )));
+                    events.push(GestureEvent::DragUpdate(DragUpdateEvent::new(
+                        mouse,
+                        pressed.button,
+                        pressed.press_point,
+                        pressed.press_point,
+                        to,
+                    )));
+                }
+            }
+        }
+
+        if let Some(pinch) = &mut self.pinch {
+            let old_points = pinch.points;
+            if pinch.mouses.0 == mouse {
+                pinch.points.0 = to;
+            }
+            if pinch.mouses.1 == mouse {
+                pinch.points.1 = to;
+            }
+
+            if pinch.points != old_points {
+                let old_distance = old_points.0.distance_to(old_points.1);
+                let new_distance = pinch.points.0.distance_to(pinch.points.1);
+                let scale_factor = match old_distance > 0.0 {
+                    true => new_distance / old_distance,
+                    false => 1.0,
+                };
+
+                let mut rotation =
+                    angle(pinch.points.0, pinch.points.1) - angle(old_points.0, old_points.1);
+                if rotation > PI {
+                    rotation -= 2.0 * PI;
+                }
+                if rotation < -PI {
+                    rotation += 2.0 * PI;
+                }
+
+                events.push(GestureEvent::Pinch(PinchEvent::new(
+                    pinch.mouses,
+                    pinch.start_center,
+                    pinch.get_center(),
+                    scale_factor,
+                    rotation,
+                )));
+            }
+        }
+
+        events
+    }
+
+    pub fn mouse_leave(&mut self, event: MouseLeaveEvent) -> Vec<GestureEvent> {
+        let mut events = Vec::new();
+        let mouse = event.get_mouse();
+
+        // When a mouse leaves the application, all its drags end
+        for pressed in &self.pressed_buttons {
+            if pressed.mouse == mouse && pressed.dragging {
+                events.push(GestureEvent::DragEnd(DragEndEvent::new(
+                    mouse,
+                    pressed.button,
+                    pressed.press_point,
+                    event.get_exit_point(),
+                )));
+            }
+        }
+        self.pressed_buttons
+            .retain(|pressed| pressed.mouse != mouse);
+        self.end_pinch_of(mouse);
+
+        events
+    }
+
+    fn end_pinch_of(&mut self, mouse: Mouse) {
+        if let Some(pinch) = &self.pinch {
+            if pinch.mouses.0 == mouse || pinch.mouses.1 == mouse {
+                self.pinch = None;
+            }
+        }
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use std::time::Duration;
+
+    fn press(mouse: u16, x: f32, y: f32) -> MousePressEvent {
+        MousePressEvent::new(Mouse::new(mouse), Point::new(x, y), MouseButton::primary())
+    }
+
+    fn release(mouse: u16, x: f32, y: f32) -> MouseReleaseEvent {
+        MouseReleaseEvent::new(Mouse::new(mouse), Point::new(x, y), MouseButton::primary())
+    }
+
+    fn click(mouse: u16, x: f32, y: f32) -> MouseClickEvent {
+        MouseClickEvent::new(Mouse::new(mouse), Point::new(x, y), MouseButton::primary())
+    }
+
+    fn move_mouse(mouse: u16, from: (f32, f32), to: (f32, f32)) -> MouseMoveEvent {
+        MouseMoveEvent::new(
+            Mouse::new(mouse),
+            Point::new(from.0, from.1),
+            Point::new(to.0, to.1),
+        )
+    }
+
+    #[test]
+    fn test_double_click() {
+        let mut recognizer = GestureRecognizer::new();
+
+        // Without knowing the time, double-clicks can't be recognized
+        assert!(recognizer.mouse_click(click(0, 0.5, 0.5)).is_empty());
+        assert!(recognizer.mouse_click(click(0, 0.5, 0.5)).is_empty());
+
+        recognizer.tick(Duration::from_millis(1000));
+        assert!(recognizer.mouse_click(click(0, 0.5, 0.5)).is_empty());
+        recognizer.tick(Duration::from_millis(1200));
+        assert_eq!(
+            vec![GestureEvent::DoubleClick(DoubleClickEvent::new(
+                Mouse::new(0),
+                Point::new(0.51, 0.5),
+                MouseButton::primary()
+            ))],
+            recognizer.mouse_click(click(0, 0.51, 0.5))
+        );
+
+        // The third click should start a new potential double-click
+        assert!(recognizer.mouse_click(click(0, 0.5, 0.5)).is_empty());
+
+        // But not if it takes too long
+        recognizer.tick(Duration::from_millis(1800));
+        assert!(recognizer.mouse_click(click(0, 0.5, 0.5)).is_empty());
+
+        // Or if the clicks are too far apart
+        assert!(recognizer.mouse_click(click(0, 0.7, 0.5)).is_empty());
+
+        // Or if another mouse clicks
+        assert!(recognizer.mouse_click(click(1, 0.7, 0.5)).is_empty());
+    }
+
+    #[test]
+    fn test_long_press() {
+        let mut recognizer = GestureRecognizer::new();
+        recognizer.tick(Duration::from_millis(100));
+
+        recognizer.mouse_press(press(0, 0.2, 0.3));
+        assert!(recognizer.tick(Duration::from_millis(500)).is_empty());
+        assert_eq!(
+            vec![GestureEvent::LongPress(LongPressEvent::new(
+                Mouse::new(0),
+                Point::new(0.2, 0.3),
+                MouseButton::primary()
+            ))],
+            recognizer.tick(Duration::from_millis(600))
+        );
+
+        // It should only be fired once per press
+        assert!(recognizer.tick(Duration::from_millis(2000)).is_empty());
+        recognizer.mouse_release(release(0, 0.2, 0.3));
+
+        // Dragging should prevent long presses
+        recognizer.mouse_press(press(0, 0.2, 0.3));
+        recognizer.mouse_move(move_mouse(0, (0.2, 0.3), (0.5, 0.3)));
+        assert!(recognizer.tick(Duration::from_millis(5000)).is_empty());
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut recognizer = GestureRecognizer::new();
+        let mouse = Mouse::new(0);
+        let button = MouseButton::primary();
+
+        // Moving without pressing shouldn't cause drags
+        assert!(recognizer
+            .mouse_move(move_mouse(0, (0.1, 0.1), (0.5, 0.5)))
+            .is_empty());
+
+        recognizer.mouse_press(press(0, 0.5, 0.5));
+
+        // Tiny movements shouldn't start a drag
+        assert!(recognizer
+            .mouse_move(move_mouse(0, (0.5, 0.5), (0.51, 0.5)))
+            .is_empty());
+
+        assert_eq!(
+            vec![
+                GestureEvent::DragStart(DragStartEvent::new(mouse, button, Point::new(0.5, 0.5))),
+                GestureEvent::DragUpdate(DragUpdateEvent::new(
+                    mouse,
+                    button,
+                    Point::new(0.5, 0.5),
+                    Point::new(0.5, 0.5),
+                    Point::new(0.6, 0.5)
+                ))
+            ],
+            recognizer.mouse_move(move_mouse(0, (0.51, 0.5), (0.6, 0.5)))
+        );
+        assert_eq!(
+            vec![GestureEvent::DragUpdate(DragUpdateEvent::new(
+                mouse,
+                button,
+                Point::new(0.5, 0.5),
+                Point::new(0.6, 0.5),
+                Point::new(0.6, 0.6)
+            ))],
+            recognizer.mouse_move(move_mouse(0, (0.6, 0.5), (0.6, 0.6)))
+        );
+        assert_eq!(
+            vec![GestureEvent::DragEnd(DragEndEvent::new(
+                mouse,
+                button,
+                Point::new(0.5, 0.5),
+                Point::new(0.6, 0.6)
+            ))],
+            recognizer.mouse_release(release(0, 0.6, 0.6))
+        );
+
+        // Leaving the application should also end the drag
+        recognizer.mouse_press(press(0, 0.5, 0.5));
+        recognizer.mouse_move(move_mouse(0, (0.5, 0.5), (0.9, 0.5)));
+        assert_eq!(
+            vec![GestureEvent::DragEnd(DragEndEvent::new(
+                mouse,
+                button,
+                Point::new(0.5, 0.5),
+                Point::new(1.0, 0.5)
+            ))],
+            recognizer.mouse_leave(MouseLeaveEvent::new(mouse, Point::new(1.0, 0.5)))
+        );
+        assert!(recognizer
+            .mouse_move(move_mouse(0, (0.9, 0.5), (0.8, 0.5)))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_pinch() {
+        let mut recognizer = GestureRecognizer::new();
+        let mouses = (Mouse::new(1), Mouse::new(2));
+
+        recognizer.mouse_press(press(1, 0.4, 0.5));
+        recognizer.mouse_move(move_mouse(1, (0.4, 0.5), (0.3, 0.5)));
+
+        // Pressing the second mouse should end the drag of the first mouse
+        assert_eq!(
+            vec![GestureEvent::DragEnd(DragEndEvent::new(
+                mouses.0,
+                MouseButton::primary(),
+                Point::new(0.4, 0.5),
+                Point::new(0.3, 0.5)
+            ))],
+            recognizer.mouse_press(press(2, 0.7, 0.5))
+        );
+
+        // Moving the mouses apart should give a scale factor larger than 1
+        let events = recognizer.mouse_move(move_mouse(2, (0.7, 0.5), (1.1, 0.5)));
+        assert_eq!(1, events.len());
+        if let GestureEvent::Pinch(pinch) = events[0] {
+            assert_eq!(mouses, pinch.get_mouses());
+            assert!(pinch.get_start_center().nearly_equal(Point::new(0.5, 0.5)));
+            assert!(pinch.get_center().nearly_equal(Point::new(0.7, 0.5)));
+            assert!((pinch.get_scale_factor() - 2.0).abs() < 0.001);
+            assert!(pinch.get_rotation().abs() < 0.001);
+        } else {
+            panic!("Expected a pinch event, but got {:?}", events[0]);
+        }
+
+        // Rotating the second mouse around the first mouse
+        let events = recognizer.mouse_move(move_mouse(2, (1.1, 0.5), (0.3, 1.3)));
+        assert_eq!(1, events.len());
+        if let GestureEvent::Pinch(pinch) = events[0] {
+            assert!((pinch.get_scale_factor() - 1.0).abs() < 0.001);
+            assert!((pinch.get_rotation() - std::f32::consts::FRAC_PI_2).abs() < 0.001);
+        } else {
+            panic!("Expected a pinch event, but got {:?}", events[0]);
+        }
+
+        // Releasing either mouse should end the pinch, and the other mouse shouldn't start dragging
+        recognizer.mouse_release(release(1, 0.3, 0.5));
+        assert!(recognizer
+            .mouse_move(move_mouse(2, (0.3, 1.3), (0.2, 0.2)))
+            .is_empty());
+    }
+}
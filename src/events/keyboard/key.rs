@@ -0,0 +1,140 @@
+/// Represents a (physical) key on a keyboard, for instance the `Enter` key or the `A` key.
+///
+/// This enum is used by the `KeyPressEvent` and `KeyReleaseEvent` to indicate which key was
+/// pressed or released. It is also used by the `is_key_down` method of `ComponentBuddy`.
+///
+/// To keep this crate cross-platform, only the keys that are available on most keyboards are
+/// distinguished. The *wrapper* is responsible for converting the platform-specific key codes to
+/// instances of this enum, and will simply ignore keys that can't be represented.
+///
+/// ### Key versus character
+/// A `Key` only tells *which* key was pressed, not which character it produced: the `Letter('A')`
+/// key is the same key, regardless of whether shift was pressed and regardless of the keyboard
+/// layout. Components that want to know which text the user typed should use the `CharTypeEvent`
+/// instead.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Key {
+    /// One of the letter keys. The letter is always represented by an *uppercase* ASCII character
+    /// (from 'A' to 'Z').
+    Letter(char),
+    /// One of the digit keys (from 0 to 9). The *wrapper* doesn't distinguish the digit keys
+    /// above the letters from the digit keys on the numpad.
+    Digit(u8),
+    /// One of the function keys, represented by its number (F1 is `Function(1)`).
+    Function(u8),
+
+    Escape,
+    /// The enter (or return) key
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+
+    /// The left arrow key
+    Left,
+    /// The right arrow key
+    Right,
+    /// The up arrow key
+    Up,
+    /// The down arrow key
+    Down,
+
+    Space,
+    Minus,
+    Equals,
+    Comma,
+    Period,
+    Slash,
+    Backslash,
+    Semicolon,
+    Apostrophe,
+    LeftBracket,
+    RightBracket,
+    /// The grave accent (or backtick) key
+    Grave,
+
+    /// Either the left or the right shift key
+    Shift,
+    /// Either the left or the right control key
+    Control,
+    /// Either the left or the right alt key
+    Alt,
+    /// The meta key: the Windows key on Windows and the command key on Mac OS
+    Meta,
+    CapsLock,
+}
+
+/// Represents the state of the modifier keys (shift, control, alt, and meta) at the time a key
+/// event was fired.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyModifiers {
+    shift: bool,
+    control: bool,
+    alt: bool,
+    meta: bool,
+}
+
+impl KeyModifiers {
+    /// Constructs a new `KeyModifiers` with the given state. This function should normally only be
+    /// used by the *wrapper*.
+    pub const fn new(shift: bool, control: bool, alt: bool, meta: bool) -> Self {
+        Self {
+            shift,
+            control,
+            alt,
+            meta,
+        }
+    }
+
+    /// Constructs a `KeyModifiers` that indicates that none of the modifier keys is pressed.
+    pub const fn none() -> Self {
+        Self::new(false, false, false, false)
+    }
+
+    /// Checks whether (either of) the shift key(s) was down
+    pub fn is_shift_down(&self) -> bool {
+        self.shift
+    }
+
+    /// Checks whether (either of) the control key(s) was down
+    pub fn is_control_down(&self) -> bool {
+        self.control
+    }
+
+    /// Checks whether (either of) the alt key(s) was down
+    pub fn is_alt_down(&self) -> bool {
+        self.alt
+    }
+
+    /// Checks whether the meta key was down (the Windows key on Windows and the command key on
+    /// Mac OS)
+    pub fn is_meta_down(&self) -> bool {
+        self.meta
+    }
+
+    /// Checks whether none of the modifier keys was down
+    pub fn is_empty(&self) -> bool {
+        !self.shift && !self.control && !self.alt && !self.meta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KeyModifiers;
+
+    #[test]
+    fn test_modifiers() {
+        assert!(KeyModifiers::none().is_empty());
+        let modifiers = KeyModifiers::new(true, false, true, false);
+        assert!(!modifiers.is_empty());
+        assert!(modifiers.is_shift_down());
+        assert!(!modifiers.is_control_down());
+        assert!(modifiers.is_alt_down());
+        assert!(!modifiers.is_meta_down());
+    }
+}
@@ -0,0 +1,61 @@
+mod key;
+
+pub use key::*;
+
+/// This event is for the `on_key_press` method of `Component`. It indicates that the user pressed
+/// a key on the keyboard.
+///
+/// Use this event for keys that *do* something (like the arrow keys, `Enter`, or `Escape`) or for
+/// keyboard shortcuts. Use the `CharTypeEvent` to find out which text the user typed.
+///
+/// ### Limitations
+/// Like the `CharTypeEvent`, this event can only be fired if the user has some kind of keyboard.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyPressEvent {
+    key: Key,
+    modifiers: KeyModifiers,
+}
+
+impl KeyPressEvent {
+    /// Constructs a new `KeyPressEvent` with the given `Key` and `KeyModifiers`. This function
+    /// should normally only be used by the *wrapper*.
+    pub fn new(key: Key, modifiers: KeyModifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Gets the `Key` that was pressed
+    pub fn get_key(&self) -> Key {
+        self.key
+    }
+
+    /// Gets the state of the modifier keys at the time the key was pressed
+    pub fn get_modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+}
+
+/// This event is for the `on_key_release` method of `Component`. It indicates that the user
+/// released a key on the keyboard.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyReleaseEvent {
+    key: Key,
+    modifiers: KeyModifiers,
+}
+
+impl KeyReleaseEvent {
+    /// Constructs a new `KeyReleaseEvent` with the given `Key` and `KeyModifiers`. This function
+    /// should normally only be used by the *wrapper*.
+    pub fn new(key: Key, modifiers: KeyModifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Gets the `Key` that was released
+    pub fn get_key(&self) -> Key {
+        self.key
+    }
+
+    /// Gets the state of the modifier keys at the time the key was released
+    pub fn get_modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+}
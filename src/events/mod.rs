@@ -1,5 +1,17 @@
+mod animation_frame;
+mod drag_and_drop;
+mod gesture;
+mod keyboard;
 mod mouse;
+mod resize;
 mod text;
+mod timer;
 
+pub use animation_frame::*;
+pub use drag_and_drop::*;
+pub use gesture::*;
+pub use keyboard::*;
 pub use mouse::*;
+pub use resize::*;
 pub use text::*;
+pub use timer::*;
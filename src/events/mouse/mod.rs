@@ -316,3 +316,55 @@ impl MouseLeaveEvent {
         self.exit_point
     }
 }
+
+/// The event for the `on_mouse_scroll` method of `Component`. This event indicates
+/// that the user used the scroll wheel (or touchpad) while the mouse cursor was
+/// *on* the component.
+///
+/// The deltas are expressed in *lines*: the *wrapper* converts pixel-based scroll
+/// deltas to (fractional) lines. A positive `delta_y` means that the user scrolled
+/// up (away from the user) and a positive `delta_x` means that the user scrolled
+/// to the right.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MouseScrollEvent {
+    mouse: Mouse,
+    point: Point,
+    delta_x: f32,
+    delta_y: f32,
+}
+
+impl MouseScrollEvent {
+    /// Constructs a new `MouseScrollEvent` with the given mouse, relative mouse
+    /// cursor position (point) and scroll deltas
+    pub fn new(mouse: Mouse, point: Point, delta_x: f32, delta_y: f32) -> Self {
+        Self {
+            mouse,
+            point,
+            delta_x,
+            delta_y,
+        }
+    }
+
+    /// Gets the `Mouse` that was used to scroll
+    pub fn get_mouse(&self) -> Mouse {
+        self.mouse
+    }
+
+    /// Gets the position of the mouse cursor, relative to the component that
+    /// listens to this event
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    /// Gets the number of lines the user scrolled in the x-direction. Positive
+    /// values mean scrolling to the right.
+    pub fn get_delta_x(&self) -> f32 {
+        self.delta_x
+    }
+
+    /// Gets the number of lines the user scrolled in the y-direction. Positive
+    /// values mean scrolling up.
+    pub fn get_delta_y(&self) -> f32 {
+        self.delta_y
+    }
+}
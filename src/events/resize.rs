@@ -0,0 +1,34 @@
+/// This event is for the `on_resize` method of `Component`. It indicates that the size of the
+/// area on which the component is drawn changed, for instance because the user resized the window.
+///
+/// The size is expressed in pixels. Since the coordinates of components are always relative (from
+/// 0.0 to 1.0), most components won't need the exact size, but the aspect ratio can be important
+/// to avoid distortion.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ResizeEvent {
+    width: u32,
+    height: u32,
+}
+
+impl ResizeEvent {
+    /// Constructs a new `ResizeEvent` with the given `width` and `height` (in pixels). This
+    /// function should normally only be used by the *wrapper* and by menu components.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Gets the new width of the component, in pixels
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the new height of the component, in pixels
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the new aspect ratio of the component. This is just the width divided by the height.
+    pub fn get_aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+}
@@ -8,6 +8,7 @@
 /// Note that this event can only be fired if the user has some kind of
 /// keyboard. If no keyboard is available, only the `request_text_input` method
 /// of the component buddy can be used to ask the user for text input.
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub struct CharTypeEvent {
     text: String,
 }
@@ -25,3 +26,28 @@ impl CharTypeEvent {
         &self.text
     }
 }
+
+/// This event is for the `on_text_input` method of `Component`. It contains the answer of the user
+/// to a `request_text_input` call of the buddy of the component.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TextInputEvent {
+    text: Option<String>,
+}
+
+impl TextInputEvent {
+    /// Constructs a new `TextInputEvent` with the given `text`, which should be `None` if the user
+    /// cancelled. This function should normally only be used by the `Application`.
+    pub fn new(text: Option<String>) -> Self {
+        Self { text }
+    }
+
+    /// Gets the text that the user typed, or `None` if the user cancelled
+    pub fn get_text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Checks whether the user cancelled the text input
+    pub fn is_cancelled(&self) -> bool {
+        self.text.is_none()
+    }
+}
@@ -0,0 +1,19 @@
+/// This event is for the `on_timer` method of `Component`. It indicates that a timer that the
+/// component scheduled with the `schedule_timer` method of its buddy expired.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TimerEvent {
+    id: u32,
+}
+
+impl TimerEvent {
+    /// Constructs a new `TimerEvent` for the timer with the given `id`. This function should
+    /// normally only be used by the `Application` and by menu components.
+    pub fn new(id: u32) -> Self {
+        Self { id }
+    }
+
+    /// Gets the id that the component passed to `schedule_timer`
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+}
@@ -1,6 +1,7 @@
 #![feature(drain_filter)]
 #![feature(option_unwrap_none)]
 
+mod animation;
 mod application;
 mod component;
 mod components;
@@ -13,6 +14,7 @@ mod wrapper;
 mod renderer;
 mod texture;
 
+pub use animation::*;
 pub use application::*;
 pub use component::*;
 pub use components::*;
@@ -101,6 +101,11 @@ impl Renderer {
                 viewport_stack.pop();
                 let mut scissor_stack = self.scissor_stack.borrow_mut();
                 scissor_stack.pop();
+                drop(viewport_stack);
+                drop(scissor_stack);
+
+                // Restore the viewport and scissor of the caller
+                self.apply_viewport_and_scissor();
 
                 // Return the result
                 Some(result)
@@ -159,12 +164,14 @@ impl Renderer {
                 let mut scissor_stack = self.scissor_stack.borrow_mut();
                 scissor_stack.push(combined_scissor);
                 drop(scissor_stack);
+                self.apply_viewport_and_scissor();
 
                 let result = render_function();
 
                 let mut scissor_stack = self.scissor_stack.borrow_mut();
                 scissor_stack.pop();
                 drop(scissor_stack);
+                self.apply_viewport_and_scissor();
 
                 return Some(result);
             }
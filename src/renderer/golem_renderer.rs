@@ -27,6 +27,63 @@ impl Renderer {
         self.context.clear();
     }
 
+    /// Blends the given (partially transparent) `Color` over all pixels within the current viewport
+    /// and scissor. Unlike `clear`, this takes the alpha component of the color into account, which
+    /// makes it suitable for dimming or fading whatever has been drawn already.
+    pub fn blend_color(&self, color: Color) -> Result<(), GolemError> {
+        self.context
+            .set_blend_mode(Some(blend::BlendMode::default()));
+
+        let shader_id = ShaderId::from_strs("knukki", "Renderer.BlendColor");
+        let result = self.use_cached_shader(&shader_id, create_blend_color_shader, |shader| {
+            shader.set_uniform(
+                "color",
+                UniformValue::Vector4([
+                    color.get_red_float(),
+                    color.get_green_float(),
+                    color.get_blue_float(),
+                    color.get_alpha_float(),
+                ]),
+            )?;
+            unsafe {
+                shader.draw(
+                    self.get_quad_vertices(),
+                    self.get_quad_indices(),
+                    0..self.get_num_quad_indices(),
+                    GeometryMode::Triangles,
+                )
+            }
+        });
+
+        self.context.set_blend_mode(None);
+        result
+    }
+
+    /// Fills the largest oval that fits inside the current viewport with the given `Color`. The
+    /// oval will be a circle if the viewport is square. Like `clear`, this ignores the alpha
+    /// component of the color.
+    pub fn fill_oval(&self, color: Color) -> Result<(), GolemError> {
+        let shader_id = ShaderId::from_strs("knukki", "Renderer.FillOval");
+        self.use_cached_shader(&shader_id, create_fill_oval_shader, |shader| {
+            shader.set_uniform(
+                "color",
+                UniformValue::Vector3([
+                    color.get_red_float(),
+                    color.get_green_float(),
+                    color.get_blue_float(),
+                ]),
+            )?;
+            unsafe {
+                shader.draw(
+                    self.get_quad_vertices(),
+                    self.get_quad_indices(),
+                    0..self.get_num_quad_indices(),
+                    GeometryMode::Triangles,
+                )
+            }
+        })
+    }
+
     /// Gets the golem `Context` of this `Renderer`. Use this context to perform drawing operations
     /// that are not covered by the other methods of `Renderer` (currently, almost all components
     /// will need this, because the `Renderer` struct doesn't have many methods yet).
@@ -116,6 +173,59 @@ impl Renderer {
     }
 }
 
+#[rustfmt::skip]
+fn create_blend_color_shader(golem: &Context) -> Result<ShaderProgram, GolemError> {
+    let description = ShaderDescription {
+        vertex_input: &[
+            Attribute::new("position", AttributeType::Vector(Dimension::D2))
+        ],
+        fragment_input: &[],
+        uniforms: &[
+            Uniform::new("color", UniformType::Vector(NumberType::Float, Dimension::D4)),
+        ],
+        vertex_shader: "
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }",
+        fragment_shader: "
+            void main() {
+                gl_FragColor = color;
+            }",
+    };
+
+    ShaderProgram::new(golem, description)
+}
+
+#[rustfmt::skip]
+fn create_fill_oval_shader(golem: &Context) -> Result<ShaderProgram, GolemError> {
+    let description = ShaderDescription {
+        vertex_input: &[
+            Attribute::new("position", AttributeType::Vector(Dimension::D2))
+        ],
+        fragment_input: &[
+            Attribute::new("passPosition", AttributeType::Vector(Dimension::D2))
+        ],
+        uniforms: &[
+            Uniform::new("color", UniformType::Vector(NumberType::Float, Dimension::D3)),
+        ],
+        vertex_shader: "
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                passPosition = position;
+            }",
+        fragment_shader: "
+            void main() {
+                if (dot(passPosition, passPosition) <= 1.0) {
+                    gl_FragColor = vec4(color, 1.0);
+                } else {
+                    discard;
+                }
+            }",
+    };
+
+    ShaderProgram::new(golem, description)
+}
+
 pub(super) struct GolemRenderStorage {
     // Frequently used and cheap buffers
     quad_vertices: VertexBuffer,
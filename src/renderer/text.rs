@@ -40,6 +40,28 @@ impl TextRenderer {
         let mut internal = self.internal.borrow_mut();
         internal.draw_text(text, font, position, renderer)
     }
+
+    /// Computes where the boundaries between the graphemes of `text` would be if it were drawn at
+    /// the given `position` using `draw_text`, without actually drawing it. The result contains
+    /// the x-coordinate of the left side of each grapheme, followed by the x-coordinate of the
+    /// right side of the last grapheme (so it has 1 more element than the number of graphemes).
+    ///
+    /// The x-coordinates use the same coordinate system as the `TextDrawPosition`. This method
+    /// is useful for components that need to draw a cursor or selection in some text, or need to
+    /// find the grapheme that the user clicked on.
+    ///
+    /// This method returns `None` if the text couldn't be prepared for drawing. In that case,
+    /// `draw_text` will fail as well, and return the reason.
+    pub fn get_grapheme_offsets(
+        &self,
+        text: &str,
+        font: FontHandle,
+        position: TextDrawPosition,
+        renderer: &Renderer,
+    ) -> Option<Vec<f32>> {
+        let mut internal = self.internal.borrow_mut();
+        internal.get_grapheme_offsets(text, font, position, renderer).ok()
+    }
 }
 
 struct InternalTextRenderer {
@@ -79,6 +101,35 @@ impl InternalTextRenderer {
         position: TextDrawPosition,
         renderer: &Renderer,
     ) -> Result<DrawnTextPosition, TextRenderError> {
+        self.prepare_text_model(text, font, renderer)?;
+        self.draw_text_model(text, font, position, renderer)
+    }
+
+    pub fn get_grapheme_offsets(
+        &mut self,
+        text: &str,
+        font: FontHandle,
+        position: TextDrawPosition,
+        renderer: &Renderer,
+    ) -> Result<Vec<f32>, TextRenderError> {
+        self.prepare_text_model(text, font, renderer)?;
+
+        let model = &self.fonts[&font].string_models[text];
+        let (uniform_position, _) = compute_text_position(
+            model.width as f32, model.height as f32,
+            position, renderer.get_viewport()
+        );
+
+        Ok(model.grapheme_offsets.iter().map(
+            |offset| uniform_position.offset_x + uniform_position.scale_x * *offset as f32
+        ).collect())
+    }
+
+    fn prepare_text_model(
+        &mut self, text: &str, font: FontHandle,
+        #[allow(unused_variables)] // The renderer is only used when golem rendering is enabled
+        renderer: &Renderer
+    ) -> Result<(), TextRenderError> {
         if !self.fonts[&font].string_models.contains_key(text) {
             let text_model = self.create_text_model(
                 #[cfg(feature = "golem_rendering")]
@@ -88,8 +139,7 @@ impl InternalTextRenderer {
             )?;
             self.fonts.get_mut(&font).expect("Font handle is valid").string_models.insert(text.to_string(), text_model);
         }
-
-        self.draw_text_model(text, font, position, renderer)
+        Ok(())
     }
 
     // This seems to be a reasonable value. Perhaps, I could improve it later
@@ -121,7 +171,9 @@ impl InternalTextRenderer {
         // unique graphemes in 1 go on the texture atlas group because I didn't optimize groups for
         // such usage.
         let mut offset_x = 0;
+        let mut grapheme_offsets = Vec::new();
         let grapheme_positions: Vec<_> = text.graphemes(true).filter_map(|grapheme| {
+            grapheme_offsets.push(offset_x);
 
             let font = &entry.font;
             let atlas_group = &mut entry.atlas_group;
@@ -171,6 +223,7 @@ impl InternalTextRenderer {
         }).collect();
 
         let width = offset_x;
+        grapheme_offsets.push(width);
 
         // TODO Improve this for multi-line models
         let height = (entry.font.get_max_ascent(point_size) + entry.font.get_max_descent(point_size)).ceil() as u32;
@@ -211,6 +264,7 @@ impl InternalTextRenderer {
         Ok(TextModel {
             width,
             height,
+            grapheme_offsets,
 
             fragments,
             quads: text_vertices,
@@ -339,6 +393,7 @@ pub struct DrawnTextPosition {
     pub max_y: f32,
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct TextDrawPosition {
     pub min_x: f32,
     pub min_y: f32,
@@ -434,6 +489,8 @@ struct TextModel {
     quads: Vec<TextQuad>,
     width: u32,
     height: u32,
+    // The x-coordinate of the left side of each grapheme, followed by the width of the model
+    grapheme_offsets: Vec<u32>,
 
     #[allow(dead_code)] // This field is used in unit tests and when golem rendering is enabled
     fragments: Vec<TextModelFragment>,
@@ -854,6 +911,37 @@ mod tests {
 
         assert_eq!(1, text_model.fragments.len());
         assert_eq!(0, text_model.fragments[0].atlas_index);
+
+        let expected_offsets: Vec<_> = [0.0, 1.0, 1.8, 2.8, 3.6].iter().map(
+            |offset| (offset * point_size) as u32
+        ).collect();
+        assert_eq!(expected_offsets, text_model.grapheme_offsets);
+    }
+
+    #[test]
+    #[cfg(not(feature = "golem_rendering"))]
+    fn test_get_grapheme_offsets() {
+        let mut renderer = test_renderer(RenderRegion::with_size(0, 0, 360, 100));
+        let test_font_handle = renderer.text_renderer.register_font(Box::new(TestFont {}));
+
+        let position = TextDrawPosition {
+            min_x: -1.0,
+            min_y: -1.0,
+            max_x: 1.0,
+            max_y: 1.0,
+            horizontal_alignment: HorizontalTextAlignment::Left,
+            vertical_alignment: VerticalTextAlignment::Bottom,
+        };
+        let offsets = renderer.get_text_renderer().get_grapheme_offsets(
+            "a b ", test_font_handle, position, &renderer
+        ).expect("The test font should be able to prepare the text");
+
+        // The text fits exactly in the viewport, so it should span from -1.0 to 1.0
+        let expected_offsets = [-1.0, 1.0 / 1.8 - 1.0, 0.0, 2.8 / 1.8 - 1.0, 1.0];
+        assert_eq!(expected_offsets.len(), offsets.len());
+        for index in 0 .. offsets.len() {
+            assert!((expected_offsets[index] - offsets[index]).abs() < 0.001);
+        }
     }
 
     struct TestFont {}
@@ -1,11 +1,14 @@
-use crate::{Application, MouseEnterEvent, MouseLeaveEvent, MouseMoveEvent, RenderRegion, Renderer, MousePressEvent};
+use crate::{Application, MouseEnterEvent, MouseLeaveEvent, MouseMoveEvent, RenderRegion, Renderer, MousePressEvent, MouseScrollEvent, ResizeEvent, Key, KeyModifiers, KeyPressEvent, KeyReleaseEvent, CharTypeEvent, Color, TextDrawPosition, HorizontalTextAlignment, VerticalTextAlignment};
 
 use golem::*;
 
 use glutin::{
     dpi::PhysicalPosition,
     dpi::PhysicalSize,
-    event::{ElementState, Event, MouseButton, WindowEvent},
+    event::{
+        ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode,
+        WindowEvent,
+    },
     event_loop::{ControlFlow, EventLoop},
     window::Window,
     window::WindowBuilder,
@@ -13,10 +16,14 @@ use glutin::{
 };
 
 use golem::Dimension::D2;
+use unicode_segmentation::UnicodeSegmentation;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
 
+// Touchpads tend to report scrolling in pixels, but knukki expresses scroll deltas in lines
+const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
 pub fn start(mut app: Application, title: &str) {
     let event_loop = EventLoop::new();
     let builder = WindowBuilder::new()
@@ -50,13 +57,34 @@ pub fn start(mut app: Application, title: &str) {
 
     let mut start_time = Instant::now();
 
+    // The application wants to know the time since some fixed starting point
+    let app_start_time = Instant::now();
+
     let mut mouse_position: Option<PhysicalPosition<i32>> = None;
     let mut last_press_point: Option<crate::Point> = None;
     let mut pressed_buttons = Vec::with_capacity(2);
     let mut should_fire_mouse_enter_event = false;
 
+    // The modifiers are derived from this list rather than from winit because the modifiers field
+    // of KeyboardInput is deprecated. This stores the physical keys rather than the knukki keys,
+    // because knukki doesn't distinguish the left and right modifier keys: releasing the left
+    // shift key while the right one is still held down should not release Key::Shift.
+    let mut pressed_keycodes: Vec<VirtualKeyCode> = Vec::new();
+
+    // Winit fires a ReceivedCharacter event for each char, but knukki wants grapheme clusters, so
+    // the chars are collected here and split into graphemes once all events have been processed.
+    let mut typed_chars = String::new();
+
+    // This will be Some while the user is answering a text input request of the application
+    let mut text_input_overlay: Option<TextInputOverlay> = None;
+    let mut force_next_render = false;
+
     let mut render_surface: Option<Surface> = None;
 
+    // The application should know its size before it is rendered for the first time
+    let initial_size = windowed_context.window().inner_size();
+    app.fire_resize_event(ResizeEvent::new(initial_size.width, initial_size.height));
+
     event_loop.run(move |event, _target, control_flow| {
         // I use `Poll` instead of `Wait` to get more control over the control flow.
         // I use a simple custom system to avoid too large power usage
@@ -72,10 +100,13 @@ pub fn start(mut app: Application, title: &str) {
                 event: window_event,
             } => {
                 match window_event {
-                    WindowEvent::Resized(_) => {
-                        // TODO app.on_resize
+                    WindowEvent::Resized(new_size) => {
+                        app.fire_resize_event(ResizeEvent::new(new_size.width, new_size.height));
                         render_surface = None;
                     }
+                    WindowEvent::MouseInput { .. } if text_input_overlay.is_some() => {
+                        // The text input overlay blocks the mouse input of the application
+                    }
                     WindowEvent::MouseInput {
                         device_id: _,
                         state,
@@ -207,6 +238,34 @@ pub fn start(mut app: Application, title: &str) {
 
                         mouse_position = Some(position);
                     }
+                    WindowEvent::MouseWheel { .. } if text_input_overlay.is_some() => {
+                        // The text input overlay blocks the mouse input of the application
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        // Scroll events don't have a position, so we use the last known position
+                        if let Some(scroll_position) = mouse_position {
+                            let window_size = windowed_context.window().inner_size();
+                            let knukki_x = scroll_position.x as f32 / window_size.width as f32;
+                            let knukki_y =
+                                1.0 - (scroll_position.y as f32 / window_size.height as f32);
+
+                            // Both winit and knukki use positive y-values for scrolling up
+                            let (delta_x, delta_y) = match delta {
+                                MouseScrollDelta::LineDelta(x, y) => (x, y),
+                                MouseScrollDelta::PixelDelta(pixels) => (
+                                    pixels.x as f32 / PIXELS_PER_SCROLL_LINE,
+                                    pixels.y as f32 / PIXELS_PER_SCROLL_LINE,
+                                ),
+                            };
+
+                            app.fire_mouse_scroll_event(MouseScrollEvent::new(
+                                crate::Mouse::new(0),
+                                crate::Point::new(knukki_x, knukki_y),
+                                delta_x,
+                                delta_y,
+                            ));
+                        }
+                    }
                     WindowEvent::CursorEntered { .. } => {
                         should_fire_mouse_enter_event = true;
                     }
@@ -228,12 +287,86 @@ pub fn start(mut app: Application, title: &str) {
                         // won't be at this mouse position
                         mouse_position = None;
                     }
+                    WindowEvent::ReceivedCharacter(typed_char) => {
+                        // Control characters (like backspace and enter) are handled by the key
+                        // events instead
+                        if !typed_char.is_control() {
+                            match &mut text_input_overlay {
+                                Some(overlay) => overlay.type_char(typed_char),
+                                None => typed_chars.push(typed_char),
+                            }
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        input: KeyboardInput {
+                            state,
+                            virtual_keycode: Some(keycode),
+                            ..
+                        },
+                        ..
+                    } => {
+                        // Keys that can't be represented by knukki are simply ignored
+                        if let Some(key) = convert_key(keycode) {
+                            match state {
+                                ElementState::Pressed => {
+                                    if !pressed_keycodes.contains(&keycode) {
+                                        pressed_keycodes.push(keycode);
+                                    }
+
+                                    if let Some(overlay) = &mut text_input_overlay {
+                                        // The key presses are for the overlay rather than for the
+                                        // application. (The key releases are still passed to the
+                                        // application, to keep its keyboard state consistent.)
+                                        if let Some(answer) = overlay.press_key(key) {
+                                            text_input_overlay = None;
+                                            app.fire_text_input_event(answer);
+
+                                            // Get rid of the overlay pixels
+                                            force_next_render = true;
+                                        }
+                                    } else {
+                                        let modifiers = get_modifiers(&pressed_keycodes);
+                                        app.fire_key_press_event(KeyPressEvent::new(key, modifiers));
+                                    }
+                                }
+                                ElementState::Released => {
+                                    pressed_keycodes.retain(|pressed_code| *pressed_code != keycode);
+
+                                    // The key is still down if another physical key maps to it
+                                    let is_still_pressed = pressed_keycodes
+                                        .iter()
+                                        .any(|pressed_code| convert_key(*pressed_code) == Some(key));
+                                    if !is_still_pressed {
+                                        let modifiers = get_modifiers(&pressed_keycodes);
+                                        app.fire_key_release_event(KeyReleaseEvent::new(key, modifiers));
+                                    }
+                                }
+                            }
+                        }
+                    }
                     _ => (),
                 }
             }
             Event::MainEventsCleared => {
-                // Let the application decide whether it needs to redraw itself
-                let force = false;
+                // Fire the CharTypeEvents for the characters that were typed since the last time
+                if !typed_chars.is_empty() {
+                    for grapheme in typed_chars.graphemes(true) {
+                        app.fire_char_type_event(CharTypeEvent::new(grapheme.to_string()));
+                    }
+                    typed_chars.clear();
+                }
+
+                // Check if the application wants the user to type some text
+                if text_input_overlay.is_none() {
+                    if let Some(start_text) = app.take_text_input_request() {
+                        text_input_overlay = Some(TextInputOverlay::new(start_text));
+                    }
+                }
+
+                // Let the application decide whether it needs to redraw itself, unless the text
+                // input overlay was just closed
+                let force = force_next_render;
+                force_next_render = false;
 
                 // Draw onto the entire inner window buffer
                 let size = windowed_context.window().inner_size();
@@ -246,6 +379,8 @@ pub fn start(mut app: Application, title: &str) {
                 }
                 start_time = Instant::now();
 
+                app.tick(app_start_time.elapsed());
+
                 draw_application(
                     &mut app,
                     &mut renderer,
@@ -253,6 +388,7 @@ pub fn start(mut app: Application, title: &str) {
                     &mut render_surface,
                     size,
                     force,
+                    text_input_overlay.as_mut(),
                     &windowed_context,
                 )
                 .expect("Should be able to draw app");
@@ -272,6 +408,7 @@ pub fn start(mut app: Application, title: &str) {
                     &mut render_surface,
                     size,
                     force,
+                    text_input_overlay.as_mut(),
                     &windowed_context,
                 )
                 .expect("Should be able to force draw app");
@@ -287,6 +424,7 @@ pub fn start(mut app: Application, title: &str) {
         render_surface: &mut Option<Surface>,
         size: PhysicalSize<u32>,
         force: bool,
+        text_input_overlay: Option<&mut TextInputOverlay>,
         windowed_context: &ContextWrapper<PossiblyCurrent, Window>,
     ) -> Result<(), GolemError> {
         let region = RenderRegion::with_size(0, 0, size.width, size.height);
@@ -309,7 +447,17 @@ pub fn start(mut app: Application, title: &str) {
         // Draw the application on the render texture
         let render_surface = render_surface.as_ref().unwrap();
         renderer.reset_viewport(region);
-        if app.render(&renderer, force || created_surface) {
+        let mut should_present = app.render(&renderer, force || created_surface);
+
+        // The overlay must be drawn again whenever the application drew over it
+        if let Some(overlay) = text_input_overlay {
+            if should_present || overlay.changed {
+                overlay.draw(renderer)?;
+                should_present = true;
+            }
+        }
+
+        if should_present {
             // Draw the render texture onto the presenting texture
             Surface::unbind(renderer.get_context());
             renderer
@@ -379,3 +527,181 @@ pub fn start(mut app: Application, title: &str) {
         Ok((shader, vb, eb))
     }
 }
+
+/// A simple overlay that lets the user type text for the `request_text_input` method of
+/// `ComponentBuddy`. It will be drawn on top of the application until the user presses Enter (to
+/// confirm) or Escape (to cancel).
+struct TextInputOverlay {
+    text: String,
+    changed: bool,
+}
+
+impl TextInputOverlay {
+    fn new(start_text: String) -> Self {
+        Self {
+            text: start_text,
+            changed: true,
+        }
+    }
+
+    fn type_char(&mut self, typed_char: char) {
+        self.text.push(typed_char);
+        self.changed = true;
+    }
+
+    /// Returns `Some` answer if the user finished typing, and `None` otherwise
+    fn press_key(&mut self, key: Key) -> Option<Option<String>> {
+        match key {
+            Key::Enter => Some(Some(self.text.clone())),
+            Key::Escape => Some(None),
+            Key::Backspace => {
+                // Remove the last grapheme rather than the last char
+                if let Some((index, _)) = self.text.grapheme_indices(true).last() {
+                    self.text.truncate(index);
+                    self.changed = true;
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, renderer: &Renderer) -> Result<(), GolemError> {
+        self.changed = false;
+        let text_renderer = renderer.get_text_renderer();
+        let result = renderer.push_viewport(0.1, 0.4, 0.9, 0.6, || {
+            // Draw a border around the text
+            renderer.clear(Color::rgb(50, 50, 50));
+            renderer.push_viewport(0.01, 0.05, 0.99, 0.95, || {
+                renderer.clear(Color::rgb(240, 240, 240));
+
+                // The underscore serves as cursor, and ensures that the text is never empty
+                let position = TextDrawPosition {
+                    min_x: -0.98,
+                    min_y: -0.8,
+                    max_x: 0.98,
+                    max_y: 0.8,
+                    horizontal_alignment: HorizontalTextAlignment::Left,
+                    vertical_alignment: VerticalTextAlignment::Center,
+                };
+                text_renderer.draw_text(
+                    &format!("{}_", self.text),
+                    text_renderer.get_default_font(),
+                    position,
+                    renderer,
+                )
+            })
+        });
+
+        match result {
+            Some(Some(Err(error))) => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn get_modifiers(pressed_keycodes: &[VirtualKeyCode]) -> KeyModifiers {
+    let pressed_keys: Vec<Key> = pressed_keycodes
+        .iter()
+        .filter_map(|keycode| convert_key(*keycode))
+        .collect();
+    KeyModifiers::new(
+        pressed_keys.contains(&Key::Shift),
+        pressed_keys.contains(&Key::Control),
+        pressed_keys.contains(&Key::Alt),
+        pressed_keys.contains(&Key::Meta),
+    )
+}
+
+fn convert_key(keycode: VirtualKeyCode) -> Option<Key> {
+    use VirtualKeyCode::*;
+    let key = match keycode {
+        Key1 | Numpad1 => Key::Digit(1),
+        Key2 | Numpad2 => Key::Digit(2),
+        Key3 | Numpad3 => Key::Digit(3),
+        Key4 | Numpad4 => Key::Digit(4),
+        Key5 | Numpad5 => Key::Digit(5),
+        Key6 | Numpad6 => Key::Digit(6),
+        Key7 | Numpad7 => Key::Digit(7),
+        Key8 | Numpad8 => Key::Digit(8),
+        Key9 | Numpad9 => Key::Digit(9),
+        Key0 | Numpad0 => Key::Digit(0),
+
+        A => Key::Letter('A'),
+        B => Key::Letter('B'),
+        C => Key::Letter('C'),
+        D => Key::Letter('D'),
+        E => Key::Letter('E'),
+        F => Key::Letter('F'),
+        G => Key::Letter('G'),
+        H => Key::Letter('H'),
+        I => Key::Letter('I'),
+        J => Key::Letter('J'),
+        K => Key::Letter('K'),
+        L => Key::Letter('L'),
+        M => Key::Letter('M'),
+        N => Key::Letter('N'),
+        O => Key::Letter('O'),
+        P => Key::Letter('P'),
+        Q => Key::Letter('Q'),
+        R => Key::Letter('R'),
+        S => Key::Letter('S'),
+        T => Key::Letter('T'),
+        U => Key::Letter('U'),
+        V => Key::Letter('V'),
+        W => Key::Letter('W'),
+        X => Key::Letter('X'),
+        Y => Key::Letter('Y'),
+        Z => Key::Letter('Z'),
+
+        F1 => Key::Function(1),
+        F2 => Key::Function(2),
+        F3 => Key::Function(3),
+        F4 => Key::Function(4),
+        F5 => Key::Function(5),
+        F6 => Key::Function(6),
+        F7 => Key::Function(7),
+        F8 => Key::Function(8),
+        F9 => Key::Function(9),
+        F10 => Key::Function(10),
+        F11 => Key::Function(11),
+        F12 => Key::Function(12),
+
+        Escape => Key::Escape,
+        Return | NumpadEnter => Key::Enter,
+        Tab => Key::Tab,
+        Back => Key::Backspace,
+        Delete => Key::Delete,
+        Insert => Key::Insert,
+        Home => Key::Home,
+        End => Key::End,
+        PageUp => Key::PageUp,
+        PageDown => Key::PageDown,
+
+        Left => Key::Left,
+        Right => Key::Right,
+        Up => Key::Up,
+        Down => Key::Down,
+
+        Space => Key::Space,
+        Minus | Subtract => Key::Minus,
+        Equals => Key::Equals,
+        Comma => Key::Comma,
+        Period => Key::Period,
+        Slash | Divide => Key::Slash,
+        Backslash => Key::Backslash,
+        Semicolon => Key::Semicolon,
+        Apostrophe => Key::Apostrophe,
+        LBracket => Key::LeftBracket,
+        RBracket => Key::RightBracket,
+        Grave => Key::Grave,
+
+        LShift | RShift => Key::Shift,
+        LControl | RControl => Key::Control,
+        LAlt | RAlt => Key::Alt,
+        LWin | RWin => Key::Meta,
+        Capital => Key::CapsLock,
+        _ => return None,
+    };
+    Some(key)
+}
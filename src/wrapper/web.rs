@@ -12,6 +12,9 @@ use std::cell::{
 };
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::time::Duration;
+
+use unicode_segmentation::UnicodeSegmentation;
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{
@@ -20,17 +23,23 @@ use wasm_bindgen::{
 };
 
 use web_sys::{
+    AddEventListenerOptions,
     Document,
     Element,
     Event,
     HtmlCanvasElement,
     HtmlElement,
+    KeyboardEvent,
     MouseEvent,
+    PopStateEvent,
+    Touch,
+    TouchEvent,
     WebGlRenderingContext,
+    WheelEvent,
     window
 };
 
-pub fn start(app: Application, title: &str) {
+pub fn start(mut app: Application, title: &str) {
 
     // For the sake of debugging, binding the console is the first thing that must be done
     bind_console();
@@ -40,6 +49,9 @@ pub fn start(app: Application, title: &str) {
 
     let canvas = create_canvas();
 
+    // The browser has a built-in prompt, so the application can use that for text input
+    app.set_text_input_provider(Box::new(PromptTextInputProvider {}));
+
     // Any event can cause any component to request a redraw, so this must be shared between all
     // event handlers.
     let force_next_render = Rc::new(Cell::new(true));
@@ -47,9 +59,15 @@ pub fn start(app: Application, title: &str) {
     // Similarly, all event handlers must have access to the application
     let wrap_app = Rc::new(RefCell::new(app));
 
-    maintain_canvas_size(&canvas, Rc::clone(&force_next_render));
+    maintain_canvas_size(&canvas, &wrap_app, Rc::clone(&force_next_render));
     propagate_mouse_events(&wrap_app);
-    start_render_loop(&canvas, wrap_app, force_next_render);
+    propagate_touch_events(&wrap_app);
+    propagate_keyboard_events(&wrap_app);
+
+    // The number of history entries that were pushed for the navigation stack of the application
+    let history_depth = Rc::new(Cell::new(0));
+    propagate_history_events(&wrap_app, &history_depth);
+    start_render_loop(&canvas, wrap_app, force_next_render, history_depth);
 }
 
 fn bind_console() {
@@ -106,7 +124,8 @@ impl Serialize for ContextJSON {
 fn start_render_loop(
     canvas: &HtmlCanvasElement,
     wrap_app: Rc<RefCell<Application>>,
-    force_next_render: Rc<Cell<bool>>
+    force_next_render: Rc<Cell<bool>>,
+    history_depth: Rc<Cell<usize>>
 ) {
 
     let the_window = window().expect("There should be a window");
@@ -133,7 +152,7 @@ fn start_render_loop(
         RenderRegion::with_size(0, 0, 100, 100)
     );
 
-    let mut render_function = move || {
+    let mut render_function = move |timestamp: f64| {
         let scale_factor = get_scale_factor();
         let unscaled_width = get_window_width();
         let unscaled_height = get_window_height();
@@ -146,22 +165,28 @@ fn start_render_loop(
         renderer.reset_viewport(region);
 
         let mut app = wrap_app.borrow_mut();
+
+        // The timestamp of requestAnimationFrame is in milliseconds since the page was loaded
+        app.tick(Duration::from_secs_f64(timestamp / 1000.0));
         app.render(&renderer, force_next_render.get());
 
         force_next_render.set(false);
+
+        // The event handlers of the previous frame could have pushed or popped menus
+        sync_history(&app, &history_depth);
     };
 
-    let closure_rr: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
+    let closure_rr: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
     let closure_rr_inner = Rc::clone(&closure_rr);
 
-    let render_closure = Closure::wrap(Box::new(move || {
-        render_function();
+    let render_closure = Closure::wrap(Box::new(move |timestamp: f64| {
+        render_function(timestamp);
 
         let inner_render_closure = closure_rr_inner.borrow();
         the_window.request_animation_frame(
             inner_render_closure.as_ref().unwrap().as_ref().unchecked_ref()
         ).expect("Should be able to continue requestAnimationFrame");
-    }) as Box<dyn FnMut()>);
+    }) as Box<dyn FnMut(f64)>);
 
     closure_rr.replace(Some(render_closure));
 
@@ -173,6 +198,9 @@ fn start_render_loop(
     ).expect("Should be able to initiate requestAnimationFrame");
 }
 
+// Browsers usually report scrolling in pixels, but knukki expresses scroll deltas in lines
+const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
 fn propagate_mouse_events(
     wrap_app: &Rc<RefCell<Application>>
 ) {
@@ -213,6 +241,7 @@ fn propagate_mouse_events(
     let move_wrap_app = Rc::clone(wrap_app);
     let enter_wrap_app = Rc::clone(wrap_app);
     let leave_wrap_app = Rc::clone(wrap_app);
+    let scroll_wrap_app = Rc::clone(wrap_app);
 
     let press_point_rc_press = Rc::clone(&last_press_point_rc);
     let press_point_rc_click = Rc::clone(&last_press_point_rc);
@@ -325,6 +354,25 @@ fn propagate_mouse_events(
         mouse_point_rc_leave.set(None);
     }) as Box<dyn FnMut(MouseEvent)>);
 
+    let scroll_closure = Closure::wrap(Box::new(move |event: WheelEvent| {
+        // The DOM uses positive y-values for scrolling down, but knukki uses positive y-values
+        // for scrolling up. Also, knukki expresses the deltas in lines rather than pixels.
+        let lines_per_unit = match event.delta_mode() {
+            WheelEvent::DOM_DELTA_PIXEL => 1.0 / PIXELS_PER_SCROLL_LINE,
+            WheelEvent::DOM_DELTA_LINE => 1.0,
+            // A page is roughly as high as the window
+            _ => get_window_height() as f32 / PIXELS_PER_SCROLL_LINE,
+        };
+
+        let mut app = scroll_wrap_app.borrow_mut();
+        app.fire_mouse_scroll_event(MouseScrollEvent::new(
+            primary_mouse,
+            Point::new(get_x(&event), get_y(&event)),
+            event.delta_x() as f32 * lines_per_unit,
+            -event.delta_y() as f32 * lines_per_unit
+        ));
+    }) as Box<dyn FnMut(WheelEvent)>);
+
     let context_closure = Closure::wrap(Box::new(|event: Event| {
         event.prevent_default();
     }) as Box<dyn FnMut(Event)>);
@@ -343,6 +391,8 @@ fn propagate_mouse_events(
         .expect("Should be able to add mouseover listener");
     the_window.add_event_listener_with_callback("mouseout", leave_closure.as_ref().unchecked_ref())
         .expect("Should be able to add mouseout listener");
+    the_window.add_event_listener_with_callback("wheel", scroll_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add wheel listener");
     the_window.add_event_listener_with_callback("contextmenu", context_closure.as_ref().unchecked_ref())
         .expect("Should be able to add contextmenu listener");
 
@@ -352,19 +402,380 @@ fn propagate_mouse_events(
     move_closure.forget();
     enter_closure.forget();
     leave_closure.forget();
+    scroll_closure.forget();
     context_closure.forget();
 }
 
-fn maintain_canvas_size(canvas: &HtmlCanvasElement, force_next_render: Rc<Cell<bool>>) {
+fn propagate_touch_events(
+    wrap_app: &Rc<RefCell<Application>>
+) {
+    let the_window = window().expect("Expected a window");
+
+    fn get_point(touch: &Touch) -> Point {
+        let x = touch.client_x() as f32 / get_window_width() as f32;
+        let y = 1.0 - touch.client_y() as f32 / get_window_height() as f32;
+
+        // Fingers can move outside the window while touching it, but the Application expects all
+        // mouse positions to be inside the window
+        Point::new(x.max(0.0).min(1.0), y.max(0.0).min(1.0))
+    }
+
+    fn get_changed_touches(event: &TouchEvent) -> Vec<Touch> {
+        let touch_list = event.changed_touches();
+        (0..touch_list.length()).filter_map(|index| touch_list.item(index)).collect()
+    }
+
+    struct ActiveTouch {
+        identifier: i32,
+        mouse: Mouse,
+        point: Point,
+        press_point: Point
+    }
+
+    // Every finger gets its own Mouse. Mouse 0 is reserved for the regular DOM mouse events, and
+    // the ids of lifted fingers will be reused.
+    fn claim_mouse(active_touches: &[ActiveTouch]) -> Mouse {
+        let mut id = 1;
+        while active_touches.iter().any(|active_touch| active_touch.mouse == Mouse::new(id)) {
+            id += 1;
+        }
+        Mouse::new(id)
+    }
+
+    // Touching is treated like pressing the primary mouse button
+    fn touch_button() -> MouseButton {
+        MouseButton::primary()
+    }
+
+    fn finish_touches(
+        event: &TouchEvent, app: &mut Application, active_touches: &mut Vec<ActiveTouch>, cancelled: bool
+    ) {
+        for touch in get_changed_touches(event) {
+            let maybe_index = active_touches.iter().position(
+                |active_touch| active_touch.identifier == touch.identifier()
+            );
+            if let Some(index) = maybe_index {
+                let active_touch = active_touches.remove(index);
+                let point = get_point(&touch);
+
+                if point != active_touch.point {
+                    app.fire_mouse_move_event(MouseMoveEvent::new(
+                        active_touch.mouse, active_touch.point, point
+                    ));
+                }
+                app.fire_mouse_release_event(MouseReleaseEvent::new(
+                    active_touch.mouse, point, touch_button()
+                ));
+
+                // Just like with regular mouses, drags shouldn't count as clicks
+                if !cancelled && point.distance_to(active_touch.press_point) < 0.1 {
+                    app.fire_mouse_click_event(MouseClickEvent::new(
+                        active_touch.mouse, point, touch_button()
+                    ));
+                }
+
+                // The finger is no longer on the screen, so it should 'leave' the application
+                app.fire_mouse_leave_event(MouseLeaveEvent::new(active_touch.mouse, point));
+            }
+        }
+    }
+
+    let active_touches_rc = Rc::new(RefCell::new(Vec::new()));
+
+    let start_wrap_app = Rc::clone(wrap_app);
+    let move_wrap_app = Rc::clone(wrap_app);
+    let end_wrap_app = Rc::clone(wrap_app);
+    let cancel_wrap_app = Rc::clone(wrap_app);
+
+    let active_touches_rc_start = Rc::clone(&active_touches_rc);
+    let active_touches_rc_move = Rc::clone(&active_touches_rc);
+    let active_touches_rc_end = Rc::clone(&active_touches_rc);
+    let active_touches_rc_cancel = Rc::clone(&active_touches_rc);
+
+    let start_closure = Closure::wrap(Box::new(move |event: TouchEvent| {
+        // This prevents the browser from firing emulated mouse events for the same touch
+        event.prevent_default();
+
+        let mut app = start_wrap_app.borrow_mut();
+        let mut active_touches = active_touches_rc_start.borrow_mut();
+        for touch in get_changed_touches(&event) {
+            let mouse = claim_mouse(&active_touches);
+            let point = get_point(&touch);
+
+            // Unlike regular mouses, fingers appear out of nowhere, so they 'enter' the application
+            // right before pressing it
+            app.fire_mouse_enter_event(MouseEnterEvent::new(mouse, point));
+            app.fire_mouse_press_event(MousePressEvent::new(mouse, point, touch_button()));
+
+            active_touches.push(ActiveTouch {
+                identifier: touch.identifier(),
+                mouse,
+                point,
+                press_point: point
+            });
+        }
+    }) as Box<dyn FnMut(TouchEvent)>);
+
+    let move_closure = Closure::wrap(Box::new(move |event: TouchEvent| {
+        event.prevent_default();
+
+        let mut app = move_wrap_app.borrow_mut();
+        let mut active_touches = active_touches_rc_move.borrow_mut();
+        for touch in get_changed_touches(&event) {
+            let maybe_active_touch = active_touches.iter_mut().find(
+                |active_touch| active_touch.identifier == touch.identifier()
+            );
+            if let Some(active_touch) = maybe_active_touch {
+                let new_point = get_point(&touch);
+
+                // Protect the Application from 0-length move events
+                if new_point != active_touch.point {
+                    app.fire_mouse_move_event(MouseMoveEvent::new(
+                        active_touch.mouse, active_touch.point, new_point
+                    ));
+                    active_touch.point = new_point;
+                }
+            }
+        }
+    }) as Box<dyn FnMut(TouchEvent)>);
+
+    let end_closure = Closure::wrap(Box::new(move |event: TouchEvent| {
+        event.prevent_default();
+
+        let mut app = end_wrap_app.borrow_mut();
+        let mut active_touches = active_touches_rc_end.borrow_mut();
+        finish_touches(&event, &mut app, &mut active_touches, false);
+    }) as Box<dyn FnMut(TouchEvent)>);
+
+    let cancel_closure = Closure::wrap(Box::new(move |event: TouchEvent| {
+        let mut app = cancel_wrap_app.borrow_mut();
+        let mut active_touches = active_touches_rc_cancel.borrow_mut();
+        finish_touches(&event, &mut app, &mut active_touches, true);
+    }) as Box<dyn FnMut(TouchEvent)>);
+
+    // Browsers treat touch listeners on the window as passive by default, which would make them
+    // ignore the prevent_default calls
+    let mut options = AddEventListenerOptions::new();
+    options.set_passive(false);
+
+    the_window.add_event_listener_with_callback_and_add_event_listener_options(
+        "touchstart", start_closure.as_ref().unchecked_ref(), &options
+    ).expect("Should be able to add touchstart listener");
+    the_window.add_event_listener_with_callback_and_add_event_listener_options(
+        "touchmove", move_closure.as_ref().unchecked_ref(), &options
+    ).expect("Should be able to add touchmove listener");
+    the_window.add_event_listener_with_callback_and_add_event_listener_options(
+        "touchend", end_closure.as_ref().unchecked_ref(), &options
+    ).expect("Should be able to add touchend listener");
+    the_window.add_event_listener_with_callback_and_add_event_listener_options(
+        "touchcancel", cancel_closure.as_ref().unchecked_ref(), &options
+    ).expect("Should be able to add touchcancel listener");
+
+    start_closure.forget();
+    move_closure.forget();
+    end_closure.forget();
+    cancel_closure.forget();
+}
+
+fn propagate_keyboard_events(
+    wrap_app: &Rc<RefCell<Application>>
+) {
+    let the_window = window().expect("Expected a window");
+
+    fn get_modifiers(event: &KeyboardEvent) -> KeyModifiers {
+        KeyModifiers::new(event.shift_key(), event.ctrl_key(), event.alt_key(), event.meta_key())
+    }
+
+    let press_wrap_app = Rc::clone(wrap_app);
+    let release_wrap_app = Rc::clone(wrap_app);
+
+    let press_closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        let mut app = press_wrap_app.borrow_mut();
+
+        // Keys that can't be represented by knukki are simply ignored
+        if let Some(key) = convert_key(&event.code()) {
+            app.fire_key_press_event(KeyPressEvent::new(key, get_modifiers(&event)));
+        }
+
+        // The key property of the event is the typed text if the key produces text, and the name
+        // of the key otherwise (for instance "Enter" or "Shift"). Since names of keys always
+        // consist of more than 1 grapheme, this can be used to distinguish them. Keyboard
+        // shortcuts (like ctrl+c) shouldn't type any text.
+        // Note: the 'input' event is not used because it is only fired at editable elements, and
+        // the canvas is not one of them.
+        let text = event.key();
+        if !event.ctrl_key() && !event.meta_key() && !event.is_composing()
+            && text.graphemes(true).count() == 1 {
+            app.fire_char_type_event(CharTypeEvent::new(text));
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    let release_closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if let Some(key) = convert_key(&event.code()) {
+            let mut app = release_wrap_app.borrow_mut();
+            app.fire_key_release_event(KeyReleaseEvent::new(key, get_modifiers(&event)));
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    the_window.add_event_listener_with_callback("keydown", press_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add keydown listener");
+    the_window.add_event_listener_with_callback("keyup", release_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add keyup listener");
+
+    press_closure.forget();
+    release_closure.forget();
+}
+
+/// Lets the back and forward buttons of the browser control the navigation stack of the
+/// application. Every history entry pushed by knukki stores the navigation depth as its state.
+fn propagate_history_events(
+    wrap_app: &Rc<RefCell<Application>>,
+    history_depth: &Rc<Cell<usize>>
+) {
+    let the_window = window().expect("Expected a window");
+    let history = the_window.history().expect("Expected history");
+
+    // The initial entry represents the initial root component
+    history.replace_state(&JsValue::from_f64(0.0), "")
+        .expect("Should be able to replace the initial history state");
+
+    let popstate_wrap_app = Rc::clone(wrap_app);
+    let popstate_history_depth = Rc::clone(history_depth);
+
+    let popstate_closure = Closure::wrap(Box::new(move |event: PopStateEvent| {
+        // Entries without state were not pushed by knukki, so they belong to the initial menu
+        let target_depth = event.state().as_f64().unwrap_or(0.0) as usize;
+
+        let mut app = popstate_wrap_app.borrow_mut();
+        while app.get_navigation_depth() > target_depth && app.go_back() {}
+        while app.get_navigation_depth() < target_depth && app.go_forward() {}
+        popstate_history_depth.set(app.get_navigation_depth());
+    }) as Box<dyn FnMut(PopStateEvent)>);
+
+    the_window.add_event_listener_with_callback("popstate", popstate_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add popstate listener");
+
+    popstate_closure.forget();
+}
+
+/// Pushes or pops history entries when the components of the application pushed or popped menus
+fn sync_history(app: &Application, history_depth: &Cell<usize>) {
+    let depth = app.get_navigation_depth();
+    let old_depth = history_depth.get();
+    if depth == old_depth {
+        return;
+    }
+
+    let history = window().expect("Expected a window").history().expect("Expected history");
+    if depth > old_depth {
+        for new_depth in old_depth + 1 ..= depth {
+            history.push_state(&JsValue::from_f64(new_depth as f64), "")
+                .expect("Should be able to push history state");
+        }
+    } else {
+        // This will cause a popstate event, but the application will already have the right depth
+        history.go_with_delta(depth as i32 - old_depth as i32)
+            .expect("Should be able to go back in history");
+    }
+    history_depth.set(depth);
+}
+
+/// Converts the *code* of a JS KeyboardEvent to a knukki `Key`. The code is used rather than the
+/// key because the code describes the physical key, regardless of the keyboard layout.
+fn convert_key(code: &str) -> Option<Key> {
+    if code.len() == 4 && code.starts_with("Key") {
+        return code.chars().nth(3).map(|letter| Key::Letter(letter));
+    }
+    if code.len() == 6 && code.starts_with("Digit") {
+        return code[5..].parse().ok().map(|digit| Key::Digit(digit));
+    }
+    if code.len() == 7 && code.starts_with("Numpad") {
+        if let Ok(digit) = code[6..].parse() {
+            return Some(Key::Digit(digit));
+        }
+    }
+    if code.starts_with('F') {
+        if let Ok(number) = code[1..].parse() {
+            return Some(Key::Function(number));
+        }
+    }
+
+    let key = match code {
+        "Escape" => Key::Escape,
+        "Enter" | "NumpadEnter" => Key::Enter,
+        "Tab" => Key::Tab,
+        "Backspace" => Key::Backspace,
+        "Delete" => Key::Delete,
+        "Insert" => Key::Insert,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+
+        "ArrowLeft" => Key::Left,
+        "ArrowRight" => Key::Right,
+        "ArrowUp" => Key::Up,
+        "ArrowDown" => Key::Down,
+
+        "Space" => Key::Space,
+        "Minus" | "NumpadSubtract" => Key::Minus,
+        "Equal" => Key::Equals,
+        "Comma" => Key::Comma,
+        "Period" => Key::Period,
+        "Slash" | "NumpadDivide" => Key::Slash,
+        "Backslash" => Key::Backslash,
+        "Semicolon" => Key::Semicolon,
+        "Quote" => Key::Apostrophe,
+        "BracketLeft" => Key::LeftBracket,
+        "BracketRight" => Key::RightBracket,
+        "Backquote" => Key::Grave,
+
+        "ShiftLeft" | "ShiftRight" => Key::Shift,
+        "ControlLeft" | "ControlRight" => Key::Control,
+        "AltLeft" | "AltRight" => Key::Alt,
+        "MetaLeft" | "MetaRight" | "OSLeft" | "OSRight" => Key::Meta,
+        "CapsLock" => Key::CapsLock,
+        _ => return None
+    };
+    Some(key)
+}
+
+struct PromptTextInputProvider {}
+
+impl TextInputProvider for PromptTextInputProvider {
+    fn provide_text_input(&mut self, start_text: String) -> Option<String> {
+        let the_window = window().expect("Expected a window");
+
+        // The prompt blocks the entire page until the user is finished, which is exactly what the
+        // request_text_input method of ComponentBuddy promises
+        match the_window.prompt_with_message_and_default("", &start_text) {
+            Ok(result) => result,
+            Err(error) => {
+                log::error!("Failed to prompt the user for text input: {:?}", error);
+                None
+            }
+        }
+    }
+}
+
+fn maintain_canvas_size(
+    canvas: &HtmlCanvasElement,
+    wrap_app: &Rc<RefCell<Application>>,
+    force_next_render: Rc<Cell<bool>>
+) {
     let the_window = window().expect("Expected a window");
 
+    // The application should know its size before it is rendered for the first time
+    fire_resize_event(canvas, &mut wrap_app.borrow_mut());
+
     // Note: This is a clone of a reference to the JS canvas; not a clone of the actual canvas
     let canvas = canvas.clone();
+    let wrap_app = Rc::clone(wrap_app);
 
     let resize_closure = Closure::wrap(Box::new(move || {
         set_canvas_size(&canvas);
         force_next_render.set(true);
-        // TODO Fire resize event
+        fire_resize_event(&canvas, &mut wrap_app.borrow_mut());
     }) as Box<dyn FnMut()>);
 
     the_window.add_event_listener_with_callback(
@@ -374,6 +785,11 @@ fn maintain_canvas_size(canvas: &HtmlCanvasElement, force_next_render: Rc<Cell<b
     resize_closure.forget();
 }
 
+fn fire_resize_event(canvas: &HtmlCanvasElement, app: &mut Application) {
+    // The size of the canvas is the (scaled) size that is used for rendering
+    app.fire_resize_event(ResizeEvent::new(canvas.width(), canvas.height()));
+}
+
 fn set_canvas_size(canvas: &HtmlCanvasElement) {
     let unscaled_width = get_window_width();
     let unscaled_height = get_window_height();